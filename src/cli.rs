@@ -118,6 +118,10 @@ pub struct BenchmarkParameters {
     use_bp: bool,
     #[clap(long, action)]
     bp_application_ratio: Option<f64>,
+    /// override the edge weights of the code, e.g. from BP: a file with one weight per edge per line, either as a
+    /// fraction `num/den` or as a decimal
+    #[clap(long)]
+    weights: Option<String>,
 }
 
 #[derive(Subcommand, Clone, Debug)]
@@ -314,6 +318,7 @@ impl Cli {
                 single_seed,
                 use_bp,
                 bp_application_ratio,
+                weights,
             }) => {
                 // whether to disable progress bar, useful when running jobs in background
                 #[cfg(feature = "progress_bar")]
@@ -332,7 +337,15 @@ impl Cli {
                     code.set_erasure_probability(pe);
                 }
                 // create initializer and solver
-                let initializer = Arc::new(code.get_initializer());
+                let mut initializer = code.get_initializer();
+                if let Some(weights_filepath) = weights.as_ref() {
+                    let weights = read_edge_weights(weights_filepath, initializer.weighted_edges.len())
+                        .unwrap_or_else(|error| panic!("invalid weights file {weights_filepath}: {error}"));
+                    for (hyperedge, weight) in initializer.weighted_edges.iter_mut().zip(weights) {
+                        hyperedge.weight = weight;
+                    }
+                }
+                let initializer = Arc::new(initializer);
                 let mut solver = solver_type.build(&initializer, &*code, solver_config.clone());
                 if use_bp {
                     solver = match SolverBPWrapper::new(solver.solver_base(), 1, bp_application_ratio.unwrap_or(0.1))
@@ -559,6 +572,43 @@ pub fn execute_in_cli<'a>(iter: impl Iterator<Item = &'a String> + Clone, print_
     Cli::parse_from(iter).run();
 }
 
+/// parse an edge weight written either as a fraction `num/den` or as a decimal
+pub fn parse_weight(text: &str) -> Result<Weight, String> {
+    let text = text.trim();
+    let weight = match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            let parse = |value: &str| value.trim().parse::<i64>().map_err(|error| format!("{text:?}: {error}"));
+            let (numerator, denominator) = (parse(numerator)?, parse(denominator)?);
+            if denominator == 0 {
+                return Err(format!("{text:?}: zero denominator"));
+            }
+            Weight::from_i64(numerator).unwrap() / Weight::from_i64(denominator).unwrap()
+        }
+        None => {
+            let value = text.parse::<f64>().map_err(|error| format!("{text:?}: {error}"))?;
+            Weight::from_f64(value).ok_or_else(|| format!("{text:?}: not a finite number"))?
+        }
+    };
+    Ok(weight)
+}
+
+/// read one weight per edge per line (see [`parse_weight`]), skipping blank lines;
+/// the number of weights must match the number of edges
+pub fn read_edge_weights(filepath: &str, edge_num: usize) -> Result<Vec<Weight>, String> {
+    let content = std::fs::read_to_string(filepath).map_err(|error| error.to_string())?;
+    let weights = (content.lines())
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_weight)
+        .collect::<Result<Vec<_>, _>>()?;
+    if weights.len() != edge_num {
+        return Err(format!(
+            "expected {edge_num} weights, one per edge, but found {}",
+            weights.len()
+        ));
+    }
+    Ok(weights)
+}
+
 impl ExampleCodeType {
     fn build(
         &self,
@@ -725,3 +775,45 @@ impl ResultVerifier for VerifierActualError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn cli_read_edge_weights() {
+        // cargo test cli_read_edge_weights -- --nocapture
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        // make the boundary edge 3 of vertex 0 expensive, so that the defect is matched through the other boundary
+        let mut weights_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(weights_file, "1\n2/2\n1.0\n\n10\n1").unwrap();
+        let weights_filepath = weights_file.path().to_str().unwrap().to_string();
+        let weights = read_edge_weights(&weights_filepath, code.edge_num()).unwrap();
+        assert_eq!(weights[1], Weight::from_usize(1).unwrap());
+        assert_eq!(weights[3], Weight::from_usize(10).unwrap());
+        assert!(read_edge_weights(&weights_filepath, code.edge_num() + 1)
+            .unwrap_err()
+            .starts_with("expected 6 weights"));
+        assert!(parse_weight("1/0").is_err());
+        assert!(parse_weight("abc").is_err());
+        let decode = |initializer: SolverInitializer| {
+            let mut solver = SolverSerialJointSingleHair::new(&Arc::new(initializer), json!({}));
+            solver.solve(SyndromePattern::new_vertices(vec![0]));
+            let mut subgraph: Vec<EdgeIndex> = solver.subgraph().into_iter().collect();
+            subgraph.sort();
+            subgraph
+        };
+        assert_eq!(decode(code.get_initializer()), vec![3]);
+        let mut initializer = code.get_initializer();
+        for (hyperedge, weight) in initializer.weighted_edges.iter_mut().zip(weights) {
+            hyperedge.weight = weight;
+        }
+        assert_eq!(decode(initializer), vec![0, 1, 2, 4]);
+        // the same file is accepted by the command line
+        let command = format!(
+            "mwpf benchmark 5 0.1 -c code-capacity-repetition-code -p joint-single-hair --single-seed 1 --weights {weights_filepath}"
+        );
+        execute_in_cli(command.split(' ').map(String::from).collect::<Vec<_>>().iter(), false);
+    }
+}
@@ -7,17 +7,17 @@ use crate::decoding_hypergraph::*;
 use crate::derivative::Derivative;
 use crate::invalid_subgraph::*;
 use crate::model_hypergraph::*;
-use crate::num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+use crate::num_traits::{FromPrimitive, One, Signed, Zero};
 use crate::pointers::*;
 use crate::primal_module::Affinity;
 use crate::primal_module_serial::PrimalClusterPtr;
 use crate::relaxer_optimizer::OptimizerResult;
 use crate::util::*;
 use crate::visualize::*;
-use hashbrown::HashSet;
 #[cfg(feature = "python_binding")]
 use pyo3::prelude::*;
 
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
@@ -51,6 +51,80 @@ impl DualModuleMode {
     }
 }
 
+/// the coefficients of [`DualModuleImpl::calculate_cluster_affinity`]: `gap_weight * gap - size_penalty * size`,
+/// where the size of a cluster is the number of its hyperedges and dual variables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AffinityConfig {
+    /// how much a larger primal-dual gap (more room for improvement) raises the affinity
+    #[serde(default = "affinity_default_configs::gap_weight")]
+    pub gap_weight: f64,
+    /// how much a larger cluster (more expensive to work on) lowers the affinity, e.g. raise it on memory-constrained runs
+    #[serde(default = "affinity_default_configs::size_penalty")]
+    pub size_penalty: f64,
+}
+
+pub mod affinity_default_configs {
+    pub fn gap_weight() -> f64 {
+        1.
+    }
+    pub fn size_penalty() -> f64 {
+        1.
+    }
+}
+
+impl Default for AffinityConfig {
+    fn default() -> Self {
+        Self {
+            gap_weight: affinity_default_configs::gap_weight(),
+            size_penalty: affinity_default_configs::size_penalty(),
+        }
+    }
+}
+
+impl AffinityConfig {
+    pub fn affinity(&self, cluster_size: usize, gap: &Rational) -> Affinity {
+        Affinity::from(self.gap_weight * rational_to_f64_lossy(gap) - self.size_penalty * cluster_size as f64)
+    }
+}
+
+/// what [`DualModuleImpl::grow`] does when a dual variable is grown past its shrink-to-zero event, which only happens
+/// when a caller grows by more than the valid growth reported by [`DualModuleImpl::report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverShrinkPolicy {
+    /// no check at runtime, only a debug assertion
+    #[default]
+    Unchecked,
+    /// floor the dual variable at zero and print a warning, only once per process: the dual node stops shrinking at its
+    /// shrink-to-zero time, with its grow rate set to zero, and so do its hair edges
+    Clamp,
+    /// panic with the offending dual node
+    Strict,
+}
+
+/// whether [`OverShrinkPolicy::Clamp`] has already printed its warning
+static OVER_SHRINK_WARNING_LOGGED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+impl OverShrinkPolicy {
+    /// the value to keep for a dual variable that has just been grown to `dual_variable`
+    pub fn apply(&self, node_index: NodeIndex, dual_variable: Rational) -> Rational {
+        match self {
+            Self::Unchecked => {
+                debug_assert!(!dual_variable.is_negative(), "negative dual variable");
+                dual_variable
+            }
+            _ if !dual_variable.is_negative() => dual_variable,
+            Self::Clamp => {
+                if !OVER_SHRINK_WARNING_LOGGED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+                    eprintln!("[warning] dual node {node_index} is shrunk to {dual_variable:?}, clamped to zero");
+                }
+                Rational::zero()
+            }
+            Self::Strict => panic!("dual node {node_index} is shrunk to {dual_variable:?}, below zero"),
+        }
+    }
+}
+
 // Each dual_module impl should have mode and affinity_map, hence these methods should be shared
 //      Note: Affinity Map is not implemented in this branch, but a different file/branch (there incurs performance overhead)
 #[macro_export]
@@ -69,7 +143,9 @@ macro_rules! add_shared_methods {
 }
 
 pub struct DualNode {
-    /// the index of this dual node, helps to locate internal details of this dual node
+    /// the index of this dual node, helps to locate internal details of this dual node; it is the position of the node
+    /// in [`DualModuleInterface::nodes`], so indices are assigned in creation order and restart from zero after
+    /// [`DualModuleInterfacePtr::clear`], making the ordering of [`OrderedDualNodePtr`] reproducible across decodings
     pub index: NodeIndex,
     /// the corresponding invalid subgraph
     pub invalid_subgraph: Arc<InvalidSubgraph>,
@@ -121,6 +197,23 @@ impl DualNode {
 pub type DualNodePtr = ArcRwLock<DualNode>;
 pub type DualNodeWeak = WeakRwLock<DualNode>;
 
+impl DualNodePtr {
+    /// the index of this dual node, see [`DualNode::index`] for its stability guarantee
+    pub fn node_index(&self) -> NodeIndex {
+        self.read_recursive().index
+    }
+
+    /// the vertices of the invalid subgraph of this dual node, in ascending order
+    pub fn internal_vertices(&self) -> Vec<VertexIndex> {
+        self.read_recursive().invalid_subgraph.vertices.iter().cloned().collect()
+    }
+
+    /// the hair edges of the invalid subgraph of this dual node, in ascending order
+    pub fn hair_edges(&self) -> Vec<EdgeIndex> {
+        self.read_recursive().invalid_subgraph.hair.iter().cloned().collect()
+    }
+}
+
 impl std::fmt::Debug for DualNodePtr {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let dual_node = self.read_recursive(); // reading index is consistent
@@ -251,6 +344,25 @@ pub enum DualReport {
     Obstacles(Vec<Obstacle>),
 }
 
+/// the LP whose dual is solved by a dual module: minimize the total weight `sum_e w_e x_e` subject to
+/// `sum_{e in hair(S)} x_e >= 1` for the invalid subgraph `S` of every dual node, with one variable `x_e >= 0` per edge
+#[cfg(feature = "slp")]
+pub fn dual_lp_problem(edge_weights: &[Rational], dual_nodes: &[DualNodePtr]) -> slp::parser::LpProblem<Rational> {
+    let vars_list = (0..edge_weights.len()).map(|edge_index| format!("x{edge_index}")).collect();
+    // slp only takes `<=` constraints, so the covering constraints are negated
+    let constraints = dual_nodes
+        .iter()
+        .map(|dual_node_ptr| {
+            let mut coefficients = vec![Rational::zero(); edge_weights.len()];
+            for &edge_index in dual_node_ptr.read_recursive().invalid_subgraph.hair.iter() {
+                coefficients[edge_index] = -Rational::one();
+            }
+            (coefficients, -Rational::one())
+        })
+        .collect();
+    slp::parser::LpProblem::new(vars_list, slp::parser::ObjectiveType::Min, edge_weights.to_vec(), constraints)
+}
+
 /// common trait that must be implemented for each implementation of dual module
 pub trait DualModuleImpl {
     /// create a new dual module with empty syndrome
@@ -265,13 +377,38 @@ pub trait DualModuleImpl {
     /// add corresponding dual node, note that the `internal_vertices` and `hair_edges` are not set
     fn add_dual_node(&mut self, dual_node_ptr: &DualNodePtr);
 
+    /// pre-size the structures that grow while decoding for about `expected` defects, to avoid reallocation in hot
+    /// loops; the reservation survives [`Self::clear`]
+    fn reserve_for_defects(&mut self, _expected: usize) {}
+
+    /// add corresponding dual node with a given initial grow rate, overriding the grow rate of the node;
+    /// the implementation should schedule the obstacles just like `set_grow_rate`, e.g. a shrink-to-zero for a negative rate
+    fn add_dual_node_with_rate(&mut self, dual_node_ptr: &DualNodePtr, grow_rate: Rational) {
+        dual_node_ptr.write().grow_rate = grow_rate;
+        self.add_dual_node(dual_node_ptr);
+    }
+
     /// update grow rate
     fn set_grow_rate(&mut self, dual_node_ptr: &DualNodePtr, grow_rate: Rational);
 
+    /// update the grow rates of multiple nodes at once; the implementation may recompute the obstacles of the
+    /// affected edges only once after all the rates are applied, instead of once per node
+    fn set_grow_rates(&mut self, rates: &[(DualNodePtr, Rational)]) {
+        for (dual_node_ptr, grow_rate) in rates.iter() {
+            self.set_grow_rate(dual_node_ptr, grow_rate.clone());
+        }
+    }
+
     /// check the maximum length to grow (shrink) for all nodes, return a list of conflicting reason and a single number indicating the maximum rate to grow:
     /// this number will be 0 if any conflicting reason presents
     fn report(&mut self) -> DualReport;
 
+    /// return an obstacle of a [`DualReport`] to the dual module, so that it resurfaces in the next [`DualModuleImpl::report`]
+    /// as long as it is still valid; this lets primal strategies defer handling some of the reported obstacles, which
+    /// are otherwise dropped once reported unless changing the grow rates brings them back.
+    /// Dual modules that recompute all the obstacles in every report need not remember anything
+    fn requeue_obstacle(&mut self, _obstacle: Obstacle) {}
+
     /// An optional function that can manipulate individual dual node, not necessarily supported by all implementations
     fn grow_dual_node(&mut self, _dual_node_ptr: &DualNodePtr, _length: Rational) {
         panic!("the dual module implementation doesn't support this function, please use another dual module")
@@ -281,6 +418,18 @@ pub trait DualModuleImpl {
     /// note that a negative growth should be implemented by reversing the speed of each dual node
     fn grow(&mut self, length: Rational);
 
+    /// combine [`DualModuleImpl::report`] and [`DualModuleImpl::grow`]: keep growing by the maximum valid length until
+    /// there are obstacles to resolve, and return them (or [`DualReport::Unbounded`] if nothing can block the growth)
+    fn grow_until_obstacle(&mut self) -> DualReport {
+        loop {
+            let dual_report = self.report();
+            match dual_report {
+                DualReport::ValidGrow(length) => self.grow(length),
+                _ => return dual_report,
+            }
+        }
+    }
+
     /// get all nodes contributing to the edge
     fn get_edge_nodes(&self, edge_index: EdgeIndex) -> Vec<DualNodePtr>;
 
@@ -290,6 +439,35 @@ pub trait DualModuleImpl {
     /// check if the edge is tight
     fn is_edge_tight(&self, edge_index: EdgeIndex) -> bool;
 
+    /// the number of tight edges, from a counter maintained as edges become tight or loose, so that convergence
+    /// heuristics need not call [`DualModuleImpl::is_edge_tight`] on every edge; not supported by all implementations
+    fn num_tight_edges(&self) -> usize {
+        panic!("the dual module implementation doesn't maintain a tight edge counter, please use another dual module")
+    }
+
+    /// the explicit LP whose dual this dual module is solving, see [`dual_lp_problem`], so that its optimum can be
+    /// cross-checked by the `slp` solver: at the optimum of the dual it equals the sum of the dual variables;
+    /// not supported by all implementations
+    #[cfg(feature = "slp")]
+    fn to_lp_problem(&self) -> slp::parser::LpProblem<Rational> {
+        panic!("the dual module implementation doesn't support exporting its LP, please use another dual module")
+    }
+
+    /// get the current grow rate of the edge, i.e. the sum of grow rates of all the nodes contributing to it
+    fn edge_grow_rate(&self, edge_index: EdgeIndex) -> Rational;
+
+    /// check the invariants of the dual module: every edge's growth is no larger than its weight, every dual variable
+    /// is non-negative and nothing is ahead of the global time; returns the first violation.
+    /// this is called after every primal round when the `validate_state` feature is enabled
+    fn validate_state(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// a delta of the visualizer snapshot: only the edges whose growth may have changed since the previous call
+    /// (every edge at the first call and after [`Self::clear`]), together with the global time; this is the only
+    /// call that resets the per-edge change tracking
+    fn snapshot_minimal(&mut self, abbrev: bool) -> serde_json::Value;
+
     /* New tuning-related methods */
     // mode managements
 
@@ -364,7 +542,7 @@ pub trait DualModuleImpl {
     /// calculate affinity based on the following metric
     ///     Clusters with larger primal-dual gaps will receive high affinity because working on those clusters
     ///     will often reduce the gap faster. However, clusters with a large number of dual variables, vertices,
-    ///     and hyperedges will receive a lower affinity
+    ///     and hyperedges will receive a lower affinity, see [`AffinityConfig`] for the coefficients
     fn calculate_cluster_affinity(&mut self, _cluster: PrimalClusterPtr) -> Option<Affinity> {
         eprintln!("not implemented, skipping");
         Some(Affinity::from(100.0))
@@ -530,15 +708,27 @@ pub trait DualModuleImpl {
         unimplemented!()
     }
 
+    /// restore the original weights of the initializer without clearing the dual nodes, e.g. between outer iterations of BP;
+    /// the negative-weight adjustment is kept, because the syndrome loaded into the dual module is flipped accordingly: the
+    /// edges of [`Self::get_negative_edges`] take their negated original weight and [`Self::get_negative_weight_sum`] is
+    /// recomputed from the original weights, while [`Self::get_flip_vertices`] is unchanged. Growing edges continue towards
+    /// their restored weights, but an edge that has already grown beyond its restored weight leaves the dual solution
+    /// infeasible, so only reset when that cannot happen
+    fn reset_weights(&mut self) {
+        unimplemented!()
+    }
+
     fn get_negative_weight_sum(&self) -> Rational {
         unimplemented!()
     }
 
-    fn get_negative_edges(&self) -> HashSet<EdgeIndex> {
+    /// edges with negative weights, in ascending order for reproducible outputs
+    fn get_negative_edges(&self) -> BTreeSet<EdgeIndex> {
         unimplemented!()
     }
 
-    fn get_flip_vertices(&self) -> HashSet<VertexIndex> {
+    /// vertices whose defect status is flipped by the negative edges, in ascending order
+    fn get_flip_vertices(&self) -> BTreeSet<VertexIndex> {
         unimplemented!()
     }
 }
@@ -549,6 +739,24 @@ pub enum Obstacle {
     ShrinkToZero { dual_node_ptr: OrderedDualNodePtr },
 }
 
+impl Obstacle {
+    /// the conflicting edge, or `None` if it's not a [`Obstacle::Conflict`]
+    pub fn edge(&self) -> Option<EdgeIndex> {
+        match self {
+            Self::Conflict { edge_index } => Some(*edge_index),
+            Self::ShrinkToZero { .. } => None,
+        }
+    }
+
+    /// the dual node shrinking to zero, or `None` if it's not a [`Obstacle::ShrinkToZero`]
+    pub fn dual_node(&self) -> Option<DualNodePtr> {
+        match self {
+            Self::Conflict { .. } => None,
+            Self::ShrinkToZero { dual_node_ptr } => Some(dual_node_ptr.ptr.clone()),
+        }
+    }
+}
+
 // implement hash for Obstacle
 impl std::hash::Hash for Obstacle {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -589,6 +797,15 @@ impl DualReport {
         }
     }
 
+    /// the maximum valid grow length if this report is [`DualReport::ValidGrow`], otherwise `None`;
+    /// unlike [`DualReport::get_valid_growth`], an unbounded report is not considered an error
+    pub fn best_grow(&self) -> Option<Rational> {
+        match self {
+            Self::ValidGrow(length) => Some(length.clone()),
+            Self::Unbounded | Self::Obstacles(_) => None,
+        }
+    }
+
     pub fn pop(&mut self) -> Option<Obstacle> {
         match self {
             Self::Unbounded | Self::ValidGrow(_) => {
@@ -647,6 +864,7 @@ impl DualModuleInterfacePtr {
             .sum()
     }
 
+    /// remove all the nodes, so that the indices of new nodes restart from zero
     pub fn clear(&self) {
         let mut interface = self.write();
         interface.nodes.clear();
@@ -699,6 +917,21 @@ impl DualModuleInterfacePtr {
         self.create_node_internal(invalid_subgraph, dual_module, Rational::one(), DualModuleImpl::add_dual_node)
     }
 
+    /// `create_node` with a given initial grow rate
+    pub fn create_node_with_rate(
+        &self,
+        invalid_subgraph: Arc<InvalidSubgraph>,
+        dual_module: &mut impl DualModuleImpl,
+        grow_rate: Rational,
+    ) -> DualNodePtr {
+        self.create_node_internal(
+            invalid_subgraph,
+            dual_module,
+            grow_rate.clone(),
+            |dual_module, dual_node_ptr| dual_module.add_dual_node_with_rate(dual_node_ptr, grow_rate),
+        )
+    }
+
     /// `create_node` for tuning
     pub fn create_node_tune(
         &self,
@@ -743,7 +976,7 @@ impl DualModuleInterfacePtr {
         invalid_subgraph: Arc<InvalidSubgraph>,
         dual_module: &mut D,
         grow_rate: Rational,
-        add_dual_node_fn: fn(&mut D, &DualNodePtr),
+        add_dual_node_fn: impl FnOnce(&mut D, &DualNodePtr),
     ) -> DualNodePtr {
         debug_assert!(
             self.find_node(&invalid_subgraph).is_none(),
@@ -806,10 +1039,10 @@ impl MWPSVisualizer for DualModuleInterfacePtr {
                 if abbrev { "e" } else { "edges" }: dual_node.invalid_subgraph.edges,
                 if abbrev { "v" } else { "vertices" }: dual_node.invalid_subgraph.vertices,
                 if abbrev { "h" } else { "hair" }: dual_node.invalid_subgraph.hair,
-                if abbrev { "d" } else { "dual_variable" }: dual_node.get_dual_variable().to_f64(),
+                if abbrev { "d" } else { "dual_variable" }: rational_to_f64_lossy(&dual_node.get_dual_variable()),
                 if abbrev { "dn" } else { "dual_variable_numerator" }: numer_of(&dual_node.get_dual_variable()),
                 if abbrev { "dd" } else { "dual_variable_denominator" }: denom_of(&dual_node.get_dual_variable()),
-                if abbrev { "r" } else { "grow_rate" }: dual_node.grow_rate.to_f64(),
+                if abbrev { "r" } else { "grow_rate" }: rational_to_f64_lossy(&dual_node.grow_rate),
                 if abbrev { "rn" } else { "grow_rate_numerator" }: numer_of(&dual_node.grow_rate),
                 if abbrev { "rd" } else { "grow_rate_denominator" }: denom_of(&dual_node.grow_rate),
             }));
@@ -817,7 +1050,7 @@ impl MWPSVisualizer for DualModuleInterfacePtr {
         let sum_dual = self.sum_dual_variables();
         json!({
             "interface": {
-                "sum_dual": sum_dual.to_f64(),
+                "sum_dual": rational_to_f64_lossy(&sum_dual),
                 "sdn": numer_of(&sum_dual),
                 "sdd": denom_of(&sum_dual),
             },
@@ -825,3 +1058,78 @@ impl MWPSVisualizer for DualModuleInterfacePtr {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual_module_pq::DualModulePQ;
+    use crate::example_codes::*;
+
+    #[test]
+    fn dual_module_report_best_grow() {
+        // cargo test dual_module_report_best_grow -- --nocapture
+        let length = Rational::from_f64(0.5).unwrap();
+        assert_eq!(DualReport::ValidGrow(length.clone()).best_grow(), Some(length));
+        let mut dual_report = DualReport::new();
+        assert_eq!(dual_report.best_grow(), None);
+        dual_report.add_obstacle(Obstacle::Conflict { edge_index: 0 });
+        assert_eq!(dual_report.best_grow(), None);
+    }
+
+    #[test]
+    fn dual_module_obstacle_accessors() {
+        // cargo test dual_module_obstacle_accessors -- --nocapture
+        let code = CodeCapacityColorCode::new(5, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph, vec![7, 1]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        let conflict = Obstacle::Conflict { edge_index: 3 };
+        assert_eq!(conflict.edge(), Some(3));
+        assert!(conflict.dual_node().is_none());
+        let node_ptr = interface_ptr.get_node(1).unwrap();
+        let shrink_to_zero = Obstacle::ShrinkToZero {
+            dual_node_ptr: node_ptr.clone().into(),
+        };
+        assert_eq!(shrink_to_zero.edge(), None);
+        assert_eq!(shrink_to_zero.dual_node(), Some(node_ptr));
+    }
+
+    #[test]
+    fn dual_module_node_introspection() {
+        // cargo test dual_module_node_introspection -- --nocapture
+        let code = CodeCapacityColorCode::new(5, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph, vec![7, 1]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        let defect_node_ptr = interface_ptr.get_node(0).unwrap();
+        assert_eq!(defect_node_ptr.internal_vertices(), vec![7]);
+        let cluster_node_ptr = interface_ptr.create_node_vec(&[13], &mut dual_module);
+        assert_eq!(cluster_node_ptr.internal_vertices(), vec![2, 6, 7]);
+        assert_eq!(cluster_node_ptr.hair_edges(), vec![5, 6, 9, 10, 11, 12, 14, 15, 16, 17]);
+    }
+
+    #[test]
+    fn dual_module_node_index_stable_across_clear() {
+        // cargo test dual_module_node_index_stable_across_clear -- --nocapture
+        let code = CodeCapacityColorCode::new(5, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph);
+        let decode = |dual_module: &mut DualModulePQ| {
+            interface_ptr.load(Arc::new(SyndromePattern::new_vertices(vec![7, 1, 3])), dual_module);
+            dual_module.grow_until_obstacle();
+            interface_ptr.create_node_vec(&[13], dual_module);
+            let indices: Vec<(NodeIndex, Vec<VertexIndex>)> = (interface_ptr.read_recursive().nodes.iter())
+                .map(|node_ptr| (node_ptr.node_index(), node_ptr.internal_vertices()))
+                .collect();
+            interface_ptr.clear();
+            dual_module.clear();
+            indices
+        };
+        let first = decode(&mut dual_module);
+        assert_eq!(first.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(decode(&mut dual_module), first);
+    }
+}
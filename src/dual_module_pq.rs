@@ -5,7 +5,8 @@
 //! Only debug tests are failing, which aligns with the dual_module_serial behavior
 //!
 
-use crate::num_traits::{ToPrimitive, Zero};
+use crate::model_hypergraph::ModelHyperGraph;
+use crate::num_traits::Zero;
 use crate::pointers::*;
 use crate::primal_module::Affinity;
 use crate::primal_module_serial::PrimalClusterPtr;
@@ -22,7 +23,7 @@ use std::{
 
 use derivative::Derivative;
 use hashbrown::hash_map::Entry;
-use hashbrown::{HashMap, HashSet};
+use hashbrown::HashMap;
 use heapz::RankPairingHeap;
 use heapz::{DecreaseKey, Heap};
 use num_traits::Signed;
@@ -61,6 +62,9 @@ pub type FutureObstacle<T> = FutureEvent<T, Obstacle>;
 pub type MinBinaryHeap<F> = BinaryHeap<Reverse<F>>;
 pub type _FutureObstacleQueue<T> = MinBinaryHeap<FutureObstacle<T>>;
 
+/// a timed callback scheduled with [`DualModulePQGeneric::schedule_user_event`], e.g. to log the state at a given time
+pub type UserEvent<Queue> = Arc<dyn Fn(&DualModulePQGeneric<Queue>) + Send + Sync>;
+
 pub type MinPriorityQueue<O, T> = PriorityQueue<O, Reverse<T>>;
 pub type FutureObstacleQueue<T> = MinPriorityQueue<Obstacle, T>;
 
@@ -80,6 +84,12 @@ impl<T: Ord + PartialEq + Eq + std::fmt::Debug> FutureQueueMethods<T, Obstacle>
     fn len(&self) -> usize {
         self.len()
     }
+    fn reserve(&mut self, additional: usize) {
+        PriorityQueue::reserve(self, additional);
+    }
+    fn capacity(&self) -> Option<usize> {
+        Some(PriorityQueue::capacity(self))
+    }
 }
 
 pub trait FutureQueueMethods<T: Ord + PartialEq + Eq + std::fmt::Debug, E: std::fmt::Debug> {
@@ -103,6 +113,14 @@ pub trait FutureQueueMethods<T: Ord + PartialEq + Eq + std::fmt::Debug, E: std::
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// reserve space for at least `additional` more events, where supported
+    fn reserve(&mut self, _additional: usize) {}
+
+    /// how many events fit without reallocating, `None` if the queue allocates per event
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl<T: Ord + PartialEq + Eq + std::fmt::Debug, E: std::fmt::Debug> FutureQueueMethods<T, E>
@@ -123,6 +141,12 @@ impl<T: Ord + PartialEq + Eq + std::fmt::Debug, E: std::fmt::Debug> FutureQueueM
     fn len(&self) -> usize {
         self.len()
     }
+    fn reserve(&mut self, additional: usize) {
+        BinaryHeap::reserve(self, additional);
+    }
+    fn capacity(&self) -> Option<usize> {
+        Some(BinaryHeap::capacity(self))
+    }
 }
 
 /* Vertices and Edges */
@@ -181,6 +205,11 @@ pub struct Edge {
     last_updated_time: Rational,
     /// growth value at the last updated time, also, growth_at_last_updated_time <= weight
     growth_at_last_updated_time: Rational,
+    /// set whenever the growth changes other than by growing at `grow_rate`; only
+    /// [`DualModuleImpl::snapshot_minimal`] resets it, other reads like [`MWPSVisualizer::snapshot`] leave it set
+    dirty: bool,
+    /// whether this edge is counted in [`DualModulePQGeneric::num_tight_edges`]
+    is_tight: bool,
 
     #[cfg(feature = "incr_lp")]
     /// storing the weights of the clusters that are currently contributing to this edge
@@ -191,6 +220,7 @@ impl Edge {
     fn clear(&mut self) {
         self.growth_at_last_updated_time = Rational::zero();
         self.last_updated_time = Rational::zero();
+        self.dirty = true;
         self.dual_nodes.clear();
         self.grow_rate = Rational::zero();
         #[cfg(feature = "incr_lp")]
@@ -262,11 +292,67 @@ where
 
     // negative weight handling
     negative_weight_sum: Rational,
-    negative_edges: HashSet<EdgeIndex>,
-    flip_vertices: HashSet<VertexIndex>,
+    negative_edges: BTreeSet<EdgeIndex>,
+    flip_vertices: BTreeSet<VertexIndex>,
 
     // remember the initializer for original weights and heralded weighted edges
     pub initializer: Arc<SolverInitializer>,
+
+    /// the coefficients of the cluster affinity
+    pub affinity_config: AffinityConfig,
+
+    /// how many queued obstacles were found valid or stale, see [`DualModulePQGeneric::obstacle_stats`]
+    obstacle_stats: ObstacleStats,
+
+    /// how to handle a dual variable grown below zero; unless unchecked, every [`DualModuleImpl::grow`] visits
+    /// all the dual nodes with a negative grow rate
+    pub over_shrink_policy: OverShrinkPolicy,
+
+    /// the global time of the last [`DualModuleImpl::snapshot_minimal`]; every edge with a non-zero grow rate has
+    /// changed since then if the global time has moved
+    last_snapshot_time: Rational,
+
+    /// the user events, processed alongside the obstacles in [`DualModuleImpl::report`]
+    user_event_queue: MinBinaryHeap<FutureEvent<Rational, UserEvent<Queue>>>,
+
+    /// the relative tolerance of an edge slack or a dual variable to be considered zero when validating obstacles and
+    /// checking tight edges, scaled by `max(1, |weight|)` of the edge (or of the dual variable being shrunk); the growth
+    /// recomputed at the time of an event is off by the rounding of `f64`, which grows with the weight, and the event
+    /// would otherwise be discarded as stale and the conflict missed
+    #[cfg(feature = "f64_weight")]
+    pub epsilon: f64,
+
+    /// the number of edges that are tight as of the last [`DualModuleImpl::report`]: an edge becomes tight when its
+    /// conflict is reported and loose when it grows at a negative rate; shared like the edges themselves, because
+    /// [`DualModuleImpl::grow_edge`] only takes a shared reference
+    num_tight_edges: ArcRwLock<usize>,
+    /// the tight edges with a negative grow rate, which become loose at the next [`DualModuleImpl::grow`]
+    loosening_edges: BTreeSet<EdgeIndex>,
+    /// the dual nodes with a negative grow rate, checked against [`DualModulePQGeneric::over_shrink_policy`] at every
+    /// [`DualModuleImpl::grow`]
+    shrinking_nodes: BTreeMap<NodeIndex, DualNodePtr>,
+}
+
+/// the number of queued obstacles validated or discarded as stale when popping the queue in
+/// [`DualModuleImpl::report`]; a high stale ratio means the queue holds many outdated events, e.g. because of
+/// frequent grow rate changes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObstacleStats {
+    /// obstacles found valid; an obstacle still at the head of the queue is counted again at every report
+    pub validated: usize,
+    /// obstacles discarded because the edge or dual node has changed since they were scheduled
+    pub stale: usize,
+}
+
+impl ObstacleStats {
+    /// the fraction of the checked obstacles that were stale
+    pub fn stale_ratio(&self) -> f64 {
+        let total = self.validated + self.stale;
+        if total == 0 {
+            return 0.;
+        }
+        self.stale as f64 / total as f64
+    }
 }
 
 impl<Queue> DualModulePQGeneric<Queue>
@@ -288,6 +374,7 @@ where
 
         let time_diff = global_time.clone() - &edge.last_updated_time;
         let newly_grown_amount = &time_diff * &edge.grow_rate;
+        edge.dirty |= !newly_grown_amount.is_zero();
         edge.growth_at_last_updated_time += newly_grown_amount;
         edge.last_updated_time = global_time.clone();
 
@@ -297,6 +384,65 @@ where
         // );
     }
 
+    /// the current growth of an edge and its unexplored part `weight - growth`, as reported in the snapshot
+    pub fn edge_exploration(&self, edge_ptr: &EdgePtr) -> (Rational, Rational) {
+        let edge = edge_ptr.read_recursive();
+        let current_growth = &edge.growth_at_last_updated_time
+            + (&self.global_time.read_recursive().clone() - &edge.last_updated_time) * &edge.grow_rate;
+        let unexplored = &edge.weight - &current_growth;
+        (current_growth, unexplored)
+    }
+
+    /// create a dual module for the decoding graph of `model_graph`, same as [`DualModuleImpl::new_empty`] with its
+    /// initializer, which is kept for later weight updates
+    pub fn from_model_graph(model_graph: &Arc<ModelHyperGraph>) -> Self {
+        Self::new_empty(&model_graph.initializer)
+    }
+
+    /// the absolute time of this dual module, i.e. the sum of all the lengths grown so far
+    pub fn get_global_time(&self) -> Rational {
+        self.global_time.read_recursive().clone()
+    }
+
+    /// grow to an absolute `target` time instead of by a relative length, e.g. when replaying a recorded schedule;
+    /// growing to the current global time is a no-op
+    pub fn grow_to_time(&mut self, target: Rational) {
+        let global_time = self.get_global_time();
+        assert!(
+            target >= global_time,
+            "cannot grow back in time: target {target:?} is behind the global time {global_time:?}"
+        );
+        if target > global_time {
+            self.grow(target - global_time);
+        }
+    }
+
+    /// same as [`DualModuleImpl::grow`], but returns how many distinct obstacles are due at or before the new global
+    /// time, i.e. whether this step reached any event boundary; stale events found on the way are discarded
+    pub fn grow_checked(&mut self, length: Rational) -> usize {
+        self.grow(length);
+        let global_time = self.get_global_time();
+        let mut due: Vec<(Rational, Obstacle)> = vec![];
+        while let Some((time, _)) = self.obstacle_queue.peek_event() {
+            if *time > global_time {
+                break;
+            }
+            let (time, event) = self.obstacle_queue.pop_event().unwrap();
+            if !self.is_valid_obstacle(&event, &time) {
+                self.obstacle_stats.stale += 1;
+                continue;
+            }
+            if due.iter().all(|(_, obstacle)| obstacle != &event) {
+                due.push((time, event));
+            }
+        }
+        let count = due.len();
+        for (time, event) in due {
+            self.obstacle_queue.will_happen(time, event);
+        }
+        count
+    }
+
     /// helper function to bring a dual node update to speed with current time if needed
     fn update_dual_node_if_necessary(&mut self, node: &mut RwLockWriteGuard<RawRwLock, DualNode>) {
         let global_time = self.global_time.read_recursive();
@@ -340,6 +486,7 @@ where
         while let Some((time, event)) = self.obstacle_queue.peek_event() {
             // found a valid event
             if self.is_valid_obstacle(event, time) {
+                self.obstacle_stats.validated += 1;
                 // valid grow
                 if time != &global_time {
                     return Some(time - global_time.clone());
@@ -347,11 +494,194 @@ where
                 // goto else
                 break;
             }
+            self.obstacle_stats.stale += 1;
             self.obstacle_queue.pop_event();
         }
         None
     }
 
+    /// the obstacles at the current global time, or the maximum valid growth until the next one
+    fn report_obstacles(&mut self) -> DualReport {
+        if let Some(max_valid_grow) = self.compute_max_valid_grow() {
+            return DualReport::ValidGrow(max_valid_grow);
+        }
+
+        let global_time = self.global_time.read_recursive().clone();
+
+        // else , it is a valid conflict to resolve
+        if let Some((_, event)) = self.obstacle_queue.pop_event() {
+            // this is used, since queues are not sets, and can contain duplicate events
+            // Note: check that this is the assumption, though not much more overhead anyway
+            // let mut group_max_update_length_set = BTreeSet::default();
+
+            // Note: With de-dup queue implementation, we could use vectors here
+            let mut dual_report = DualReport::new();
+            dual_report.add_obstacle(event);
+
+            // append all conflicts that happen at the same time as now
+            while let Some((time, _)) = self.obstacle_queue.peek_event() {
+                if global_time == *time {
+                    let (time, event) = self.obstacle_queue.pop_event().unwrap();
+                    if !self.is_valid_obstacle(&event, &time) {
+                        self.obstacle_stats.stale += 1;
+                        continue;
+                    }
+                    self.obstacle_stats.validated += 1;
+                    // the same obstacle may have been queued several times, e.g. when explicitly requeued
+                    if dual_report.iter().unwrap().any(|obstacle| obstacle == &event) {
+                        continue;
+                    }
+                    // add
+                    dual_report.add_obstacle(event);
+                } else {
+                    break;
+                }
+            }
+
+            // the reported obstacles leave the queue: changing the grow rates schedules them again if they persist,
+            // and a primal module that defers one must put it back with [`DualModuleImpl::requeue_obstacle`]
+            for obstacle in dual_report.iter().unwrap() {
+                if let Obstacle::Conflict { edge_index } = obstacle {
+                    let mut edge = self.edges[*edge_index].write();
+                    self.set_edge_tight(&mut edge, true);
+                }
+            }
+            return dual_report;
+        }
+
+        // nothing useful could be done, return unbounded
+        DualReport::new()
+    }
+
+    /// schedule `callback` to be called with this dual module once the global time reaches `time`;
+    /// [`DualModuleImpl::report`] stops the growth at the earliest pending event and fires it at the next report.
+    /// pending events are dropped by [`DualModuleImpl::clear`]
+    pub fn schedule_user_event(&mut self, time: Rational, callback: impl Fn(&Self) + Send + Sync + 'static) {
+        let global_time = self.get_global_time();
+        assert!(
+            time >= global_time,
+            "cannot schedule an event at {time:?} before the global time {global_time:?}"
+        );
+        self.user_event_queue.push(Reverse(FutureEvent {
+            time,
+            event: Arc::new(callback),
+        }));
+    }
+
+    /// the capacity of the obstacle queue, see [`DualModuleImpl::reserve_for_defects`]; `None` if the queue
+    /// allocates per event
+    pub fn obstacle_queue_capacity(&self) -> Option<usize> {
+        self.obstacle_queue.capacity()
+    }
+
+    /// the number of user events that have not fired yet
+    pub fn pending_user_events(&self) -> usize {
+        self.user_event_queue.len()
+    }
+
+    /// call the user events that are due, in the order of their time
+    fn fire_user_events(&mut self) {
+        let global_time = self.get_global_time();
+        while self.user_event_queue.peek().is_some_and(|next| next.0.time <= global_time) {
+            let Reverse(FutureEvent { event: callback, .. }) = self.user_event_queue.pop().unwrap();
+            callback(self);
+        }
+    }
+
+    /// apply [`DualModulePQGeneric::over_shrink_policy`] to the dual nodes that would be negative at `target_time`;
+    /// a clamped node stops at zero at its shrink-to-zero time with its grow rate set to zero, and its hair edges stop
+    /// shrinking with it
+    fn guard_over_shrink(&mut self, target_time: &Rational) {
+        let mut over_shrunk_nodes = vec![];
+        for dual_node_ptr in self.shrinking_nodes.values() {
+            let dual_node = dual_node_ptr.read_recursive();
+            let dual_variable = &dual_node.dual_variable_at_last_updated_time
+                + (target_time - &dual_node.last_updated_time) * &dual_node.grow_rate;
+            if !dual_variable.is_negative() {
+                continue;
+            }
+            self.over_shrink_policy.apply(dual_node.index, dual_variable);
+            let zero_time =
+                &dual_node.last_updated_time + &dual_node.dual_variable_at_last_updated_time / -dual_node.grow_rate.clone();
+            over_shrunk_nodes.push((zero_time, dual_node_ptr.clone()));
+        }
+        // an edge shared by several clamped nodes is brought forward in time one node after the other
+        over_shrunk_nodes.sort_by(|(time_1, _), (time_2, _)| time_1.cmp(time_2));
+        for (zero_time, dual_node_ptr) in over_shrunk_nodes {
+            let mut dual_node = dual_node_ptr.write();
+            let grow_rate = std::mem::replace(&mut dual_node.grow_rate, Rational::zero());
+            dual_node.set_dual_variable(Rational::zero());
+            dual_node.last_updated_time = zero_time.clone();
+            self.shrinking_nodes.remove(&dual_node.index);
+            for &edge_index in dual_node.invalid_subgraph.hair.iter() {
+                let mut edge = self.edges[edge_index].write();
+                let growth = (&zero_time - &edge.last_updated_time) * &edge.grow_rate;
+                edge.growth_at_last_updated_time += growth;
+                edge.last_updated_time = zero_time.clone();
+                edge.grow_rate -= &grow_rate;
+                edge.dirty = true;
+                if edge.grow_rate.is_positive() {
+                    self.obstacle_queue.will_happen(
+                        (&edge.weight - &edge.growth_at_last_updated_time) / &edge.grow_rate + &zero_time,
+                        Obstacle::Conflict { edge_index },
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn obstacle_stats(&self) -> ObstacleStats {
+        self.obstacle_stats
+    }
+
+    /// mark an edge as tight or loose, keeping [`DualModulePQGeneric::num_tight_edges`] in sync
+    fn set_edge_tight(&self, edge: &mut Edge, is_tight: bool) {
+        if edge.is_tight == is_tight {
+            return;
+        }
+        edge.is_tight = is_tight;
+        let mut num_tight_edges = self.num_tight_edges.write();
+        if is_tight {
+            *num_tight_edges += 1;
+        } else {
+            *num_tight_edges -= 1;
+        }
+    }
+
+    /// count the tight edges from scratch, after the weights have been changed
+    fn recount_tight_edges(&mut self) {
+        let global_time = self.global_time.read_recursive().clone();
+        self.loosening_edges.clear();
+        let mut num_tight_edges = 0;
+        for edge_ptr in self.edges.iter() {
+            let mut edge = edge_ptr.write();
+            let slack = &edge.weight
+                - &edge.growth_at_last_updated_time
+                - (&global_time - &edge.last_updated_time) * &edge.grow_rate;
+            edge.is_tight = self.is_zero_within_tolerance(&slack, &edge.weight);
+            if edge.is_tight {
+                num_tight_edges += 1;
+                if edge.grow_rate.is_negative() {
+                    self.loosening_edges.insert(edge.edge_index);
+                }
+            }
+        }
+        *self.num_tight_edges.write() = num_tight_edges;
+    }
+
+    /// whether a value that would be exactly zero in rational arithmetic is zero, relative to the magnitude `scale` of the
+    /// quantities it is computed from, see [`DualModulePQGeneric::epsilon`]
+    #[cfg_attr(not(feature = "f64_weight"), allow(unused_variables))]
+    fn is_zero_within_tolerance(&self, value: &Rational, scale: &Rational) -> bool {
+        cfg_if::cfg_if! {
+            if #[cfg(feature="f64_weight")] {
+                value.numer().abs() <= self.epsilon * scale.numer().abs().max(1.)
+            } else {
+                value.is_zero()
+            }
+        }
+    }
+
     /// return if the current obstacle is valid
     ///     note: even when the pq cannot hold duplicate events, `is_invalid` approach is more efficient than needing to remove items from the q
     fn is_valid_obstacle(
@@ -371,7 +701,7 @@ where
                     &edge.growth_at_last_updated_time + (event_time - &edge.last_updated_time) * &edge.grow_rate;
 
                 // we have a postivie grow rate, should become tight
-                growth_at_event_time == edge.weight
+                self.is_zero_within_tolerance(&(&edge.weight - growth_at_event_time), &edge.weight)
             }
             Obstacle::ShrinkToZero { dual_node_ptr } => {
                 let node = dual_node_ptr.ptr.read_recursive();
@@ -383,7 +713,7 @@ where
                     &node.dual_variable_at_last_updated_time + (event_time - &node.last_updated_time) * &node.grow_rate;
 
                 // we have a negative grow rate, should become zero
-                growth_at_event_time.is_zero()
+                self.is_zero_within_tolerance(&growth_at_event_time, &node.dual_variable_at_last_updated_time)
             }
         };
     }
@@ -426,6 +756,8 @@ where
                     .collect::<Vec<_>>(),
                 last_updated_time: Rational::zero(),
                 growth_at_last_updated_time: Rational::zero(),
+                dirty: true,
+                is_tight: false,
                 grow_rate: Rational::zero(),
                 #[cfg(feature = "incr_lp")]
                 cluster_weights: hashbrown::HashMap::new(),
@@ -439,7 +771,7 @@ where
 
             edges.push(edge_ptr);
         }
-        Self {
+        let mut dual_module = Self {
             vertices,
             edges,
             obstacle_queue: Queue::default(),
@@ -451,7 +783,19 @@ where
             negative_edges: Default::default(),
             flip_vertices: Default::default(),
             initializer: initializer.clone(),
-        }
+            affinity_config: AffinityConfig::default(),
+            obstacle_stats: ObstacleStats::default(),
+            over_shrink_policy: OverShrinkPolicy::default(),
+            last_snapshot_time: Rational::zero(),
+            user_event_queue: MinBinaryHeap::new(),
+            #[cfg(feature = "f64_weight")]
+            epsilon: crate::ordered_float::EPSILON,
+            num_tight_edges: ArcRwLock::new_value(0),
+            loosening_edges: BTreeSet::new(),
+            shrinking_nodes: BTreeMap::new(),
+        };
+        dual_module.recount_tight_edges();
+        dual_module
     }
 
     /// clear all growth and existing dual nodes
@@ -477,6 +821,11 @@ where
         self.negative_edges.clear();
         self.negative_weight_sum = Rational::zero();
         self.flip_vertices.clear();
+        self.obstacle_stats = ObstacleStats::default();
+        self.last_snapshot_time = Rational::zero();
+        self.user_event_queue.clear();
+        self.shrinking_nodes.clear();
+        self.recount_tight_edges();
     }
 
     #[allow(clippy::unnecessary_cast)]
@@ -497,6 +846,17 @@ where
         self.add_dual_node(dual_node_ptr);
     }
 
+    /// every defect node schedules at most one obstacle per incident edge, so the obstacle queue is sized for
+    /// `expected` times the largest vertex degree
+    fn reserve_for_defects(&mut self, expected: usize) {
+        let max_degree = (self.vertices.iter())
+            .map(|vertex_ptr| vertex_ptr.read_recursive().edges.len())
+            .max()
+            .unwrap_or(0);
+        let additional = (expected * max_degree).saturating_sub(self.obstacle_queue.len());
+        self.obstacle_queue.reserve(additional);
+    }
+
     #[allow(clippy::unnecessary_cast)]
     /// Mostly invoked by `add_defect_node`, triggering a pq update, and edges updates
     fn add_dual_node(&mut self, dual_node_ptr: &DualNodePtr) {
@@ -506,6 +866,7 @@ where
         let dual_node = dual_node_ptr.read_recursive();
 
         if dual_node.grow_rate.is_negative() {
+            self.shrinking_nodes.insert(dual_node.index, dual_node_ptr.clone());
             self.obstacle_queue.will_happen(
                 // it is okay to use global_time now, as this must be up-to-speed
                 dual_node.get_dual_variable().clone() / (-dual_node.grow_rate.clone()) + global_time.clone(),
@@ -524,6 +885,9 @@ where
             edge.grow_rate += &dual_node.grow_rate;
             edge.dual_nodes
                 .push(OrderedDualNodeWeak::new(dual_node.index, dual_node_weak.clone()));
+            if edge.is_tight && edge.grow_rate.is_negative() {
+                self.loosening_edges.insert(edge_index);
+            }
 
             if edge.grow_rate.is_positive() {
                 self.obstacle_queue.will_happen(
@@ -561,11 +925,14 @@ where
 
         dual_node.grow_rate = grow_rate.clone();
         if dual_node.grow_rate.is_negative() {
+            self.shrinking_nodes.insert(dual_node.index, dual_node_ptr.clone());
             let time = dual_node.get_dual_variable().clone() / (-grow_rate) + global_time.clone();
             let event = Obstacle::ShrinkToZero {
                 dual_node_ptr: OrderedDualNodePtr::new(dual_node.index, dual_node_ptr.clone()),
             };
             self.obstacle_queue.will_happen(time, event);
+        } else {
+            self.shrinking_nodes.remove(&dual_node.index);
         }
 
         // don't reacquire the read guard
@@ -574,6 +941,9 @@ where
             self.update_edge_if_necessary(&mut edge);
 
             edge.grow_rate += &grow_rate_diff;
+            if edge.is_tight && edge.grow_rate.is_negative() {
+                self.loosening_edges.insert(edge_index);
+            }
             if edge.grow_rate.is_positive() {
                 self.obstacle_queue.will_happen(
                     // it is okay to use global_time now, as this must be up-to-speed
@@ -585,47 +955,70 @@ where
         }
     }
 
-    fn report(&mut self) -> DualReport {
-        // self.debug_print();
-
-        if let Some(max_valid_grow) = self.compute_max_valid_grow() {
-            return DualReport::ValidGrow(max_valid_grow);
-        }
-
+    #[allow(clippy::unnecessary_cast)]
+    /// apply all the rate changes first, then schedule a single conflict per affected edge
+    fn set_grow_rates(&mut self, rates: &[(DualNodePtr, Rational)]) {
+        // it is okay to use global_time now, as nodes and edges are brought up-to-speed before being modified
         let global_time = self.global_time.read_recursive().clone();
+        let mut affected_edges = BTreeSet::<EdgeIndex>::new();
 
-        // else , it is a valid conflict to resolve
-        if let Some((_, event)) = self.obstacle_queue.pop_event() {
-            // this is used, since queues are not sets, and can contain duplicate events
-            // Note: check that this is the assumption, though not much more overhead anyway
-            // let mut group_max_update_length_set = BTreeSet::default();
+        for (dual_node_ptr, grow_rate) in rates.iter() {
+            let mut dual_node = dual_node_ptr.write();
+            self.update_dual_node_if_necessary(&mut dual_node);
 
-            // Note: With de-dup queue implementation, we could use vectors here
-            let mut dual_report = DualReport::new();
-            dual_report.add_obstacle(event);
+            let grow_rate_diff = grow_rate - &dual_node.grow_rate;
+            dual_node.grow_rate = grow_rate.clone();
+            if dual_node.grow_rate.is_negative() {
+                self.shrinking_nodes.insert(dual_node.index, dual_node_ptr.clone());
+                let time = dual_node.get_dual_variable().clone() / (-grow_rate.clone()) + global_time.clone();
+                let event = Obstacle::ShrinkToZero {
+                    dual_node_ptr: OrderedDualNodePtr::new(dual_node.index, dual_node_ptr.clone()),
+                };
+                self.obstacle_queue.will_happen(time, event);
+            } else {
+                self.shrinking_nodes.remove(&dual_node.index);
+            }
 
-            // append all conflicts that happen at the same time as now
-            while let Some((time, _)) = self.obstacle_queue.peek_event() {
-                if global_time == *time {
-                    let (time, event) = self.obstacle_queue.pop_event().unwrap();
-                    if !self.is_valid_obstacle(&event, &time) {
-                        continue;
-                    }
-                    // add
-                    dual_report.add_obstacle(event);
-                } else {
-                    break;
+            for &edge_index in dual_node.invalid_subgraph.hair.iter() {
+                let mut edge = self.edges[edge_index as usize].write();
+                self.update_edge_if_necessary(&mut edge);
+                edge.grow_rate += &grow_rate_diff;
+                if edge.is_tight && edge.grow_rate.is_negative() {
+                    self.loosening_edges.insert(edge_index);
                 }
+                affected_edges.insert(edge_index);
+            }
+        }
+
+        for edge_index in affected_edges.into_iter() {
+            let edge = self.edges[edge_index as usize].read_recursive();
+            if edge.grow_rate.is_positive() {
+                self.obstacle_queue.will_happen(
+                    (edge.weight.clone() - edge.growth_at_last_updated_time.clone()) / edge.grow_rate.clone()
+                        + global_time.clone(),
+                    Obstacle::Conflict { edge_index },
+                );
             }
+        }
+    }
 
-            for obstacle in dual_report.iter().unwrap() {
-                self.obstacle_queue.will_happen(global_time.clone(), obstacle.clone());
+    fn report(&mut self) -> DualReport {
+        self.fire_user_events();
+        let dual_report = self.report_obstacles();
+        // stop the growth at the next user event, if it comes earlier than any obstacle
+        let global_time = self.get_global_time();
+        match (&dual_report, self.user_event_queue.peek()) {
+            (DualReport::ValidGrow(length), Some(Reverse(next))) if &next.time - &global_time < *length => {
+                DualReport::ValidGrow(&next.time - &global_time)
             }
-            return dual_report;
+            (DualReport::Unbounded, Some(Reverse(next))) => DualReport::ValidGrow(&next.time - &global_time),
+            _ => dual_report,
         }
+    }
 
-        // nothing useful could be done, return unbounded
-        DualReport::new()
+    fn requeue_obstacle(&mut self, obstacle: Obstacle) {
+        let global_time = self.global_time.read_recursive().clone();
+        self.obstacle_queue.will_happen(global_time, obstacle);
     }
 
     /// for pq implementation, simply updating the global time is enough, could be part of the `report` function
@@ -637,8 +1030,18 @@ where
             length.is_positive(),
             "growth should be positive; if desired, please set grow rate to negative for shrinking"
         );
-        let mut global_time_write = self.global_time.write();
-        *global_time_write = global_time_write.clone() + length;
+        let target_time = self.global_time.read_recursive().clone() + length;
+        if self.over_shrink_policy != OverShrinkPolicy::Unchecked {
+            self.guard_over_shrink(&target_time);
+        }
+        *self.global_time.write() = target_time;
+        for edge_index in std::mem::take(&mut self.loosening_edges) {
+            let mut edge = self.edges[edge_index].write();
+            // the grow rate may have been restored since the edge was recorded
+            if edge.grow_rate.is_negative() {
+                self.set_edge_tight(&mut edge, false);
+            }
+        }
     }
 
     /* identical with the dual_module_serial */
@@ -661,9 +1064,91 @@ where
             - edge.growth_at_last_updated_time.clone()
     }
 
-    /// is the edge saturated
+    /// is the edge saturated, within [`DualModulePQGeneric::epsilon`] for `f64` weights
     fn is_edge_tight(&self, edge_index: EdgeIndex) -> bool {
-        self.get_edge_slack(edge_index).is_zero()
+        let weight = self.get_edge_weight(edge_index);
+        self.is_zero_within_tolerance(&self.get_edge_slack(edge_index), &weight)
+    }
+
+    /// the edges tight as of the last [`DualModuleImpl::report`], see [`DualModulePQGeneric::num_tight_edges`]
+    fn num_tight_edges(&self) -> usize {
+        *self.num_tight_edges.read_recursive()
+    }
+
+    fn edge_grow_rate(&self, edge_index: EdgeIndex) -> Rational {
+        self.edges[edge_index].read_recursive().grow_rate.clone()
+    }
+
+    fn validate_state(&self) -> Result<(), String> {
+        let global_time = self.global_time.read_recursive().clone();
+        for edge_ptr in self.edges.iter() {
+            let edge = edge_ptr.read_recursive();
+            if edge.last_updated_time > global_time {
+                return Err(format!(
+                    "edge {}: last updated at {:?}, ahead of the global time {global_time:?}",
+                    edge.edge_index, edge.last_updated_time
+                ));
+            }
+            let (growth, _) = self.edge_exploration(edge_ptr);
+            if growth > edge.weight {
+                return Err(format!(
+                    "edge {}: growth {growth:?} exceeds weight {:?}",
+                    edge.edge_index, edge.weight
+                ));
+            }
+            for dual_node_weak in edge.dual_nodes.iter() {
+                let dual_node_ptr = dual_node_weak.upgrade_force().ptr;
+                let dual_node = dual_node_ptr.read_recursive();
+                if dual_node.last_updated_time > global_time {
+                    return Err(format!(
+                        "dual node {}: last updated at {:?}, ahead of the global time {global_time:?}",
+                        dual_node.index, dual_node.last_updated_time
+                    ));
+                }
+                let dual_variable = dual_node.get_dual_variable();
+                if dual_variable.is_negative() {
+                    return Err(format!(
+                        "dual node {}: negative dual variable {dual_variable:?}",
+                        dual_node.index
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn snapshot_minimal(&mut self, abbrev: bool) -> serde_json::Value {
+        let global_time = self.get_global_time();
+        let time_moved = global_time != self.last_snapshot_time;
+        let mut edges: Vec<serde_json::Value> = vec![];
+        for edge_ptr in self.edges.iter() {
+            let mut edge = edge_ptr.write();
+            let is_growing = time_moved && !edge.grow_rate.is_zero();
+            if !edge.dirty && !is_growing {
+                continue;
+            }
+            edge.dirty = false;
+            let edge_index = edge.edge_index;
+            drop(edge);
+            let (current_growth, unexplored) = self.edge_exploration(edge_ptr);
+            edges.push(json!({
+                if abbrev { "i" } else { "edge_index" }: edge_index,
+                if abbrev { "g" } else { "growth" }: rational_to_f64_lossy(&current_growth),
+                "gn": numer_of(&current_growth),
+                "gd": denom_of(&current_growth),
+                if abbrev { "u" } else { "unexplored" }: rational_to_f64_lossy(&unexplored),
+                "un": numer_of(&unexplored),
+                "ud": denom_of(&unexplored),
+            }));
+        }
+        let snapshot = json!({
+            "global_time": rational_to_f64_lossy(&global_time),
+            "tn": numer_of(&global_time),
+            "td": denom_of(&global_time),
+            "edges": edges,
+        });
+        self.last_snapshot_time = global_time;
+        snapshot
     }
 
     /* tuning mode related new methods */
@@ -709,6 +1194,10 @@ where
     fn grow_edge(&self, edge_index: EdgeIndex, amount: &Rational) {
         let mut edge = self.edges[edge_index].write();
         edge.growth_at_last_updated_time += amount;
+        edge.dirty = true;
+        // the edges are synced in tuning mode
+        let is_tight = self.is_zero_within_tolerance(&(&edge.weight - &edge.growth_at_last_updated_time), &edge.weight);
+        self.set_edge_tight(&mut edge, is_tight);
     }
 
     /// sync all states and global time so the concept of time and pq can retire
@@ -801,9 +1290,7 @@ where
 
     /* affinity */
     fn calculate_cluster_affinity(&mut self, cluster: PrimalClusterPtr) -> Option<Affinity> {
-        let mut start = 0.0;
         let cluster = cluster.read_recursive();
-        start -= cluster.edges.len() as f64 + cluster.nodes.len() as f64;
         let global_time = self.global_time.read_recursive().clone();
 
         let mut weight = Rational::zero();
@@ -821,8 +1308,10 @@ where
         if weight.is_zero() {
             return None;
         }
-        start += weight.to_f64().unwrap();
-        Some(Affinity::from(start))
+        Some(
+            self.affinity_config
+                .affinity(cluster.edges.len() + cluster.nodes.len(), &weight),
+        )
     }
 
     fn get_edge_free_weight(
@@ -848,6 +1337,22 @@ where
         edge.weight.clone()
     }
 
+    /// the dual nodes are not kept by this module, so they are collected from the edges they contribute to
+    #[cfg(feature = "slp")]
+    fn to_lp_problem(&self) -> slp::parser::LpProblem<Rational> {
+        let mut edge_weights = Vec::with_capacity(self.edges.len());
+        let mut dual_nodes = BTreeMap::new();
+        for edge in self.edges.iter() {
+            let edge = edge.read_recursive();
+            edge_weights.push(edge.weight.clone());
+            for dual_node_weak in edge.dual_nodes.iter() {
+                let dual_node_ptr = dual_node_weak.upgrade_force();
+                dual_nodes.insert(dual_node_ptr.index, dual_node_ptr.ptr);
+            }
+        }
+        dual_lp_problem(&edge_weights, &dual_nodes.into_values().collect::<Vec<_>>())
+    }
+
     #[cfg(feature = "incr_lp")]
     fn get_edge_free_weight_cluster(&self, edge_index: EdgeIndex, cluster_index: NodeIndex) -> Rational {
         let edge = self.edges[edge_index as usize].read_recursive();
@@ -909,6 +1414,7 @@ where
                 edge.weight = -edge.weight.clone();
             }
         }
+        self.recount_tight_edges();
     }
 
     fn update_weights(&mut self, new_weights: Vec<Weight>, mix_ratio: Weight) {
@@ -921,6 +1427,7 @@ where
 
             edge.weight = new_weight;
         }
+        self.recount_tight_edges();
     }
 
     fn set_weights(&mut self, new_weights: BTreeMap<EdgeIndex, Weight>) {
@@ -928,17 +1435,49 @@ where
             let mut edge = self.edges[edge_index].write();
             edge.weight = new_weight;
         }
+        self.recount_tight_edges();
+    }
+
+    fn reset_weights(&mut self) {
+        let global_time = self.global_time.read_recursive().clone();
+        self.negative_weight_sum = Rational::zero();
+        for (edge_ptr, hyper_edge) in self.edges.iter().zip(self.initializer.weighted_edges.iter()) {
+            let mut edge = edge_ptr.write();
+            // keep the negative-weight adjustment, the loaded syndrome is flipped accordingly
+            let weight = if self.negative_edges.contains(&edge.edge_index) {
+                self.negative_weight_sum += hyper_edge.weight.clone();
+                -hyper_edge.weight.clone()
+            } else {
+                hyper_edge.weight.clone()
+            };
+            if edge.weight == weight {
+                continue;
+            }
+            self.update_edge_if_necessary(&mut edge);
+            edge.weight = weight;
+            // the previously scheduled conflict is now stale and will be dropped by `is_valid_obstacle`
+            if edge.grow_rate.is_positive() {
+                self.obstacle_queue.will_happen(
+                    (edge.weight.clone() - edge.growth_at_last_updated_time.clone()) / edge.grow_rate.clone()
+                        + global_time.clone(),
+                    Obstacle::Conflict {
+                        edge_index: edge.edge_index,
+                    },
+                );
+            }
+        }
+        self.recount_tight_edges();
     }
 
     fn get_negative_weight_sum(&self) -> Rational {
         self.negative_weight_sum.clone()
     }
 
-    fn get_negative_edges(&self) -> HashSet<EdgeIndex> {
+    fn get_negative_edges(&self) -> BTreeSet<EdgeIndex> {
         self.negative_edges.clone()
     }
 
-    fn get_flip_vertices(&self) -> HashSet<VertexIndex> {
+    fn get_flip_vertices(&self) -> BTreeSet<VertexIndex> {
         self.flip_vertices.clone()
     }
 }
@@ -957,20 +1496,18 @@ where
         }
         let mut edges: Vec<serde_json::Value> = vec![];
         for edge_ptr in self.edges.iter() {
-            let edge = edge_ptr.read_recursive();
-            let current_growth = &edge.growth_at_last_updated_time
-                + (&self.global_time.read_recursive().clone() - &edge.last_updated_time) * &edge.grow_rate;
-            let unexplored = &edge.weight - &current_growth;
+            let (current_growth, unexplored) = self.edge_exploration(edge_ptr);
             assert!(!unexplored.is_negative());
+            let edge = edge_ptr.read_recursive();
             edges.push(json!({
-                if abbrev { "w" } else { "weight" }: edge.weight.to_f64(),
+                if abbrev { "w" } else { "weight" }: rational_to_f64_lossy(&edge.weight),
                 "wn": numer_of(&edge.weight),
                 "wd": denom_of(&edge.weight),
                 if abbrev { "v" } else { "vertices" }: edge.vertices.iter().map(|x| x.upgrade_force().read_recursive().vertex_index).collect::<Vec<_>>(),
-                if abbrev { "g" } else { "growth" }: current_growth.to_f64(),
+                if abbrev { "g" } else { "growth" }: rational_to_f64_lossy(&current_growth),
                 "gn": numer_of(&current_growth),
                 "gd": denom_of(&current_growth),
-                if abbrev { "u" } else { "unexplored" }: unexplored.to_f64(),
+                if abbrev { "u" } else { "unexplored" }: rational_to_f64_lossy(&unexplored),
                 "un": numer_of(&unexplored),
                 "ud": denom_of(&unexplored),
             }));
@@ -987,7 +1524,8 @@ mod tests {
     use super::*;
     use crate::decoding_hypergraph::*;
     use crate::example_codes::*;
-    use num_traits::FromPrimitive;
+    use crate::invalid_subgraph::InvalidSubgraph;
+    use num_traits::{FromPrimitive, One, ToPrimitive};
 
     #[test]
     fn dual_module_pq_learn_priority_queue_1() {
@@ -1036,6 +1574,23 @@ mod tests {
         println!("events: {events:?}");
     }
 
+    #[test]
+    fn dual_module_pq_heap_queues_clear() {
+        // cargo test dual_module_pq_heap_queues_clear -- --nocapture
+        fn check_clear(mut queue: impl FutureQueueMethods<usize, Obstacle>) {
+            for edge_index in 0..10 {
+                queue.will_happen(10 - edge_index, Obstacle::Conflict { edge_index });
+            }
+            queue.clear();
+            assert_eq!(queue.len(), 0);
+            assert_eq!(queue.peek_event(), None);
+            queue.will_happen(3, Obstacle::Conflict { edge_index: 3 });
+            assert_eq!(queue.pop_event(), Some((3, Obstacle::Conflict { edge_index: 3 })));
+        }
+        check_clear(PairingPQ::<usize>::default());
+        check_clear(RankPairingPQ::<usize>::default());
+    }
+
     #[test]
     fn dual_module_pq_basics_1() {
         // cargo test dual_module_pq_basics_1 -- --nocapture
@@ -1218,7 +1773,809 @@ mod tests {
         println!("open visualizer at {}", visualizer.html_along_json_path());
     }
 
-    // TODO: write more tests here, perhaps unit tests
+    #[test]
+    fn dual_module_pq_add_dual_node_with_rate() {
+        // cargo test dual_module_pq_add_dual_node_with_rate -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph, vec![3, 12]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        for node_ptr in interface_ptr.read_recursive().nodes.iter() {
+            dual_module.set_grow_rate(node_ptr, Rational::zero());
+        }
+        // a node with zero dual variable and a negative rate must shrink to zero immediately
+        let invalid_subgraph = Arc::new(InvalidSubgraph::new(
+            [2].into(),
+            &interface_ptr.read_recursive().decoding_graph,
+        ));
+        let node_ptr = interface_ptr.create_node_with_rate(invalid_subgraph, &mut dual_module, -Rational::one());
+        assert_eq!(node_ptr.read_recursive().grow_rate, -Rational::one());
+        let mut dual_report = dual_module.report();
+        assert_eq!(
+            dual_report.pop(),
+            Some(Obstacle::ShrinkToZero {
+                dual_node_ptr: node_ptr.into()
+            })
+        );
+        assert_eq!(dual_report.pop(), None);
+    }
+
+    #[test]
+    fn dual_module_pq_reset_weights() {
+        // cargo test dual_module_pq_reset_weights -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let initializer = model_graph.initializer.clone();
+        // the first obstacle under the original weights
+        let mut dual_module = DualModulePQ::new_empty(&initializer);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![3, 12]);
+        DualModuleInterfacePtr::new_load(decoding_graph.clone(), &mut dual_module);
+        let length = dual_module.report().get_valid_growth().unwrap();
+        // grow under doubled weights, then restore the original weights in the middle of the growth
+        let mut dual_module = DualModulePQ::new_empty(&initializer);
+        let doubled_weights: Vec<Weight> = initializer
+            .weighted_edges
+            .iter()
+            .map(|edge| edge.weight.clone() * Rational::from_usize(2).unwrap())
+            .collect();
+        dual_module.update_weights(doubled_weights, Rational::one());
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        let half_length = length.clone() / Rational::from_usize(2).unwrap();
+        dual_module.grow(half_length.clone());
+        dual_module.reset_weights();
+        for (edge_index, edge) in initializer.weighted_edges.iter().enumerate() {
+            assert_eq!(dual_module.get_edge_weight(edge_index), edge.weight);
+        }
+        // the dual nodes are preserved and the obstacles are rescheduled against the restored weights
+        assert_eq!(interface_ptr.read_recursive().nodes.len(), 2);
+        for node_ptr in interface_ptr.read_recursive().nodes.iter() {
+            assert_eq!(node_ptr.read_recursive().get_dual_variable(), half_length);
+        }
+        assert_eq!(dual_module.report().get_valid_growth(), Some(length - half_length));
+    }
+
+    #[test]
+    fn dual_module_pq_reset_weights_negative() {
+        // cargo test dual_module_pq_reset_weights_negative -- --nocapture
+        use crate::dual_module_serial::DualModuleSerial;
+        let mut initializer = CodeCapacityRepetitionCode::new(7, 0.1).get_initializer();
+        let weights = [3., -2., 1.5, 4., 2., -0.5, 1.];
+        for (edge, weight) in initializer.weighted_edges.iter_mut().zip(weights) {
+            edge.weight = Rational::from_float(weight).unwrap();
+        }
+        let initializer = Arc::new(initializer);
+        fn check(initializer: &Arc<SolverInitializer>, dual_module: &mut impl DualModuleImpl) {
+            dual_module.adjust_weights_for_negative_edges();
+            let adjusted_weights: Vec<Weight> = (0..initializer.weighted_edges.len())
+                .map(|edge_index| dual_module.get_edge_weight(edge_index))
+                .collect();
+            let negative_edges = dual_module.get_negative_edges();
+            let flip_vertices = dual_module.get_flip_vertices();
+            assert_eq!(negative_edges, BTreeSet::from([1, 5]));
+            assert_eq!(dual_module.get_negative_weight_sum(), Rational::from_float(-2.5).unwrap());
+            // e.g. a round of BP, then restore the weights the flipped syndrome was loaded with
+            let doubled_weights: Vec<Weight> = adjusted_weights
+                .iter()
+                .map(|weight| weight.clone() * Rational::from_usize(2).unwrap())
+                .collect();
+            dual_module.update_weights(doubled_weights, Rational::one());
+            dual_module.reset_weights();
+            for (edge_index, weight) in adjusted_weights.iter().enumerate() {
+                assert!(!weight.is_negative());
+                assert_eq!(&dual_module.get_edge_weight(edge_index), weight);
+            }
+            assert_eq!(dual_module.get_negative_edges(), negative_edges);
+            assert_eq!(dual_module.get_flip_vertices(), flip_vertices);
+            assert_eq!(dual_module.get_negative_weight_sum(), Rational::from_float(-2.5).unwrap());
+        }
+        check(&initializer, &mut DualModulePQ::new_empty(&initializer));
+        check(&initializer, &mut DualModuleSerial::new_empty(&initializer));
+    }
+
+    #[test]
+    fn dual_module_pq_grow_until_obstacle() {
+        // cargo test dual_module_pq_grow_until_obstacle -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![3, 12]);
+        // manual two-step sequence
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph.clone(), &mut dual_module);
+        let length = dual_module.report().get_valid_growth().unwrap();
+        dual_module.grow(length);
+        let dual_report = dual_module.report();
+        // combined method
+        let mut dual_module_2 = DualModulePQ::new_empty(&model_graph.initializer);
+        let interface_ptr_2 = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module_2);
+        let dual_report_2 = dual_module_2.grow_until_obstacle();
+        assert_eq!(interface_ptr.sum_dual_variables(), interface_ptr_2.sum_dual_variables());
+        for edge_index in 0..model_graph.initializer.weighted_edges.len() {
+            assert_eq!(
+                dual_module.get_edge_slack(edge_index),
+                dual_module_2.get_edge_slack(edge_index)
+            );
+        }
+        let mut obstacles: Vec<Obstacle> = dual_report.iter().unwrap().cloned().collect();
+        let mut obstacles_2: Vec<Obstacle> = dual_report_2.iter().unwrap().cloned().collect();
+        assert!(!obstacles.is_empty());
+        obstacles.sort();
+        obstacles_2.sort();
+        assert_eq!(obstacles, obstacles_2);
+    }
+
+    #[test]
+    fn dual_module_pq_requeue_obstacle() {
+        // cargo test dual_module_pq_requeue_obstacle -- --nocapture
+        let code = CodeCapacityRepetitionCode::new(11, 0.1);
+        let model_graph = code.get_model_graph();
+        let initializer = model_graph.initializer.clone();
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph, vec![2, 3, 7, 8]);
+        let mut dual_module = DualModulePQ::new_empty(&initializer);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        let dual_report = dual_module.grow_until_obstacle();
+        let obstacles: Vec<Obstacle> = dual_report.iter().unwrap().cloned().collect();
+        assert_eq!(obstacles.len(), 2);
+        let is_left_conflict = |obstacle: &Obstacle| match obstacle {
+            Obstacle::Conflict { edge_index } => initializer.weighted_edges[*edge_index].vertices.contains(&2),
+            _ => unreachable!(),
+        };
+        let deferred = obstacles.iter().find(|obstacle| !is_left_conflict(obstacle)).unwrap().clone();
+        // handle the left conflict by stopping its nodes, and defer the right one
+        for node_index in [0, 1] {
+            let dual_node_ptr = interface_ptr.get_node(node_index).unwrap();
+            dual_module.set_grow_rate(&dual_node_ptr, Rational::zero());
+        }
+        let reported = |dual_report: DualReport| -> Vec<Obstacle> {
+            dual_report
+                .iter()
+                .map(|obstacles| obstacles.cloned().collect())
+                .unwrap_or_default()
+        };
+        // a reported obstacle is not queued again by itself
+        assert!(!reported(dual_module.report()).contains(&deferred));
+        dual_module.requeue_obstacle(deferred.clone());
+        assert_eq!(reported(dual_module.report()), vec![deferred.clone()]);
+        // and it is gone again unless requeued once more
+        assert!(!reported(dual_module.report()).contains(&deferred));
+    }
+
+    #[test]
+    fn dual_module_pq_edge_grow_rate() {
+        // cargo test dual_module_pq_edge_grow_rate -- --nocapture
+        use crate::dual_module_serial::DualModuleSerial;
+        use crate::model_hypergraph::ModelHyperGraph;
+        let code = CodeCapacityRepetitionCode::new(11, 0.1);
+        let model_graph = code.get_model_graph();
+        let initializer = model_graph.initializer.clone();
+        let find_edge = |vertices: [VertexIndex; 2]| {
+            (initializer.weighted_edges.iter())
+                .position(|edge| vertices.iter().all(|vertex| edge.vertices.contains(vertex)))
+                .unwrap()
+        };
+        let (shared_edge, left_edge) = (find_edge([2, 3]), find_edge([1, 2]));
+        fn check(
+            mut dual_module: impl DualModuleImpl,
+            model_graph: Arc<ModelHyperGraph>,
+            shared_edge: EdgeIndex,
+            left_edge: EdgeIndex,
+        ) {
+            let decoding_graph = DecodingHyperGraph::new_defects(model_graph, vec![2, 3]);
+            let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+            let node_2_ptr = interface_ptr.get_node(0).unwrap();
+            let node_3_ptr = interface_ptr.get_node(1).unwrap();
+            dual_module.set_grow_rate(&node_2_ptr, Rational::from_usize(1).unwrap());
+            dual_module.set_grow_rate(&node_3_ptr, Rational::from_f64(0.5).unwrap());
+            assert_eq!(dual_module.edge_grow_rate(shared_edge), Rational::from_f64(1.5).unwrap());
+            assert_eq!(dual_module.edge_grow_rate(left_edge), Rational::from_usize(1).unwrap());
+            dual_module.set_grow_rate(&node_2_ptr, Rational::zero());
+            assert_eq!(dual_module.edge_grow_rate(shared_edge), Rational::from_f64(0.5).unwrap());
+            assert!(dual_module.edge_grow_rate(left_edge).is_zero());
+        }
+        check(
+            DualModulePQ::new_empty(&initializer),
+            model_graph.clone(),
+            shared_edge,
+            left_edge,
+        );
+        check(DualModuleSerial::new_empty(&initializer), model_graph, shared_edge, left_edge);
+    }
+
+    #[test]
+    fn dual_module_pq_set_grow_rates() {
+        // cargo test dual_module_pq_set_grow_rates -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let defect_vertices = vec![3, 5, 10, 12];
+        let rates = [1., 0.5, 2., 0.25].map(|rate| Rational::from_f64(rate).unwrap());
+        let setup = || {
+            let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), defect_vertices.clone());
+            let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+            let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+            // grow a bit so that the rate changes happen at a non-zero global time
+            dual_module.grow(Rational::from_f64(0.1).unwrap());
+            let rates: Vec<(DualNodePtr, Rational)> = (0..defect_vertices.len())
+                .map(|node_index| interface_ptr.get_node(node_index).unwrap())
+                .zip(rates.iter().cloned())
+                .collect();
+            (dual_module, interface_ptr, rates)
+        };
+        let drain = |dual_module: &mut DualModulePQ| {
+            let mut events = vec![];
+            while let Some(event) = dual_module.obstacle_queue.pop_event() {
+                events.push(event);
+            }
+            events
+        };
+        // one call for all the nodes
+        let (mut batch_module, _batch_interface, batch_rates) = setup();
+        batch_module.set_grow_rates(&batch_rates);
+        // one call per node
+        let (mut single_module, _single_interface, single_rates) = setup();
+        for (dual_node_ptr, grow_rate) in single_rates.iter() {
+            single_module.set_grow_rate(dual_node_ptr, grow_rate.clone());
+        }
+        for ((batch_node_ptr, _), (single_node_ptr, _)) in batch_rates.iter().zip(single_rates.iter()) {
+            assert_eq!(
+                batch_node_ptr.read_recursive().grow_rate,
+                single_node_ptr.read_recursive().grow_rate
+            );
+        }
+        for edge_index in 0..model_graph.initializer.weighted_edges.len() {
+            assert_eq!(
+                batch_module.edge_grow_rate(edge_index),
+                single_module.edge_grow_rate(edge_index)
+            );
+        }
+        let batch_events = drain(&mut batch_module);
+        assert!(!batch_events.is_empty());
+        assert_eq!(batch_events, drain(&mut single_module));
+    }
+
+    #[test]
+    fn dual_module_pq_grow_to_time() {
+        // cargo test dual_module_pq_grow_to_time -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let setup = || {
+            let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![3, 5, 10, 12]);
+            let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+            let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+            dual_module.grow(Rational::from_f64(0.125).unwrap());
+            (dual_module, interface_ptr)
+        };
+        let (mut relative_module, _relative_interface) = setup();
+        relative_module.grow(Rational::from_f64(0.25).unwrap());
+        let (mut absolute_module, _absolute_interface) = setup();
+        absolute_module.grow_to_time(Rational::from_f64(0.375).unwrap());
+        // growing to the current time changes nothing
+        absolute_module.grow_to_time(Rational::from_f64(0.375).unwrap());
+        assert_eq!(absolute_module.get_global_time(), relative_module.get_global_time());
+        for (absolute_edge, relative_edge) in absolute_module.edges.iter().zip(relative_module.edges.iter()) {
+            assert_eq!(
+                absolute_module.edge_exploration(absolute_edge),
+                relative_module.edge_exploration(relative_edge)
+            );
+        }
+        assert_eq!(
+            absolute_module.compute_max_valid_grow(),
+            relative_module.compute_max_valid_grow()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot grow back in time")]
+    fn dual_module_pq_grow_to_time_backwards() {
+        // cargo test dual_module_pq_grow_to_time_backwards -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let mut dual_module = DualModulePQ::new_empty(&code.get_model_graph().initializer);
+        dual_module.grow(Rational::from_f64(0.5).unwrap());
+        dual_module.grow_to_time(Rational::from_f64(0.25).unwrap());
+    }
+
+    #[test]
+    fn dual_module_pq_grow_checked() {
+        // cargo test dual_module_pq_grow_checked -- --nocapture
+        let weight = |value: usize| Rational::from_usize(value).unwrap();
+        let initializer = Arc::new(SolverInitializer::new(
+            2,
+            vec![HyperEdge::new(vec![0, 1], weight(4)), HyperEdge::new(vec![0], weight(6))],
+        ));
+        let model_graph = Arc::new(ModelHyperGraph::new(initializer));
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![0]);
+        let mut dual_module = DualModulePQ::from_model_graph(&model_graph);
+        let _interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        // half way to the first conflict
+        assert_eq!(dual_module.grow_checked(weight(2)), 0);
+        // exactly at the conflict of edge 0, while edge 1 is still growing
+        assert_eq!(dual_module.grow_checked(weight(2)), 1);
+        // the obstacle is still there to be reported
+        let dual_report = dual_module.report();
+        assert_eq!(
+            dual_report.iter().unwrap().collect::<Vec<_>>(),
+            vec![&Obstacle::Conflict { edge_index: 0 }]
+        );
+    }
+
+    #[cfg(feature = "f64_weight")]
+    #[test]
+    fn dual_module_pq_epsilon() {
+        // cargo test --features f64_weight dual_module_pq_epsilon -- --nocapture
+        // the weight 10^13/3 is exact as a rational but rounded in f64: growing at rate 3 until the conflict leaves a
+        // slack of about 5e-4, beyond any fixed tolerance of the order of the default one but not the relative one
+        let initializer = Arc::new(SolverInitializer::new(
+            2,
+            vec![HyperEdge::new(vec![0, 1], Rational::from(1e13 / 3.))],
+        ));
+        let model_graph = Arc::new(ModelHyperGraph::new(initializer));
+        let grow_until_obstacle = |epsilon: f64| {
+            let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![0]);
+            let mut dual_module = DualModulePQ::from_model_graph(&model_graph);
+            dual_module.epsilon = epsilon;
+            let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+            let dual_node_ptr = interface_ptr.read_recursive().nodes[0].clone();
+            dual_module.set_grow_rate(&dual_node_ptr, Rational::from(3.));
+            let dual_report = dual_module.grow_until_obstacle();
+            println!(
+                "epsilon {epsilon}: {dual_report:?}, slack {:?}",
+                dual_module.get_edge_slack(0)
+            );
+            (dual_report, dual_module.is_edge_tight(0))
+        };
+        // the default tolerance, scaled by the weight, catches the conflict
+        let (dual_report, is_tight) = grow_until_obstacle(crate::ordered_float::EPSILON);
+        assert_eq!(
+            dual_report.iter().unwrap().collect::<Vec<_>>(),
+            vec![&Obstacle::Conflict { edge_index: 0 }]
+        );
+        assert!(is_tight);
+        // without tolerance, the conflict is discarded as a stale event
+        let (dual_report, is_tight) = grow_until_obstacle(0.);
+        assert!(matches!(dual_report, DualReport::Unbounded));
+        assert!(!is_tight);
+    }
+
+    #[test]
+    fn dual_module_pq_validate_state() {
+        // cargo test dual_module_pq_validate_state -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![3, 5, 10, 12]);
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let _interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        dual_module.validate_state().unwrap();
+        let length = dual_module.report().get_valid_growth().unwrap();
+        dual_module.grow(length);
+        dual_module.validate_state().unwrap();
+        // corrupt an edge so that it grows beyond its weight
+        let edge_ptr = dual_module.edges[0].clone();
+        let weight = edge_ptr.read_recursive().weight.clone();
+        edge_ptr.write().growth_at_last_updated_time = weight + Rational::one();
+        let error_message = dual_module.validate_state().expect_err("corrupted edge should be detected");
+        println!("{error_message}");
+        assert!(error_message.starts_with("edge 0: growth"));
+        assert!(error_message.contains("exceeds weight"));
+    }
+
+    #[test]
+    fn dual_module_pq_obstacle_stats() {
+        // cargo test dual_module_pq_obstacle_stats -- --nocapture
+        use crate::plugin::*;
+        use crate::plugin_single_hair::PluginSingleHair;
+        use crate::plugin_union_find::PluginUnionFind;
+        use crate::primal_module::PrimalModuleImpl;
+        use crate::primal_module_serial::PrimalModuleSerial;
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        primal_module.plugins = Arc::new(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        assert_eq!(dual_module.obstacle_stats(), ObstacleStats::default());
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(
+            &interface_ptr,
+            Arc::new(SyndromePattern::new_vertices(vec![3, 5, 10, 12])),
+            &mut dual_module,
+        );
+        let stats = dual_module.obstacle_stats();
+        println!("{stats:?}, stale ratio {}", stats.stale_ratio());
+        assert!(stats.validated > 0);
+        assert!(stats.stale > 0);
+        assert!(stats.stale_ratio() > 0. && stats.stale_ratio() < 1.);
+        dual_module.clear();
+        assert_eq!(dual_module.obstacle_stats(), ObstacleStats::default());
+    }
+
+    #[test]
+    fn dual_module_pq_user_event() {
+        // cargo test dual_module_pq_user_event -- --nocapture
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![1]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        let dual_node_ptr = interface_ptr.get_node(0).unwrap();
+        dual_module.set_grow_rate(&dual_node_ptr, Rational::one());
+        // record the global time and the growth of edge 0 when each event fires
+        let fired = Arc::new(parking_lot::Mutex::new(vec![]));
+        for (name, time) in [("late", 1000.), ("half", 0.5), ("quarter", 0.25)] {
+            let fired = fired.clone();
+            dual_module.schedule_user_event(Rational::from_f64(time).unwrap(), move |dual_module: &DualModulePQ| {
+                let (growth, _) = dual_module.edge_exploration(&dual_module.edges[0]);
+                fired.lock().push((name, dual_module.get_global_time(), growth));
+            });
+        }
+        let quarter = Rational::from_f64(0.25).unwrap();
+        let half = Rational::from_f64(0.5).unwrap();
+        assert!(matches!(dual_module.report(), DualReport::ValidGrow(length) if length == quarter));
+        assert!(matches!(dual_module.grow_until_obstacle(), DualReport::Obstacles(_)));
+        let weight = dual_module.get_edge_weight(0).min(dual_module.get_edge_weight(1));
+        assert_eq!(dual_module.get_global_time(), weight);
+        assert_eq!(
+            *fired.lock(),
+            vec![("quarter", quarter.clone(), quarter), ("half", half.clone(), half)]
+        );
+        // the late event stays pending until the time reaches it, and is dropped by clear
+        assert_eq!(dual_module.pending_user_events(), 1);
+        dual_module.clear();
+        assert_eq!(dual_module.pending_user_events(), 0);
+    }
+
+    #[test]
+    fn dual_module_pq_from_model_graph() {
+        // cargo test dual_module_pq_from_model_graph -- --nocapture
+        let code = CodeCapacityColorCode::new(5, 0.1);
+        let model_graph = code.get_model_graph();
+        let dual_module = DualModulePQ::from_model_graph(&model_graph);
+        let reference = DualModulePQ::new_empty(&model_graph.initializer);
+        assert!(Arc::ptr_eq(&dual_module.initializer, &model_graph.initializer));
+        assert_eq!(dual_module.snapshot(false), reference.snapshot(false));
+        // and they decode the same way
+        let grow_first_defect = |mut dual_module: DualModulePQ| {
+            let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![3, 5]);
+            let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+            let dual_node_ptr = interface_ptr.get_node(0).unwrap();
+            dual_module.set_grow_rate(&dual_node_ptr, Rational::one());
+            assert!(matches!(dual_module.grow_until_obstacle(), DualReport::Obstacles(_)));
+            dual_module.get_global_time()
+        };
+        assert_eq!(grow_first_defect(dual_module), grow_first_defect(reference));
+    }
+
+    #[test]
+    fn dual_module_pq_reserve_for_defects() {
+        // cargo test dual_module_pq_reserve_for_defects -- --nocapture
+        let code = CodeCapacityColorCode::new(9, 0.1);
+        let model_graph = code.get_model_graph();
+        let defect_vertices = vec![1, 4, 9, 13, 16, 22, 25, 28];
+        let load = |dual_module: &mut DualModulePQ| {
+            dual_module.clear();
+            let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), defect_vertices.clone());
+            DualModuleInterfacePtr::new_load(decoding_graph, dual_module);
+        };
+        let mut dual_module = DualModulePQ::from_model_graph(&model_graph);
+        dual_module.reserve_for_defects(defect_vertices.len());
+        let capacity = dual_module.obstacle_queue_capacity().unwrap();
+        assert!(capacity >= defect_vertices.len());
+        load(&mut dual_module);
+        assert!(dual_module.obstacle_queue.len() > defect_vertices.len());
+        assert_eq!(dual_module.obstacle_queue_capacity(), Some(capacity));
+        // the reservation survives clear
+        load(&mut dual_module);
+        assert_eq!(dual_module.obstacle_queue_capacity(), Some(capacity));
+        // while the queue of a fresh module reallocates
+        let mut dual_module = DualModulePQ::from_model_graph(&model_graph);
+        let initial_capacity = dual_module.obstacle_queue_capacity().unwrap();
+        load(&mut dual_module);
+        assert_ne!(dual_module.obstacle_queue_capacity(), Some(initial_capacity));
+    }
+
+    /// the edges in a delta snapshot, together with the global time
+    fn snapshot_minimal_edges(dual_module: &mut impl DualModuleImpl) -> (Vec<EdgeIndex>, f64) {
+        let snapshot = dual_module.snapshot_minimal(false);
+        let edges = (snapshot["edges"].as_array().unwrap().iter())
+            .map(|edge| edge["edge_index"].as_u64().unwrap() as EdgeIndex)
+            .collect();
+        (edges, snapshot["global_time"].as_f64().unwrap())
+    }
+
+    fn snapshot_minimal(mut dual_module: impl DualModuleImpl) {
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        // edges: 0: [0, 1], 1: [1, 2], 2: [2, 3], 3: [0], 4: [3]
+        let decoding_graph = DecodingHyperGraph::new_defects(code.get_model_graph(), vec![1]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        // the first delta is complete
+        assert_eq!(snapshot_minimal_edges(&mut dual_module), (vec![0, 1, 2, 3, 4], 0.));
+        assert_eq!(snapshot_minimal_edges(&mut dual_module), (vec![], 0.));
+        dual_module.grow_edge(3, &Rational::from_f64(0.5).unwrap());
+        let snapshot = dual_module.snapshot_minimal(false);
+        assert_eq!(snapshot["edges"].as_array().unwrap().len(), 1);
+        assert_eq!(snapshot["edges"][0]["edge_index"], 3);
+        assert_eq!(snapshot["edges"][0]["growth"], 0.5);
+        // growing the defect node changes its hair edges
+        let dual_node_ptr = interface_ptr.get_node(0).unwrap();
+        dual_module.set_grow_rate(&dual_node_ptr, Rational::one());
+        dual_module.grow(Rational::from_f64(0.25).unwrap());
+        assert_eq!(snapshot_minimal_edges(&mut dual_module), (vec![0, 1], 0.25));
+        assert_eq!(snapshot_minimal_edges(&mut dual_module), (vec![], 0.25));
+        dual_module.clear();
+        assert_eq!(snapshot_minimal_edges(&mut dual_module), (vec![0, 1, 2, 3, 4], 0.));
+    }
+
+    #[test]
+    fn dual_module_pq_snapshot_minimal() {
+        // cargo test dual_module_pq_snapshot_minimal -- --nocapture
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        let initializer = code.get_model_graph().initializer.clone();
+        snapshot_minimal(DualModulePQ::new_empty(&initializer));
+        snapshot_minimal(crate::dual_module_serial::DualModuleSerial::new_empty(&initializer));
+    }
+
+    /// grow a dual node by 1, then shrink it by 2, past its shrink-to-zero event
+    fn over_shrink(mut dual_module: impl DualModuleImpl) -> (Rational, DualReport) {
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        let decoding_graph = DecodingHyperGraph::new_defects(code.get_model_graph(), vec![1]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        let dual_node_ptr = interface_ptr.get_node(0).unwrap();
+        dual_module.set_grow_rate(&dual_node_ptr, Rational::from_usize(1).unwrap());
+        dual_module.grow(Rational::from_f64(0.5).unwrap());
+        dual_module.set_grow_rate(&dual_node_ptr, -Rational::from_usize(1).unwrap());
+        dual_module.grow(Rational::from_usize(1).unwrap());
+        // a clamped node is not shrunk any further, and neither are its hair edges
+        dual_module.grow(Rational::from_usize(1).unwrap());
+        let dual_node = dual_node_ptr.read_recursive();
+        if dual_node.get_dual_variable().is_zero() {
+            assert!(dual_node.grow_rate.is_zero());
+            for &edge_index in dual_node.invalid_subgraph.hair.iter() {
+                assert_eq!(
+                    dual_module.get_edge_slack(edge_index),
+                    dual_module.get_edge_weight(edge_index)
+                );
+            }
+        }
+        (dual_node.get_dual_variable(), dual_module.report())
+    }
+
+    #[test]
+    fn dual_module_pq_over_shrink_clamp() {
+        // cargo test dual_module_pq_over_shrink_clamp -- --nocapture
+        use crate::dual_module_serial::DualModuleSerial;
+        let initializer = CodeCapacityRepetitionCode::new(5, 0.1).get_initializer();
+        let mut pq_module = DualModulePQ::new_empty(&Arc::new(initializer.clone()));
+        pq_module.over_shrink_policy = OverShrinkPolicy::Clamp;
+        let mut serial_module = DualModuleSerial::new_empty(&Arc::new(initializer));
+        serial_module.over_shrink_policy = OverShrinkPolicy::Clamp;
+        for (dual_variable, dual_report) in [over_shrink(pq_module), over_shrink(serial_module)] {
+            assert!(dual_variable.is_zero());
+            // the clamped node has stopped growing, so nothing is left to report
+            assert!(matches!(dual_report, DualReport::Unbounded));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dual node 0 is shrunk to")]
+    fn dual_module_pq_over_shrink_strict() {
+        // cargo test dual_module_pq_over_shrink_strict -- --nocapture
+        let initializer = CodeCapacityRepetitionCode::new(5, 0.1).get_initializer();
+        let mut dual_module = DualModulePQ::new_empty(&Arc::new(initializer));
+        dual_module.over_shrink_policy = OverShrinkPolicy::Strict;
+        over_shrink(dual_module);
+    }
+
+    #[test]
+    fn dual_module_pq_affinity_config() {
+        // cargo test dual_module_pq_affinity_config -- --nocapture
+        use crate::plugin::{EchelonMatrix, PluginManager};
+        use crate::primal_module_serial::*;
+        use crate::relaxer_optimizer::RelaxerOptimizer;
+        use parking_lot::RwLock;
+        let code = CodeCapacityRepetitionCode::new(11, 0.1);
+        let mut dual_module = DualModulePQ::new_empty(&code.get_model_graph().initializer);
+        let new_cluster = |cluster_index: NodeIndex, edges: BTreeSet<EdgeIndex>| {
+            PrimalClusterPtr::new_value(PrimalCluster {
+                cluster_index,
+                nodes: vec![],
+                edges,
+                vertices: BTreeSet::new(),
+                matrix: EchelonMatrix::new(),
+                subgraph: None,
+                optimal: false,
+                plugin_manager: PluginManager::new(Arc::new(vec![]), Arc::new(RwLock::new(1))),
+                relaxer_optimizer: RelaxerOptimizer::new(),
+                #[cfg(all(feature = "incr_lp", feature = "highs"))]
+                incr_solution: None,
+            })
+        };
+        // a small cluster with a small gap and a large cluster with a large gap
+        let small_cluster = new_cluster(0, BTreeSet::from([0]));
+        dual_module.grow_edge(0, &Rational::one());
+        let large_cluster = new_cluster(1, BTreeSet::from([2, 3, 4, 5]));
+        for edge_index in 2..6 {
+            dual_module.grow_edge(edge_index, &Rational::from_f64(0.5).unwrap());
+        }
+        let small_affinity = dual_module.calculate_cluster_affinity(small_cluster.clone()).unwrap();
+        let large_affinity = dual_module.calculate_cluster_affinity(large_cluster.clone()).unwrap();
+        assert_eq!(small_affinity, Affinity::from(0.));
+        assert_eq!(large_affinity, Affinity::from(-2.));
+        // a lower size penalty favors the large cluster instead
+        dual_module.affinity_config.size_penalty = 0.1;
+        let small_affinity = dual_module.calculate_cluster_affinity(small_cluster).unwrap();
+        let large_affinity = dual_module.calculate_cluster_affinity(large_cluster).unwrap();
+        assert!(large_affinity > small_affinity);
+    }
+
+    #[test]
+    fn dual_module_pq_edge_exploration() {
+        // cargo test dual_module_pq_edge_exploration -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph, vec![3, 12]);
+        DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        let length = dual_module.report().get_valid_growth().unwrap();
+        dual_module.grow(length.clone() / Rational::from_usize(2).unwrap());
+        let snapshot = dual_module.snapshot(false);
+        let mut grown_edges = 0;
+        for (edge_ptr, edge_json) in dual_module.edges.iter().zip(snapshot["edges"].as_array().unwrap()) {
+            let (growth, unexplored) = dual_module.edge_exploration(edge_ptr);
+            assert_eq!(growth.to_f64(), edge_json["growth"].as_f64());
+            assert_eq!(unexplored.to_f64(), edge_json["unexplored"].as_f64());
+            assert_eq!(growth + unexplored, edge_ptr.read_recursive().weight);
+            if edge_json["growth"].as_f64().unwrap() > 0. {
+                grown_edges += 1;
+            }
+        }
+        assert!(grown_edges > 0);
+    }
+
+    /// randomized schedules of grow rates that check the invariants after every `report` and `grow`;
+    /// run with `cargo test --features fuzz_test dual_module_pq_fuzz`
+    #[cfg(feature = "fuzz_test")]
+    mod fuzz {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn check_invariants(
+            dual_module: &DualModulePQ,
+            interface_ptr: &DualModuleInterfacePtr,
+        ) -> Result<(), TestCaseError> {
+            let global_time = dual_module.global_time.read_recursive().clone();
+            for edge_ptr in dual_module.edges.iter() {
+                let edge = edge_ptr.read_recursive();
+                prop_assert!(
+                    global_time >= edge.last_updated_time,
+                    "wrap-around on edge {}",
+                    edge.edge_index
+                );
+                drop(edge);
+                let (growth, unexplored) = dual_module.edge_exploration(edge_ptr);
+                prop_assert!(!growth.is_negative(), "negative growth {:?}", growth);
+                prop_assert!(!unexplored.is_negative(), "growth larger than weight by {:?}", -unexplored);
+            }
+            for dual_node_ptr in interface_ptr.read_recursive().nodes.iter() {
+                let dual_node = dual_node_ptr.read_recursive();
+                prop_assert!(
+                    global_time >= dual_node.last_updated_time,
+                    "wrap-around on node {}",
+                    dual_node.index
+                );
+                prop_assert!(!dual_node.get_dual_variable().is_negative(), "negative dual variable");
+            }
+            Ok(())
+        }
+
+        fn random_code(kind: usize, d: VertexNum) -> Box<dyn ExampleCode> {
+            match kind {
+                0 => Box::new(CodeCapacityRepetitionCode::new(d, 0.1)),
+                1 => Box::new(CodeCapacityPlanarCode::new(d, 0.1)),
+                _ => Box::new(CodeCapacityColorCode::new(d, 0.1)),
+            }
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(64))]
+
+            #[test]
+            fn dual_module_pq_fuzz_invariants(
+                kind in 0..3usize,
+                d in prop::sample::select(vec![3 as VertexNum, 5]),
+                defect_mask in prop::collection::vec(any::<bool>(), 64),
+                schedule in prop::collection::vec((any::<usize>(), -1..=2i64, 1..=4usize), 1..40),
+            ) {
+                let code = random_code(kind, d);
+                let model_graph = code.get_model_graph();
+                let defect_vertices: Vec<VertexIndex> =
+                    (0..code.vertex_num()).filter(|&vertex_index| defect_mask[vertex_index % 64]).collect();
+                prop_assume!(!defect_vertices.is_empty());
+                let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), defect_vertices);
+                let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+                let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+                check_invariants(&dual_module, &interface_ptr)?;
+                for (node_selector, grow_rate, fraction) in schedule {
+                    let nodes = interface_ptr.read_recursive().nodes.clone();
+                    let dual_node_ptr = &nodes[node_selector % nodes.len()];
+                    dual_module.set_grow_rate(dual_node_ptr, Rational::from_i64(grow_rate).unwrap());
+                    let dual_report = dual_module.report();
+                    check_invariants(&dual_module, &interface_ptr)?;
+                    if dual_report.is_unbounded() {
+                        continue;
+                    }
+                    if let Some(length) = dual_report.get_valid_growth() {
+                        dual_module.grow(length * Rational::from_usize(fraction).unwrap() / Rational::from_usize(4).unwrap());
+                    } else if let Some(obstacles) = dual_report.iter() {
+                        // stop whatever causes the obstacle so that the next schedule step can proceed
+                        for obstacle in obstacles {
+                            match obstacle {
+                                Obstacle::Conflict { edge_index } => {
+                                    for dual_node_ptr in dual_module.get_edge_nodes(*edge_index) {
+                                        if dual_node_ptr.read_recursive().grow_rate.is_positive() {
+                                            dual_module.set_grow_rate(&dual_node_ptr, Rational::zero());
+                                        }
+                                    }
+                                }
+                                Obstacle::ShrinkToZero { dual_node_ptr } => {
+                                    dual_module.set_grow_rate(&dual_node_ptr.ptr, Rational::zero());
+                                }
+                            }
+                        }
+                    }
+                    check_invariants(&dual_module, &interface_ptr)?;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dual_module_pq_num_tight_edges() {
+        // cargo test dual_module_pq_num_tight_edges -- --nocapture
+        use crate::plugin::*;
+        use crate::plugin_single_hair::PluginSingleHair;
+        use crate::plugin_union_find::PluginUnionFind;
+        use crate::primal_module::PrimalModuleImpl;
+        use crate::primal_module_serial::PrimalModuleSerial;
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let brute_force = |dual_module: &DualModulePQ| {
+            (0..dual_module.edges.len())
+                .filter(|&edge_index| dual_module.is_edge_tight(edge_index))
+                .count()
+        };
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![3, 12]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        assert_eq!(dual_module.num_tight_edges(), 0);
+        for dual_node_ptr in interface_ptr.read_recursive().nodes.iter() {
+            dual_module.set_grow_rate(dual_node_ptr, Rational::one());
+        }
+        let dual_report = dual_module.grow_until_obstacle();
+        println!("dual_report: {dual_report:?}, tight edges: {}", dual_module.num_tight_edges());
+        // all the 6 edges around each of the two defects become tight at the same time
+        assert_eq!(dual_module.num_tight_edges(), 12);
+        assert_eq!(dual_module.num_tight_edges(), brute_force(&dual_module));
+        // shrinking one of the nodes loosens its edges again
+        let dual_node_ptr = interface_ptr.read_recursive().nodes[0].clone();
+        dual_module.set_grow_rate(&interface_ptr.read_recursive().nodes[1], Rational::zero());
+        dual_module.set_grow_rate(&dual_node_ptr, -Rational::one());
+        dual_module.grow(Rational::from_usize(1).unwrap() / Rational::from_usize(10).unwrap());
+        assert_eq!(dual_module.num_tight_edges(), brute_force(&dual_module));
+        assert_eq!(dual_module.num_tight_edges(), 6);
+        // a full decoding, with growth in tuning mode as well
+        dual_module.clear();
+        assert_eq!(dual_module.num_tight_edges(), 0);
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        primal_module.plugins = Arc::new(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![1, 4, 9, 13, 16]);
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(&interface_ptr, decoding_graph.syndrome_pattern.clone(), &mut dual_module);
+        assert!(dual_module.num_tight_edges() > 0);
+        assert_eq!(dual_module.num_tight_edges(), brute_force(&dual_module));
+    }
 }
 
 // Future Object Queues that are constructed with PQ libraries that are bugged
@@ -1270,13 +2627,15 @@ impl<T: Ord + PartialEq + Eq + std::fmt::Debug + Clone + std::ops::Sub<Output =
     }
     fn clear(&mut self) {
         self.container.clear();
-        while !self.heap.is_empty() {
-            self.heap.delete_min();
-        }
+        // `pheap` has no clear, but a fresh heap is as cheap as emptying it
+        self.heap = PairingHeap::new();
     }
     fn len(&self) -> usize {
         self.heap.len()
     }
+    fn reserve(&mut self, additional: usize) {
+        self.container.reserve(additional);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1315,11 +2674,12 @@ impl<T: Ord + PartialEq + Eq + std::fmt::Debug + Clone> FutureQueueMethods<T, Ob
     }
     fn clear(&mut self) {
         self.container.clear();
-        while !self.heap.is_empty() {
-            self.heap.pop();
-        }
+        self.heap.clear();
     }
     fn len(&self) -> usize {
         self.heap.size()
     }
+    fn reserve(&mut self, additional: usize) {
+        self.container.reserve(additional);
+    }
 }
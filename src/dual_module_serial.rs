@@ -0,0 +1,832 @@
+//! Serial Dual Module
+//!
+//! A straightforward serial implementation of the dual module without priority queue:
+//! the maximum update length is recomputed by scanning all edges and dual nodes in every report.
+//! It is slow but simple, serving as a reference oracle to cross-check [`crate::dual_module_pq::DualModulePQ`]
+//!
+
+use crate::num_traits::{Signed, Zero};
+use crate::pointers::*;
+use crate::primal_module::Affinity;
+use crate::primal_module_serial::PrimalClusterPtr;
+use crate::util::*;
+use crate::visualize::*;
+use crate::{add_shared_methods, dual_module::*};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use derivative::Derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct Edge {
+    /// global edge index
+    edge_index: EdgeIndex,
+    /// total weight of this edge
+    weight: Rational,
+    /// the vertices incident to this hyperedge
+    vertices: Vec<VertexIndex>,
+    /// the dual nodes that contributes to this edge
+    #[derivative(Debug = "ignore")]
+    dual_nodes: Vec<OrderedDualNodeWeak>,
+    /// the current growth, always no larger than the weight
+    growth: Rational,
+    /// the sum of grow rates of the dual nodes contributing to this edge
+    grow_rate: Rational,
+    /// set whenever the growth changes; only [`DualModuleImpl::snapshot_minimal`] resets it, other reads like
+    /// [`MWPSVisualizer::snapshot`] leave it set
+    dirty: bool,
+
+    #[cfg(feature = "incr_lp")]
+    /// storing the weights of the clusters that are currently contributing to this edge
+    cluster_weights: hashbrown::HashMap<usize, Rational>,
+}
+
+impl Edge {
+    fn clear(&mut self) {
+        self.growth = Rational::zero();
+        self.grow_rate = Rational::zero();
+        self.dirty = true;
+        self.dual_nodes.clear();
+        #[cfg(feature = "incr_lp")]
+        self.cluster_weights.clear();
+    }
+}
+
+pub type EdgePtr = ArcRwLock<Edge>;
+pub type EdgeWeak = WeakRwLock<Edge>;
+
+pub struct DualModuleSerial {
+    /// whether each vertex is a defect
+    pub is_defect: Vec<bool>,
+    /// all the edges of the decoding graph
+    pub edges: Vec<EdgePtr>,
+    /// all the dual nodes added to this module, in the order of creation
+    nodes: Vec<DualNodePtr>,
+
+    /// the current mode of the dual module
+    mode: DualModuleMode,
+
+    // tuning mode statistics
+    tuning_start_time: Option<Instant>,
+    total_tuning_time: Option<f64>,
+
+    // negative weight handling
+    negative_weight_sum: Rational,
+    negative_edges: BTreeSet<EdgeIndex>,
+    flip_vertices: BTreeSet<VertexIndex>,
+
+    // remember the initializer for original weights
+    pub initializer: Arc<SolverInitializer>,
+
+    /// the coefficients of the cluster affinity
+    pub affinity_config: AffinityConfig,
+
+    /// how to handle a dual variable grown below zero
+    pub over_shrink_policy: OverShrinkPolicy,
+
+    /// the sum of all the lengths grown so far
+    global_time: Rational,
+
+    /// the number of tight edges, maintained whenever the growth or the weight of an edge changes;
+    /// atomic because [`DualModuleImpl::grow_edge`] only takes a shared reference
+    num_tight_edges: AtomicUsize,
+}
+
+impl DualModuleSerial {
+    /// apply [`DualModuleSerial::over_shrink_policy`] to the dual nodes that would be negative after growing by `length`;
+    /// a clamped node only shrinks by its remaining dual variable, together with its hair edges, and stops there
+    fn guard_over_shrink(&mut self, length: &Rational) {
+        let mut clamped = false;
+        for dual_node_ptr in self.nodes.iter() {
+            let mut dual_node = dual_node_ptr.write();
+            if !dual_node.grow_rate.is_negative() {
+                continue;
+            }
+            let remaining = dual_node.get_dual_variable();
+            let dual_variable = &remaining + length * &dual_node.grow_rate;
+            if !dual_variable.is_negative() {
+                continue;
+            }
+            self.over_shrink_policy.apply(dual_node.index, dual_variable);
+            for &edge_index in dual_node.invalid_subgraph.hair.iter() {
+                let mut edge = self.edges[edge_index].write();
+                edge.growth -= &remaining;
+                edge.grow_rate -= &dual_node.grow_rate;
+                edge.dirty = true;
+            }
+            dual_node.set_dual_variable(Rational::zero());
+            dual_node.grow_rate = Rational::zero();
+            clamped = true;
+        }
+        if clamped {
+            self.recount_tight_edges();
+        }
+    }
+
+    /// count the tight edges from scratch, after the weights have been changed
+    fn recount_tight_edges(&mut self) {
+        let num_tight_edges = self
+            .edges
+            .iter()
+            .filter(|edge_ptr| {
+                let edge = edge_ptr.read_recursive();
+                edge.growth == edge.weight
+            })
+            .count();
+        *self.num_tight_edges.get_mut() = num_tight_edges;
+    }
+}
+
+impl DualModuleImpl for DualModuleSerial {
+    /// initialize the dual module, which is supposed to be reused for multiple decoding tasks with the same structure
+    fn new_empty(initializer: &Arc<SolverInitializer>) -> Self {
+        #[cfg(not(feature = "loose_sanity_check"))]
+        initializer.sanity_check().unwrap();
+
+        let edges = initializer
+            .weighted_edges
+            .iter()
+            .enumerate()
+            .map(|(edge_index, hyperedge)| {
+                EdgePtr::new_value(Edge {
+                    edge_index,
+                    weight: hyperedge.weight.clone(),
+                    vertices: hyperedge.vertices.clone(),
+                    dual_nodes: vec![],
+                    growth: Rational::zero(),
+                    grow_rate: Rational::zero(),
+                    dirty: true,
+                    #[cfg(feature = "incr_lp")]
+                    cluster_weights: hashbrown::HashMap::new(),
+                })
+            })
+            .collect();
+        let mut dual_module = Self {
+            is_defect: vec![false; initializer.vertex_num],
+            edges,
+            nodes: vec![],
+            mode: DualModuleMode::default(),
+            tuning_start_time: None,
+            total_tuning_time: None,
+            negative_weight_sum: Rational::zero(),
+            negative_edges: BTreeSet::new(),
+            flip_vertices: BTreeSet::new(),
+            initializer: initializer.clone(),
+            affinity_config: AffinityConfig::default(),
+            over_shrink_policy: OverShrinkPolicy::default(),
+            global_time: Rational::zero(),
+            num_tight_edges: AtomicUsize::new(0),
+        };
+        dual_module.recount_tight_edges();
+        dual_module
+    }
+
+    /// clear all growth and existing dual nodes
+    fn clear(&mut self) {
+        self.is_defect.iter_mut().for_each(|is_defect| *is_defect = false);
+        for (edge_ptr, hyperedge) in self.edges.iter().zip(self.initializer.weighted_edges.iter()) {
+            let mut edge = edge_ptr.write();
+            edge.clear();
+            edge.weight = hyperedge.weight.clone();
+        }
+        self.nodes.clear();
+        self.mode_mut().reset();
+        self.tuning_start_time = None;
+        self.negative_edges.clear();
+        self.negative_weight_sum = Rational::zero();
+        self.flip_vertices.clear();
+        self.global_time = Rational::zero();
+        self.recount_tight_edges();
+    }
+
+    fn add_defect_node(&mut self, dual_node_ptr: &DualNodePtr) {
+        let dual_node = dual_node_ptr.read_recursive();
+        debug_assert!(dual_node.invalid_subgraph.edges.is_empty());
+        debug_assert!(
+            dual_node.invalid_subgraph.vertices.len() == 1,
+            "defect node (without edges) should only work on a single vertex, for simplicity"
+        );
+        let vertex_index = *dual_node.invalid_subgraph.vertices.iter().next().unwrap();
+        assert!(!self.is_defect[vertex_index], "defect should not be added twice");
+        self.is_defect[vertex_index] = true;
+        drop(dual_node);
+        self.add_dual_node(dual_node_ptr);
+    }
+
+    fn add_dual_node(&mut self, dual_node_ptr: &DualNodePtr) {
+        let dual_node = dual_node_ptr.read_recursive();
+        for &edge_index in dual_node.invalid_subgraph.hair.iter() {
+            let mut edge = self.edges[edge_index].write();
+            edge.grow_rate += &dual_node.grow_rate;
+            edge.dual_nodes
+                .push(OrderedDualNodeWeak::new(dual_node.index, dual_node_ptr.downgrade()));
+        }
+        drop(dual_node);
+        self.nodes.push(dual_node_ptr.clone());
+    }
+
+    /// every defect is a dual node
+    fn reserve_for_defects(&mut self, expected: usize) {
+        self.nodes.reserve(expected.saturating_sub(self.nodes.len()));
+    }
+
+    fn add_dual_node_tune(&mut self, dual_node_ptr: &DualNodePtr) {
+        let dual_node = dual_node_ptr.read_recursive();
+        for &edge_index in dual_node.invalid_subgraph.hair.iter() {
+            self.edges[edge_index]
+                .write()
+                .dual_nodes
+                .push(OrderedDualNodeWeak::new(dual_node.index, dual_node_ptr.downgrade()));
+        }
+        drop(dual_node);
+        self.nodes.push(dual_node_ptr.clone());
+    }
+
+    fn set_grow_rate(&mut self, dual_node_ptr: &DualNodePtr, grow_rate: Rational) {
+        let mut dual_node = dual_node_ptr.write();
+        let grow_rate_diff = &grow_rate - &dual_node.grow_rate;
+        dual_node.grow_rate = grow_rate;
+        for &edge_index in dual_node.invalid_subgraph.hair.iter() {
+            self.edges[edge_index].write().grow_rate += &grow_rate_diff;
+        }
+    }
+
+    /// scan all edges and dual nodes to find the obstacles or the maximum valid growth
+    fn report(&mut self) -> DualReport {
+        let mut obstacles = vec![];
+        let mut max_valid_grow: Option<Rational> = None;
+        let mut update_max_valid_grow = |length: Rational| {
+            if !matches!(&max_valid_grow, Some(max) if max <= &length) {
+                max_valid_grow = Some(length);
+            }
+        };
+        for edge_ptr in self.edges.iter() {
+            let edge = edge_ptr.read_recursive();
+            if !edge.grow_rate.is_positive() {
+                continue;
+            }
+            let slack = &edge.weight - &edge.growth;
+            if slack.is_zero() {
+                obstacles.push(Obstacle::Conflict {
+                    edge_index: edge.edge_index,
+                });
+            } else {
+                update_max_valid_grow(slack / &edge.grow_rate);
+            }
+        }
+        for dual_node_ptr in self.nodes.iter() {
+            let dual_node = dual_node_ptr.read_recursive();
+            if !dual_node.grow_rate.is_negative() {
+                continue;
+            }
+            let dual_variable = dual_node.get_dual_variable();
+            if dual_variable.is_zero() {
+                obstacles.push(Obstacle::ShrinkToZero {
+                    dual_node_ptr: OrderedDualNodePtr::new(dual_node.index, dual_node_ptr.clone()),
+                });
+            } else {
+                update_max_valid_grow(dual_variable / -dual_node.grow_rate.clone());
+            }
+        }
+        if !obstacles.is_empty() {
+            return DualReport::Obstacles(obstacles);
+        }
+        match max_valid_grow {
+            Some(length) => DualReport::ValidGrow(length),
+            None => DualReport::Unbounded,
+        }
+    }
+
+    fn grow(&mut self, length: Rational) {
+        assert!(
+            length.is_positive(),
+            "growth should be positive; if desired, please set grow rate to negative for shrinking"
+        );
+        if self.over_shrink_policy != OverShrinkPolicy::Unchecked {
+            self.guard_over_shrink(&length);
+        }
+        let num_tight_edges = self.num_tight_edges.get_mut();
+        for edge_ptr in self.edges.iter() {
+            let mut edge = edge_ptr.write();
+            if !edge.grow_rate.is_zero() {
+                let was_tight = edge.growth == edge.weight;
+                let growth = &length * &edge.grow_rate;
+                edge.growth += growth;
+                edge.dirty = true;
+                debug_assert!(edge.growth <= edge.weight, "growth larger than weight");
+                match (was_tight, edge.growth == edge.weight) {
+                    (false, true) => *num_tight_edges += 1,
+                    (true, false) => *num_tight_edges -= 1,
+                    _ => {}
+                }
+            }
+        }
+        for dual_node_ptr in self.nodes.iter() {
+            let mut dual_node = dual_node_ptr.write();
+            if !dual_node.grow_rate.is_zero() {
+                let dual_variable = dual_node.get_dual_variable() + &length * &dual_node.grow_rate;
+                let dual_variable = self.over_shrink_policy.apply(dual_node.index, dual_variable);
+                dual_node.set_dual_variable(dual_variable);
+            }
+        }
+        self.global_time += length;
+    }
+
+    fn get_edge_nodes(&self, edge_index: EdgeIndex) -> Vec<DualNodePtr> {
+        self.edges[edge_index]
+            .read_recursive()
+            .dual_nodes
+            .iter()
+            .map(|x| x.upgrade_force().ptr)
+            .collect()
+    }
+
+    fn get_edge_slack(&self, edge_index: EdgeIndex) -> Rational {
+        let edge = self.edges[edge_index].read_recursive();
+        &edge.weight - &edge.growth
+    }
+
+    fn is_edge_tight(&self, edge_index: EdgeIndex) -> bool {
+        self.get_edge_slack(edge_index).is_zero()
+    }
+
+    fn num_tight_edges(&self) -> usize {
+        self.num_tight_edges.load(Ordering::Relaxed)
+    }
+
+    fn edge_grow_rate(&self, edge_index: EdgeIndex) -> Rational {
+        self.edges[edge_index].read_recursive().grow_rate.clone()
+    }
+
+    /// the serial module keeps no per-edge time, so only the growth and the dual variables are checked
+    fn validate_state(&self) -> Result<(), String> {
+        for edge_ptr in self.edges.iter() {
+            let edge = edge_ptr.read_recursive();
+            if edge.growth > edge.weight {
+                return Err(format!(
+                    "edge {}: growth {:?} exceeds weight {:?}",
+                    edge.edge_index, edge.growth, edge.weight
+                ));
+            }
+        }
+        for dual_node_ptr in self.nodes.iter() {
+            let dual_node = dual_node_ptr.read_recursive();
+            let dual_variable = dual_node.get_dual_variable();
+            if dual_variable.is_negative() {
+                return Err(format!(
+                    "dual node {}: negative dual variable {dual_variable:?}",
+                    dual_node.index
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn snapshot_minimal(&mut self, abbrev: bool) -> serde_json::Value {
+        let mut edges: Vec<serde_json::Value> = vec![];
+        for edge_ptr in self.edges.iter() {
+            let mut edge = edge_ptr.write();
+            if !edge.dirty {
+                continue;
+            }
+            edge.dirty = false;
+            let unexplored = &edge.weight - &edge.growth;
+            edges.push(json!({
+                if abbrev { "i" } else { "edge_index" }: edge.edge_index,
+                if abbrev { "g" } else { "growth" }: rational_to_f64_lossy(&edge.growth),
+                "gn": numer_of(&edge.growth),
+                "gd": denom_of(&edge.growth),
+                if abbrev { "u" } else { "unexplored" }: rational_to_f64_lossy(&unexplored),
+                "un": numer_of(&unexplored),
+                "ud": denom_of(&unexplored),
+            }));
+        }
+        json!({
+            "global_time": rational_to_f64_lossy(&self.global_time),
+            "tn": numer_of(&self.global_time),
+            "td": denom_of(&self.global_time),
+            "edges": edges,
+        })
+    }
+
+    /* tuning mode related methods */
+
+    add_shared_methods!();
+
+    /// there is no global time to sync, so tuning works directly on the growth of each edge
+    fn is_edge_tight_tune(&self, edge_index: EdgeIndex) -> bool {
+        self.is_edge_tight(edge_index)
+    }
+
+    fn get_edge_slack_tune(&self, edge_index: EdgeIndex) -> Rational {
+        self.get_edge_slack(edge_index)
+    }
+
+    fn advance_mode(&mut self) {
+        self.tuning_start_time = Some(Instant::now());
+        self.mode_mut().advance();
+    }
+
+    fn end_tuning(&mut self) {
+        self.total_tuning_time = Some(self.tuning_start_time.unwrap().elapsed().as_secs_f64());
+    }
+
+    fn get_total_tuning_time(&self) -> Option<f64> {
+        self.total_tuning_time
+    }
+
+    fn clear_tuning_time(&mut self) {
+        self.total_tuning_time = None;
+    }
+
+    /// all states are always up-to-date
+    fn sync(&mut self) {}
+
+    fn grow_edge(&self, edge_index: EdgeIndex, amount: &Rational) {
+        let mut edge = self.edges[edge_index].write();
+        let was_tight = edge.growth == edge.weight;
+        edge.growth += amount;
+        edge.dirty = true;
+        match (was_tight, edge.growth == edge.weight) {
+            (false, true) => self.num_tight_edges.fetch_add(1, Ordering::Relaxed),
+            (true, false) => self.num_tight_edges.fetch_sub(1, Ordering::Relaxed),
+            _ => 0,
+        };
+    }
+
+    fn debug_print(&self) {
+        println!("\n[current states]");
+        for edge_ptr in self.edges.iter() {
+            let edge = edge_ptr.read_recursive();
+            if !edge.growth.is_zero() || !edge.grow_rate.is_zero() {
+                println!("{:?}", edge);
+            }
+        }
+        println!("nodes: {:?}", self.nodes);
+    }
+
+    /* affinity */
+    fn calculate_cluster_affinity(&mut self, cluster: PrimalClusterPtr) -> Option<Affinity> {
+        let cluster = cluster.read_recursive();
+        let mut weight = Rational::zero();
+        for &edge_index in cluster.edges.iter() {
+            weight += &self.edges[edge_index].read_recursive().growth;
+        }
+        for node in cluster.nodes.iter() {
+            weight -= node.read_recursive().dual_node_ptr.read_recursive().get_dual_variable();
+        }
+        if weight.is_zero() {
+            return None;
+        }
+        Some(
+            self.affinity_config
+                .affinity(cluster.edges.len() + cluster.nodes.len(), &weight),
+        )
+    }
+
+    fn get_edge_free_weight(
+        &self,
+        edge_index: EdgeIndex,
+        participating_dual_variables: &hashbrown::HashSet<usize>,
+    ) -> Rational {
+        let edge = self.edges[edge_index].read_recursive();
+        let mut free_weight = edge.weight.clone();
+        for dual_node in edge.dual_nodes.iter() {
+            if participating_dual_variables.contains(&dual_node.index) {
+                continue;
+            }
+            free_weight -= dual_node.upgrade_force().ptr.read_recursive().get_dual_variable();
+        }
+        free_weight
+    }
+
+    fn get_edge_weight(&self, edge_index: EdgeIndex) -> Rational {
+        self.edges[edge_index].read_recursive().weight.clone()
+    }
+
+    #[cfg(feature = "slp")]
+    fn to_lp_problem(&self) -> slp::parser::LpProblem<Rational> {
+        let edge_weights: Vec<Rational> = self.edges.iter().map(|edge| edge.read_recursive().weight.clone()).collect();
+        dual_lp_problem(&edge_weights, &self.nodes)
+    }
+
+    #[cfg(feature = "incr_lp")]
+    fn get_edge_free_weight_cluster(&self, edge_index: EdgeIndex, cluster_index: NodeIndex) -> Rational {
+        let edge = self.edges[edge_index].read_recursive();
+        edge.weight.clone()
+            - edge
+                .cluster_weights
+                .iter()
+                .filter_map(|(c_idx, y)| if cluster_index.ne(c_idx) { Some(y) } else { None })
+                .sum::<Rational>()
+    }
+
+    #[cfg(feature = "incr_lp")]
+    fn update_edge_cluster_weights_union(
+        &self,
+        dual_node_ptr: &DualNodePtr,
+        drained_cluster_index: NodeIndex,
+        absorbing_cluster_index: NodeIndex,
+    ) {
+        let dual_node = dual_node_ptr.read_recursive();
+        for edge_index in dual_node.invalid_subgraph.hair.iter() {
+            let mut edge = self.edges[*edge_index].write();
+            if let Some(removed) = edge.cluster_weights.remove(&drained_cluster_index) {
+                *edge
+                    .cluster_weights
+                    .entry(absorbing_cluster_index)
+                    .or_insert(Rational::zero()) += removed;
+            }
+        }
+    }
+
+    #[cfg(feature = "incr_lp")]
+    fn update_edge_cluster_weights(&self, edge_index: usize, cluster_index: usize, weight: Rational) {
+        *self.edges[edge_index]
+            .write()
+            .cluster_weights
+            .entry(cluster_index)
+            .or_insert(Rational::zero()) += weight;
+    }
+
+    fn adjust_weights_for_negative_edges(&mut self) {
+        for edge_ptr in self.edges.iter() {
+            let mut edge = edge_ptr.write();
+            if edge.weight.is_negative() {
+                self.negative_edges.insert(edge.edge_index);
+                self.negative_weight_sum += edge.weight.clone();
+                for &vertex_index in edge.vertices.iter() {
+                    if !self.flip_vertices.remove(&vertex_index) {
+                        self.flip_vertices.insert(vertex_index);
+                    }
+                }
+                edge.weight = -edge.weight.clone();
+            }
+        }
+        self.recount_tight_edges();
+    }
+
+    fn update_weights(&mut self, new_weights: Vec<Weight>, mix_ratio: Weight) {
+        for (edge_ptr, new_weight) in self.edges.iter().zip(new_weights) {
+            let mut edge = edge_ptr.write();
+            let current_weight = edge.weight.clone();
+            edge.weight = current_weight.clone() + mix_ratio.clone() * (new_weight - current_weight);
+        }
+        self.recount_tight_edges();
+    }
+
+    fn set_weights(&mut self, new_weights: BTreeMap<EdgeIndex, Weight>) {
+        for (edge_index, new_weight) in new_weights.into_iter() {
+            self.edges[edge_index].write().weight = new_weight;
+        }
+        self.recount_tight_edges();
+    }
+
+    fn reset_weights(&mut self) {
+        self.negative_weight_sum = Rational::zero();
+        for (edge_ptr, hyper_edge) in self.edges.iter().zip(self.initializer.weighted_edges.iter()) {
+            let mut edge = edge_ptr.write();
+            if self.negative_edges.contains(&edge.edge_index) {
+                self.negative_weight_sum += hyper_edge.weight.clone();
+                edge.weight = -hyper_edge.weight.clone();
+            } else {
+                edge.weight = hyper_edge.weight.clone();
+            }
+        }
+        self.recount_tight_edges();
+    }
+
+    fn get_negative_weight_sum(&self) -> Rational {
+        self.negative_weight_sum.clone()
+    }
+
+    fn get_negative_edges(&self) -> BTreeSet<EdgeIndex> {
+        self.negative_edges.clone()
+    }
+
+    fn get_flip_vertices(&self) -> BTreeSet<VertexIndex> {
+        self.flip_vertices.clone()
+    }
+}
+
+impl MWPSVisualizer for DualModuleSerial {
+    fn snapshot(&self, abbrev: bool) -> serde_json::Value {
+        let vertices: Vec<serde_json::Value> = self
+            .is_defect
+            .iter()
+            .map(|&is_defect| {
+                json!({
+                    if abbrev { "s" } else { "is_defect" }: i32::from(is_defect),
+                })
+            })
+            .collect();
+        let mut edges: Vec<serde_json::Value> = vec![];
+        for edge_ptr in self.edges.iter() {
+            let edge = edge_ptr.read_recursive();
+            let unexplored = &edge.weight - &edge.growth;
+            edges.push(json!({
+                if abbrev { "w" } else { "weight" }: rational_to_f64_lossy(&edge.weight),
+                "wn": numer_of(&edge.weight),
+                "wd": denom_of(&edge.weight),
+                if abbrev { "v" } else { "vertices" }: edge.vertices,
+                if abbrev { "g" } else { "growth" }: rational_to_f64_lossy(&edge.growth),
+                "gn": numer_of(&edge.growth),
+                "gd": denom_of(&edge.growth),
+                if abbrev { "u" } else { "unexplored" }: rational_to_f64_lossy(&unexplored),
+                "un": numer_of(&unexplored),
+                "ud": denom_of(&unexplored),
+            }));
+        }
+        json!({
+            "vertices": vertices,
+            "edges": edges,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoding_hypergraph::*;
+    use crate::dual_module_pq::DualModulePQ;
+    use crate::example_codes::*;
+    use crate::model_hypergraph::ModelHyperGraph;
+    use crate::plugin::*;
+    use crate::plugin_single_hair::PluginSingleHair;
+    use crate::plugin_union_find::PluginUnionFind;
+    use crate::primal_module::*;
+    use crate::primal_module_serial::PrimalModuleSerial;
+    use num_traits::{FromPrimitive, One};
+
+    fn dual_module_serial_solve(
+        model_graph: &Arc<ModelHyperGraph>,
+        defect_vertices: &[VertexIndex],
+        plugins: PluginVec,
+        mut dual_module: impl DualModuleImpl + MWPSVisualizer,
+    ) -> (Subgraph, WeightRange) {
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        primal_module.plugins = Arc::new(plugins);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), defect_vertices.to_vec());
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(&interface_ptr, decoding_graph.syndrome_pattern.clone(), &mut dual_module);
+        let (subgraph, weight_range) = primal_module.subgraph_range(&interface_ptr, &mut dual_module);
+        assert!(
+            model_graph.matches_subgraph_syndrome(&subgraph, defect_vertices),
+            "the result subgraph is invalid"
+        );
+        let mut subgraph = subgraph.subgraph;
+        subgraph.sort();
+        (subgraph, weight_range)
+    }
+
+    /// decode the same syndrome with both [`DualModuleSerial`] and [`DualModulePQ`] and compare the results
+    fn dual_module_serial_differential(
+        code: impl ExampleCode,
+        defect_vertices: Vec<VertexIndex>,
+        plugins: fn() -> PluginVec,
+    ) {
+        let model_graph = code.get_model_graph();
+        let serial = dual_module_serial_solve(
+            &model_graph,
+            &defect_vertices,
+            plugins(),
+            DualModuleSerial::new_empty(&model_graph.initializer),
+        );
+        let pq = dual_module_serial_solve(
+            &model_graph,
+            &defect_vertices,
+            plugins(),
+            DualModulePQ::new_empty(&model_graph.initializer),
+        );
+        assert_eq!(serial.0, pq.0, "subgraphs differ");
+        assert!(rational_approx_eq(&serial.1.lower, &pq.1.lower));
+        assert!(rational_approx_eq(&serial.1.upper, &pq.1.upper));
+    }
+
+    #[test]
+    fn dual_module_serial_differential_1() {
+        // cargo test dual_module_serial_differential_1 -- --nocapture
+        dual_module_serial_differential(CodeCapacityTailoredCode::new(7, 0., 0.01), vec![23, 24, 29, 30], Vec::new);
+        dual_module_serial_differential(
+            CodeCapacityTailoredCode::new(7, 0., 0.01),
+            vec![16, 17, 23, 25, 29, 30],
+            Vec::new,
+        );
+        dual_module_serial_differential(
+            CodeCapacityPlanarCode::new(11, 0.01),
+            vec![32, 33, 37, 47, 86, 87, 72, 82],
+            Vec::new,
+        );
+    }
+
+    #[test]
+    fn dual_module_serial_differential_2() {
+        // cargo test dual_module_serial_differential_2 -- --nocapture
+        let plugins = || {
+            vec![
+                PluginUnionFind::entry(),
+                PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+            ]
+        };
+        dual_module_serial_differential(
+            CodeCapacityTailoredCode::new(5, 0., 0.01),
+            vec![10, 11, 12, 15, 16, 17, 18],
+            plugins,
+        );
+        dual_module_serial_differential(
+            CodeCapacityPlanarCode::new(11, 0.01),
+            vec![12, 22, 23, 32, 17, 26, 27, 37, 62, 72, 73, 82, 67, 76, 77, 87],
+            plugins,
+        );
+        dual_module_serial_differential(CodeCapacityColorCode::new(7, 0.1), vec![3, 12], plugins);
+    }
+
+    #[test]
+    fn dual_module_serial_num_tight_edges() {
+        // cargo test dual_module_serial_num_tight_edges -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut dual_module = DualModuleSerial::new_empty(&model_graph.initializer);
+        let brute_force = |dual_module: &DualModuleSerial| {
+            (0..dual_module.edges.len())
+                .filter(|&edge_index| dual_module.is_edge_tight(edge_index))
+                .count()
+        };
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), vec![3, 12]);
+        let interface_ptr = DualModuleInterfacePtr::new_load(decoding_graph, &mut dual_module);
+        assert_eq!(dual_module.num_tight_edges(), 0);
+        for dual_node_ptr in interface_ptr.read_recursive().nodes.iter() {
+            dual_module.set_grow_rate(dual_node_ptr, Rational::one());
+        }
+        let dual_report = dual_module.grow_until_obstacle();
+        println!("dual_report: {dual_report:?}, tight edges: {}", dual_module.num_tight_edges());
+        // all the 6 edges around each of the two defects become tight at the same time
+        assert_eq!(dual_module.num_tight_edges(), 12);
+        assert_eq!(dual_module.num_tight_edges(), brute_force(&dual_module));
+        // shrinking one of the nodes loosens its edges again
+        let dual_node_ptr = interface_ptr.read_recursive().nodes[0].clone();
+        dual_module.set_grow_rate(&interface_ptr.read_recursive().nodes[1], Rational::zero());
+        dual_module.set_grow_rate(&dual_node_ptr, -Rational::one());
+        dual_module.grow(Rational::from_usize(1).unwrap() / Rational::from_usize(10).unwrap());
+        assert_eq!(dual_module.num_tight_edges(), brute_force(&dual_module));
+        assert_eq!(dual_module.num_tight_edges(), 6);
+        // a full decoding, with growth in tuning mode as well
+        dual_module.clear();
+        assert_eq!(dual_module.num_tight_edges(), 0);
+        let defect_vertices = vec![1, 4, 9, 13, 16];
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        primal_module.plugins = Arc::new(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), defect_vertices);
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(&interface_ptr, decoding_graph.syndrome_pattern.clone(), &mut dual_module);
+        assert!(dual_module.num_tight_edges() > 0);
+        assert_eq!(dual_module.num_tight_edges(), brute_force(&dual_module));
+    }
+
+    #[cfg(feature = "slp")]
+    #[test]
+    fn dual_module_serial_to_lp_problem() {
+        // cargo test dual_module_serial_to_lp_problem -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let defect_vertices = vec![3, 12];
+        fn check(
+            model_graph: &Arc<ModelHyperGraph>,
+            defect_vertices: &[VertexIndex],
+            dual_module: &mut impl DualModuleImpl,
+        ) {
+            let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+            primal_module.plugins = Arc::new(vec![
+                PluginUnionFind::entry(),
+                PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+            ]);
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(
+                &interface_ptr,
+                Arc::new(SyndromePattern::new_vertices(defect_vertices.to_vec())),
+                dual_module,
+            );
+            let (_, weight_range) = primal_module.subgraph_range(&interface_ptr, dual_module);
+            assert_eq!(weight_range.lower, weight_range.upper, "the decoding should be optimal");
+            let lp_problem = dual_module.to_lp_problem();
+            match slp::Solver::from(lp_problem).solve() {
+                slp::Solution::Optimal(optimum, _) => {
+                    println!("optimum: {optimum}, weight range: {weight_range:?}");
+                    assert_eq!(optimum + dual_module.get_negative_weight_sum(), weight_range.lower);
+                }
+                _ => panic!("the LP of the dual module should have an optimal solution"),
+            }
+        }
+        let mut dual_module = DualModuleSerial::new_empty(&model_graph.initializer);
+        check(&model_graph, &defect_vertices, &mut dual_module);
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        check(&model_graph, &defect_vertices, &mut dual_module);
+    }
+}
@@ -10,7 +10,7 @@
 
 use crate::derivative::Derivative;
 use crate::model_hypergraph::*;
-use crate::num_traits::{FromPrimitive, ToPrimitive, Zero};
+use crate::num_traits::{FromPrimitive, Zero};
 use crate::rand_xoshiro::rand_core::SeedableRng;
 use crate::serde_json;
 use crate::util::*;
@@ -19,7 +19,7 @@ use crate::util_py::*;
 use crate::visualize::*;
 #[cfg(feature = "python_binding")]
 use pyo3::prelude::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, BufRead};
@@ -127,6 +127,16 @@ impl CodeEdge {
     }
 }
 
+/// the initializer of a code bundled with the visualization positions of its vertices, so that both the solver and
+/// the visualizer can be constructed from a single call to [`ExampleCode::to_initializer_with_positions`]
+#[derive(Debug, Clone)]
+pub struct InitializerWithPositions {
+    /// shared so that it can be passed to the solver constructors directly
+    pub initializer: Arc<SolverInitializer>,
+    /// the position of each vertex, indexed by the vertex index
+    pub positions: Vec<VisualizePosition>,
+}
+
 /// default function for computing (pre-scaled) weight from probability
 #[cfg_attr(feature = "python_binding", pyfunction)]
 pub fn weight_of_p(p: f64) -> f64 {
@@ -135,6 +145,12 @@ pub fn weight_of_p(p: f64) -> f64 {
     ((1. - p) / p).ln()
 }
 
+/// the inverse of [`weight_of_p`], recovering the probability from a (pre-scaled) weight
+#[cfg_attr(feature = "python_binding", pyfunction)]
+pub fn p_of_weight(weight: f64) -> f64 {
+    1. / (weight.exp() + 1.)
+}
+
 pub trait ExampleCode {
     /// get mutable references to vertices and edges
     fn vertices_edges(&mut self) -> (&mut Vec<CodeVertex>, &mut Vec<CodeEdge>);
@@ -243,10 +259,8 @@ pub trait ExampleCode {
         }
         // check duplicated referenced edge from each vertex
         for (vertex_idx, vertex) in vertices.iter().enumerate() {
+            // isolated vertices (e.g. unused ancillas) are allowed: a defect on them is reported as infeasible
             let mut existing_edges = HashMap::<EdgeIndex, ()>::new();
-            if vertex.neighbor_edges.is_empty() {
-                return Err(format!("vertex {} do not have any neighbor edges", vertex_idx));
-            }
             for edge_idx in vertex.neighbor_edges.iter() {
                 if existing_edges.contains_key(edge_idx) {
                     return Err(format!("duplicate referred edge {} from vertex {}", edge_idx, vertex_idx));
@@ -265,6 +279,15 @@ pub trait ExampleCode {
         }
     }
 
+    /// set probability of each edge individually, e.g. for biased or measured noise; call `compute_weights` afterwards
+    fn set_probabilities(&mut self, ps: &[f64]) {
+        let (_vertices, edges) = self.vertices_edges();
+        assert_eq!(ps.len(), edges.len(), "must provide exactly one probability per edge");
+        for (edge, &p) in edges.iter_mut().zip(ps.iter()) {
+            edge.p = p;
+        }
+    }
+
     /// set erasure probability of all edges; user can set individual probabilities
     fn set_erasure_probability(&mut self, pe: f64) {
         let (_vertices, edges) = self.vertices_edges();
@@ -315,6 +338,14 @@ pub trait ExampleCode {
         SolverInitializer::new(vertex_num, weighted_edges)
     }
 
+    /// generate the initializer together with the vertex positions for one-call setup of solver and visualizer
+    fn to_initializer_with_positions(&self) -> InitializerWithPositions {
+        InitializerWithPositions {
+            initializer: Arc::new(self.get_initializer()),
+            positions: self.get_positions(),
+        }
+    }
+
     fn get_model_graph(&self) -> Arc<ModelHyperGraph> {
         let initializer = Arc::new(self.get_initializer());
         Arc::new(ModelHyperGraph::new(initializer))
@@ -458,6 +489,43 @@ pub trait ExampleCode {
         let (vertices, _edges) = self.immutable_vertices_edges();
         vertices[vertex_idx].is_defect
     }
+
+    /// the edge sets of the logical operators: a residual error (physical errors plus correction) without syndrome
+    /// is a logical error if and only if it overlaps with any of them on an odd number of edges;
+    /// empty if the logical structure of the code is unknown
+    fn logical_operators(&self) -> Vec<Vec<EdgeIndex>> {
+        vec![]
+    }
+
+    /// check whether a valid correction of the physical errors leads to a logical error
+    fn is_logical_error(&self, error_pattern: &[EdgeIndex], correction: &OutputSubgraph) -> bool {
+        let mut residual: BTreeSet<EdgeIndex> = error_pattern.iter().cloned().collect();
+        for &edge_index in correction.iter() {
+            if !residual.remove(&edge_index) {
+                residual.insert(edge_index);
+            }
+        }
+        self.logical_operators().iter().any(|logical_operator| {
+            logical_operator
+                .iter()
+                .filter(|edge_index| residual.contains(edge_index))
+                .count()
+                % 2
+                == 1
+        })
+    }
+}
+
+/// the left boundary edges of the repetition and planar codes, where each row creates its left-most
+/// boundary edge right before its right-most one; any chain between the two boundaries crosses it an odd number of times
+fn left_boundary_edges(edges: &[CodeEdge]) -> Vec<EdgeIndex> {
+    edges
+        .iter()
+        .enumerate()
+        .filter(|(_, edge)| edge.vertices.len() == 1)
+        .map(|(edge_index, _)| edge_index as EdgeIndex)
+        .step_by(2)
+        .collect()
 }
 
 #[cfg(feature = "python_binding")]
@@ -487,6 +555,10 @@ macro_rules! bind_trait_example_code {
             fn trait_set_probability(&mut self, p: f64) {
                 self.set_probability(p)
             }
+            #[pyo3(name = "set_probabilities")]
+            fn trait_set_probabilities(&mut self, ps: Vec<f64>) {
+                self.set_probabilities(&ps)
+            }
             #[pyo3(name = "set_erasure_probability")]
             fn trait_set_erasure_probability(&mut self, p: f64) {
                 self.set_erasure_probability(p)
@@ -503,6 +575,10 @@ macro_rules! bind_trait_example_code {
             fn trait_get_initializer(&self) -> SolverInitializer {
                 self.get_initializer()
             }
+            #[pyo3(name = "logical_operators")]
+            fn trait_logical_operators(&self) -> Vec<Vec<EdgeIndex>> {
+                self.logical_operators()
+            }
             #[pyo3(name = "set_defect_vertices")]
             fn trait_set_defect_vertices(&mut self, defect_vertices: Vec<VertexIndex>) {
                 self.set_defect_vertices(&defect_vertices)
@@ -566,7 +642,7 @@ where
         let mut edges = Vec::<serde_json::Value>::new();
         for edge in self_edges.iter() {
             edges.push(json!({
-                if abbrev { "w" } else { "weight" }: edge.weight.to_f64(),
+                if abbrev { "w" } else { "weight" }: rational_to_f64_lossy(&edge.weight),
                 "wn": numer_of(&edge.weight),
                 "wd": denom_of(&edge.weight),
                 if abbrev { "v" } else { "vertices" }: edge.vertices,
@@ -598,6 +674,9 @@ impl ExampleCode for CodeCapacityRepetitionCode {
     fn immutable_vertices_edges(&self) -> (&Vec<CodeVertex>, &Vec<CodeEdge>) {
         (&self.vertices, &self.edges)
     }
+    fn logical_operators(&self) -> Vec<Vec<EdgeIndex>> {
+        vec![left_boundary_edges(&self.edges)]
+    }
 }
 
 #[cfg(feature = "python_binding")]
@@ -675,6 +754,9 @@ impl ExampleCode for CodeCapacityPlanarCode {
     fn immutable_vertices_edges(&self) -> (&Vec<CodeVertex>, &Vec<CodeEdge>) {
         (&self.vertices, &self.edges)
     }
+    fn logical_operators(&self) -> Vec<Vec<EdgeIndex>> {
+        vec![left_boundary_edges(&self.edges)]
+    }
 }
 
 #[cfg(feature = "python_binding")]
@@ -1041,6 +1123,10 @@ impl ExampleCode for CodeCapacityColorCode {
     fn immutable_vertices_edges(&self) -> (&Vec<CodeVertex>, &Vec<CodeEdge>) {
         (&self.vertices, &self.edges)
     }
+    fn logical_operators(&self) -> Vec<Vec<EdgeIndex>> {
+        // the triangular color code has a transversal logical operator supported on all the data qubits
+        vec![(0..self.edges.len() as EdgeIndex).collect()]
+    }
 }
 
 #[cfg(feature = "python_binding")]
@@ -1574,6 +1660,91 @@ mod tests {
         visualize_code(&mut code, "example_code_capacity_color_code.json".to_string());
     }
 
+    #[test]
+    fn example_code_p_of_weight() {
+        // cargo test example_code_p_of_weight -- --nocapture
+        for p in [1e-6, 0.001, 0.01, 0.1, 0.3, 0.5, 0.7] {
+            let recovered = p_of_weight(weight_of_p(p));
+            assert!(
+                (recovered - p).abs() <= 1e-12 * p.max(1e-3),
+                "p = {p}, recovered = {recovered}"
+            );
+        }
+        assert_eq!(p_of_weight(0.), 0.5);
+    }
+
+    #[test]
+    fn example_code_set_probabilities() {
+        // cargo test example_code_set_probabilities -- --nocapture
+        let mut code = CodeCapacityRepetitionCode::new(5, 0.1);
+        let ps: Vec<f64> = (0..code.edge_num()).map(|i| 0.01 * (i + 1) as f64).collect();
+        code.set_probabilities(&ps);
+        code.compute_weights();
+        code.sanity_check().unwrap();
+        for (edge, &p) in code.edges().iter().zip(ps.iter()) {
+            assert_eq!(edge.p, p);
+        }
+        // lower probability means higher weight
+        let weights = code.get_weights();
+        for window in weights.windows(2) {
+            assert!(window[0] > window[1]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn example_code_set_probabilities_wrong_length() {
+        // cargo test example_code_set_probabilities_wrong_length -- --nocapture
+        let mut code = CodeCapacityRepetitionCode::new(5, 0.1);
+        code.set_probabilities(&[0.1, 0.2]);
+    }
+
+    #[test]
+    fn example_code_to_initializer_with_positions() {
+        // cargo test example_code_to_initializer_with_positions -- --nocapture
+        use crate::mwpf_solver::{SolverSerialJointSingleHair, SolverTrait};
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let bundle = code.to_initializer_with_positions();
+        assert_eq!(bundle.positions.len(), bundle.initializer.vertex_num);
+        assert_eq!(bundle.positions.len(), code.vertex_num());
+        let mut solver = SolverSerialJointSingleHair::new(&bundle.initializer, json!({}));
+        let mut visualizer = Visualizer::new_with_initializer_positions(None, &bundle, true).unwrap();
+        solver.solve_visualizer(SyndromePattern::new_vertices(vec![3, 12]), Some(&mut visualizer));
+        assert!(bundle.initializer.matches_subgraph_syndrome(&solver.subgraph(), &[3, 12]));
+    }
+
+    #[test]
+    fn example_code_capacity_color_code_logical_error() {
+        // cargo test example_code_capacity_color_code_logical_error -- --nocapture
+        use crate::mwpf_solver::{SolverSerialJointSingleHair, SolverTrait};
+        let mut code = CodeCapacityColorCode::new(3, 0.1);
+        // the logical operator must not be detected by any stabilizer
+        for d in [3, 5, 7] {
+            let mut code = CodeCapacityColorCode::new(d, 0.1);
+            for logical_operator in code.logical_operators() {
+                code.set_physical_errors(&logical_operator);
+                assert!(code.get_defect_vertices().is_empty());
+            }
+        }
+        let initializer = Arc::new(code.get_initializer());
+        let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+        // errors on two corners (edges 0 and 2) are corrected by the single edge 1 in between, a logical error
+        for (error_pattern, is_logical_error) in [(vec![3], false), (vec![0, 2], true)] {
+            code.set_physical_errors(&error_pattern);
+            solver.solve(code.get_syndrome());
+            let (correction, _) = solver.subgraph_range();
+            code.validate_correction(&correction);
+            assert_eq!(code.is_logical_error(&error_pattern, &correction), is_logical_error);
+            solver.clear();
+        }
+        // the boundary logical operator of the repetition and planar codes
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        let all_edges: Vec<EdgeIndex> = (0..code.edges.len()).collect();
+        assert!(code.is_logical_error(&all_edges, &OutputSubgraph::new(vec![], BTreeSet::new())));
+        let code = CodeCapacityPlanarCode::new(5, 0.1);
+        assert_eq!(code.logical_operators()[0].len(), 5);
+    }
+
     #[test]
     fn example_code_correction_validity_code_capacity_repetition_code() {
         // cargo test --release example_code_correction_validity_code_capacity_repetition_code -- --nocapture
@@ -18,6 +18,47 @@ mod rank_pairing_heap;
 pub use pairing_heap::*;
 pub use rank_pairing_heap::*;
 
+/// Increments an [`OpCounts`] counter of a heap when the `stats` feature is enabled
+macro_rules! count_op {
+    ($heap:expr, $op:ident) => {
+        #[cfg(feature = "stats")]
+        {
+            $heap.op_counts.$op += 1;
+        }
+    };
+}
+pub(crate) use count_op;
+
+/// [`OpCounts`] records how many operations a heap has performed, to compare heap implementations on a real workload
+///
+/// ```rust
+/// use heapz::{Heap, PairingHeap};
+///
+/// let mut heap = PairingHeap::min();
+///
+/// heap.push("Hello", 5);
+/// heap.push("World", 2);
+/// heap.pop();
+///
+/// let counts = heap.op_counts();
+///
+/// assert_eq!((counts.pushes, counts.pops, counts.melds), (2, 1, 1));
+/// ```
+#[cfg(feature = "stats")]
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub struct OpCounts {
+    /// number of elements pushed
+    pub pushes: usize,
+    /// number of elements popped (including the ones deleted by key)
+    pub pops: usize,
+    /// number of times two trees were merged into one
+    pub melds: usize,
+    /// number of updates that increased the priority of an element
+    pub decrease_keys: usize,
+    /// number of comparisons between element values
+    pub comparisons: usize,
+}
+
 /// [`HeapType`] Represents whether a heap/queue is min ([`HeapType::Min`]) or max ([`HeapType::Max`]) priority
 #[derive(PartialEq, Copy, Clone, Debug)]
 enum HeapType {
@@ -144,6 +185,63 @@ where
     /// check_heap(PairingHeap::min());
     /// ```
     fn pop(&mut self) -> Option<K>;
+
+    /// Removes all elements from the [`Heap`]
+    ///
+    /// ```rust
+    /// use heapz::{PairingHeap, Heap};
+    ///
+    /// fn check_heap<T: Heap<String, u8>>(mut heap: T) {
+    ///
+    ///     heap.push("Hello".to_string(), 5);
+    ///     heap.push("World".to_string(), 2);
+    ///
+    ///     heap.clear();
+    ///
+    ///     assert_eq!(heap.size(), 0);
+    ///     assert_eq!(heap.top(), None);
+    /// }
+    ///
+    /// check_heap(PairingHeap::min());
+    /// ```
+    fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Consumes the [`Heap`] and returns its elements in priority order, i.e. ascending for a min heap and descending for a max heap
+    ///
+    /// ```rust
+    /// use heapz::{PairingHeap, RankPairingHeap, Heap};
+    /// use rand::Rng;
+    ///
+    /// // keys must be unique, so each value is paired with the index it was pushed at
+    /// fn fill_heap<T: Heap<(u32, usize), u32>>(mut heap: T) -> T {
+    ///     let mut rng = rand::thread_rng();
+    ///     (0..100).for_each(|index| {
+    ///         let value = rng.gen_range(0..1000);
+    ///         heap.push((value, index), value);
+    ///     });
+    ///     heap
+    /// }
+    ///
+    /// let ascending = fill_heap(PairingHeap::min()).into_sorted_vec();
+    /// assert_eq!(ascending.len(), 100);
+    /// assert!(ascending.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    ///
+    /// let descending = fill_heap(RankPairingHeap::multi_pass_max2()).into_sorted_vec();
+    /// assert_eq!(descending.len(), 100);
+    /// assert!(descending.windows(2).all(|pair| pair[0].0 >= pair[1].0));
+    /// ```
+    fn into_sorted_vec(mut self) -> Vec<K>
+    where
+        Self: Sized,
+    {
+        let mut sorted = Vec::with_capacity(self.size());
+        while let Some(key) = self.pop() {
+            sorted.push(key);
+        }
+        sorted
+    }
 }
 
 /// [`DecreaseKey`] defines extra methods for a [`Heap`] that implement decrease-key and delete operations
@@ -1,4 +1,6 @@
-use crate::{Heap, HeapType};
+#[cfg(feature = "stats")]
+use crate::OpCounts;
+use crate::{count_op, Heap, HeapType};
 use std::hash::Hash;
 
 type BoxedNode<K, V> = Box<Node<K, V>>;
@@ -37,6 +39,8 @@ pub struct PairingHeap<K, V: PartialOrd> {
     root: Option<BoxedNode<K, V>>,
     heap_type: HeapType,
     size: usize,
+    #[cfg(feature = "stats")]
+    op_counts: OpCounts,
 }
 
 impl<K, V: PartialOrd> PairingHeap<K, V> {
@@ -67,9 +71,28 @@ impl<K, V: PartialOrd> PairingHeap<K, V> {
             root: None,
             heap_type,
             size: 0,
+            #[cfg(feature = "stats")]
+            op_counts: OpCounts::default(),
         }
     }
 
+    /// Returns the number of operations performed on the [`PairingHeap`] since it was created
+    ///
+    /// ```rust
+    /// use heapz::{Heap, PairingHeap};
+    ///
+    /// let mut heap = PairingHeap::max();
+    ///
+    /// heap.push("Hello", 5);
+    /// heap.push("World", 2);
+    ///
+    /// assert_eq!(heap.op_counts().comparisons, 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn op_counts(&self) -> OpCounts {
+        self.op_counts
+    }
+
     fn compare(&self, a: &BoxedNode<K, V>, b: &BoxedNode<K, V>) -> bool {
         match self.heap_type {
             HeapType::Max => a.value >= b.value,
@@ -91,10 +114,14 @@ impl<K, V: PartialOrd> PairingHeap<K, V> {
         node_b: Option<BoxedNode<K, V>>,
     ) -> Option<BoxedNode<K, V>> {
         match (node_a, node_b) {
-            (Some(a), Some(b)) => Some(if self.compare(&a, &b) {
-                Self::add_child(a, b)
-            } else {
-                Self::add_child(b, a)
+            (Some(a), Some(b)) => Some({
+                count_op!(self, melds);
+                count_op!(self, comparisons);
+                if self.compare(&a, &b) {
+                    Self::add_child(a, b)
+                } else {
+                    Self::add_child(b, a)
+                }
             }),
             (Some(a), None) => Some(a),
             (None, Some(b)) => Some(b),
@@ -175,6 +202,7 @@ impl<K: Hash + Eq, V: PartialOrd> Heap<K, V> for PairingHeap<K, V> {
             Some(Box::new(Node::new(key, value)))
         };
         self.size += 1;
+        count_op!(self, pushes);
     }
 
     /// Returns the highest priority element of a [`PairingHeap`] (or None)
@@ -232,8 +260,32 @@ impl<K: Hash + Eq, V: PartialOrd> Heap<K, V> for PairingHeap<K, V> {
     fn pop(&mut self) -> Option<K> {
         self.root.take().map(|mut node| {
             self.size -= 1;
+            count_op!(self, pops);
             self.root = self.two_pass_merge(node.left.take());
             node.key
         })
     }
+
+    /// Removes all elements from the [`PairingHeap`]
+    ///
+    /// ```rust
+    /// use heapz::{PairingHeap, Heap};
+    ///
+    /// let mut heap = PairingHeap::max();
+    ///
+    /// heap.push("Hello".to_string(), 5);
+    /// heap.clear();
+    ///
+    /// assert_eq!(heap.size(), 0);
+    /// assert_eq!(heap.top(), None);
+    /// ```
+    fn clear(&mut self) {
+        // unlink the children iteratively, dropping a long chain of boxes recursively could overflow the stack
+        let mut stack: Vec<BoxedNode<K, V>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.next.take());
+        }
+        self.size = 0;
+    }
 }
@@ -1,5 +1,7 @@
 use crate::utils::Bucket;
-use crate::{DecreaseKey, Heap, HeapType};
+#[cfg(feature = "stats")]
+use crate::OpCounts;
+use crate::{count_op, DecreaseKey, Heap, HeapType};
 use std::{
     cmp::{max, Eq},
     collections::HashMap,
@@ -65,7 +67,8 @@ impl<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd + Clone + std::fmt::D
 Due to the [difficulty](https://rcoh.me/posts/rust-linked-list-basically-impossible/) in creating [doubly linked lists](https://en.wikipedia.org/wiki/Doubly_linked_list) using safe rust, this [rank pairing heap](https://skycocoo.github.io/Rank-Pairing-Heap/) implementation uses an array to store nodes and uses their indices as pointers.
 
 [rank pairing heaps](https://skycocoo.github.io/Rank-Pairing-Heap/) have a few variations on how their ranks are calculated, how the heap is restructured and the order in which priority is determined.
-To address these different options there are three properties that can be set in any combination for the [`RankPairingHeap`]: [`HeapType`], [`HeapRank`] and [`HeapPasses`]
+To address these different options there are three properties that can be set in any combination for the [`RankPairingHeap`]: [`HeapType`], [`HeapRank`] and [`HeapPasses`],
+either through the named constructors (e.g. [`RankPairingHeap::multi_pass_min2`]) or through a [`RankPairingHeapBuilder`]
  */
 pub struct RankPairingHeap<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd + Clone + std::fmt::Debug> {
     root: Position,
@@ -74,6 +77,8 @@ pub struct RankPairingHeap<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd
     passes: HeapPasses,
     list: Vec<Node<K, V>>,
     keys: HashMap<K, Position>,
+    #[cfg(feature = "stats")]
+    op_counts: OpCounts,
 }
 
 // impelement clone
@@ -86,6 +91,8 @@ impl<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd + Clone + std::fmt::D
             passes: self.passes.clone(),
             list: self.list.clone(),
             keys: self.keys.clone(),
+            #[cfg(feature = "stats")]
+            op_counts: self.op_counts,
         }
     }
 }
@@ -116,9 +123,29 @@ impl<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd + Clone + std::fmt::D
             passes,
             list: vec![],
             keys: HashMap::new(),
+            #[cfg(feature = "stats")]
+            op_counts: OpCounts::default(),
         }
     }
 
+    /// Returns the number of operations performed on the [`RankPairingHeap`] since it was created
+    ///
+    /// ```rust
+    /// use heapz::{DecreaseKey, Heap, RankPairingHeap};
+    ///
+    /// let mut heap = RankPairingHeap::multi_pass_min2();
+    ///
+    /// heap.push("Hello", 5);
+    /// heap.push("World", 2);
+    /// heap.update(&"Hello", 1);
+    ///
+    /// assert_eq!(heap.op_counts().decrease_keys, 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn op_counts(&self) -> OpCounts {
+        self.op_counts
+    }
+
     /// Initializes a max ([`HeapType::Max`]) heap using [`HeapRank::One`] and [`HeapPasses::Single`]
     ///
     /// ```rust
@@ -208,6 +235,175 @@ impl<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd + Clone + std::fmt::D
     }
 }
 
+/**
+[`RankPairingHeapBuilder`] builds a [`RankPairingHeap`] from any combination of [`HeapType`], [`HeapPasses`] and [`HeapRank`],
+as an alternative to the named constructors such as [`RankPairingHeap::multi_pass_min2`]
+
+By default it builds a min heap using multiple passes and [`HeapRank::Two`], the same as [`RankPairingHeap::multi_pass_min2`]
+ */
+#[derive(Clone, Debug)]
+pub struct RankPairingHeapBuilder {
+    heap_type: HeapType,
+    heap_rank: HeapRank,
+    passes: HeapPasses,
+}
+
+impl Default for RankPairingHeapBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RankPairingHeapBuilder {
+    /// Initializes a builder for a min heap using [`HeapRank::Two`] and [`HeapPasses::Multi`]
+    ///
+    /// ```rust
+    /// use heapz::{Heap, RankPairingHeap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap: RankPairingHeap<&str, i32> = RankPairingHeapBuilder::new().build();
+    ///
+    /// heap.push("Hello", 2);
+    /// heap.push("World", 5);
+    ///
+    /// assert_eq!(heap.pop(), Some("Hello"));
+    /// ```
+    pub fn new() -> Self {
+        RankPairingHeapBuilder {
+            heap_type: HeapType::Min,
+            heap_rank: HeapRank::Two,
+            passes: HeapPasses::Multi,
+        }
+    }
+
+    /// Prioritizes elements with the minimum value ([`HeapType::Min`])
+    ///
+    /// ```rust
+    /// use heapz::{Heap, RankPairingHeap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap: RankPairingHeap<&str, i32> = RankPairingHeapBuilder::new().min().build();
+    ///
+    /// heap.push("Hello", 2);
+    /// heap.push("World", 5);
+    ///
+    /// assert_eq!(heap.top(), Some(&"Hello"));
+    /// ```
+    pub fn min(mut self) -> Self {
+        self.heap_type = HeapType::Min;
+        self
+    }
+
+    /// Prioritizes elements with the maximum value ([`HeapType::Max`])
+    ///
+    /// ```rust
+    /// use heapz::{Heap, RankPairingHeap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap: RankPairingHeap<&str, i32> = RankPairingHeapBuilder::new().max().build();
+    ///
+    /// heap.push("Hello", 2);
+    /// heap.push("World", 5);
+    ///
+    /// assert_eq!(heap.top(), Some(&"World"));
+    /// ```
+    pub fn max(mut self) -> Self {
+        self.heap_type = HeapType::Max;
+        self
+    }
+
+    /// Restructures the heap lazily ([`HeapPasses::Single`])
+    ///
+    /// ```rust
+    /// use heapz::{Heap, RankPairingHeap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap: RankPairingHeap<&str, i32> = RankPairingHeapBuilder::new().single_pass().build();
+    ///
+    /// heap.push("Hello", 2);
+    /// heap.push("World", 5);
+    ///
+    /// assert_eq!(heap.pop(), Some("Hello"));
+    /// assert_eq!(heap.pop(), Some("World"));
+    /// ```
+    pub fn single_pass(mut self) -> Self {
+        self.passes = HeapPasses::Single;
+        self
+    }
+
+    /// Restructures the heap eagerly ([`HeapPasses::Multi`])
+    ///
+    /// ```rust
+    /// use heapz::{Heap, RankPairingHeap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap: RankPairingHeap<&str, i32> = RankPairingHeapBuilder::new().multi_pass().build();
+    ///
+    /// heap.push("Hello", 2);
+    /// heap.push("World", 5);
+    ///
+    /// assert_eq!(heap.pop(), Some("Hello"));
+    /// assert_eq!(heap.pop(), Some("World"));
+    /// ```
+    pub fn multi_pass(mut self) -> Self {
+        self.passes = HeapPasses::Multi;
+        self
+    }
+
+    /// Calculates ranks using [`HeapRank::One`]
+    ///
+    /// ```rust
+    /// use heapz::{Heap, RankPairingHeap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap: RankPairingHeap<&str, i32> = RankPairingHeapBuilder::new().max().multi_pass().rank1().build();
+    ///
+    /// heap.push("Hello", 2);
+    /// heap.push("World", 5);
+    ///
+    /// assert_eq!(heap.pop(), Some("World"));
+    /// assert_eq!(heap.pop(), Some("Hello"));
+    /// ```
+    pub fn rank1(mut self) -> Self {
+        self.heap_rank = HeapRank::One;
+        self
+    }
+
+    /// Calculates ranks using [`HeapRank::Two`]
+    ///
+    /// ```rust
+    /// use heapz::{Heap, RankPairingHeap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap: RankPairingHeap<&str, i32> = RankPairingHeapBuilder::new().min().single_pass().rank2().build();
+    ///
+    /// heap.push("Hello", 2);
+    /// heap.push("World", 5);
+    ///
+    /// assert_eq!(heap.pop(), Some("Hello"));
+    /// assert_eq!(heap.pop(), Some("World"));
+    /// ```
+    pub fn rank2(mut self) -> Self {
+        self.heap_rank = HeapRank::Two;
+        self
+    }
+
+    /// Builds an empty [`RankPairingHeap`] with the selected configuration
+    ///
+    /// ```rust
+    /// use heapz::{DecreaseKey, Heap, RankPairingHeapBuilder};
+    ///
+    /// let mut heap = RankPairingHeapBuilder::new().min().multi_pass().rank1().build();
+    ///
+    /// heap.push("Hello", 5);
+    /// heap.push("World", 2);
+    /// heap.update(&"Hello", 1);
+    ///
+    /// assert_eq!(heap.pop(), Some("Hello"));
+    /// assert_eq!(heap.pop(), Some("World"));
+    /// ```
+    pub fn build<K, V>(&self) -> RankPairingHeap<K, V>
+    where
+        K: Hash + Eq + Clone + std::fmt::Debug,
+        V: PartialOrd + Clone + std::fmt::Debug,
+    {
+        RankPairingHeap::new(self.heap_type, self.heap_rank.clone(), self.passes.clone())
+    }
+}
+
 // Ranking
 #[allow(dead_code)]
 impl<K, V> RankPairingHeap<K, V>
@@ -383,6 +579,8 @@ impl<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd + Clone + std::fmt::D
 
     fn merge_trees(&mut self, node_a: Position, node_b: Position) -> Position {
         assert_ne!(node_a, node_b);
+        count_op!(self, melds);
+        count_op!(self, comparisons);
         let a = self.get_node_mut(node_a).unwrap() as *mut Node<K, V>;
         let b = self.get_node_mut(node_b).unwrap() as *mut Node<K, V>;
         let parent: Position;
@@ -609,6 +807,7 @@ impl<K: Hash + Eq + Clone + std::fmt::Debug, V: PartialOrd + Clone + std::fmt::D
 
     fn add_root_to_list(&mut self, root: Position, list: Position) -> Position {
         if list.is_some() && root.is_some() {
+            count_op!(self, comparisons);
             let root_node = self.get_node_mut(root).unwrap() as *mut Node<K, V>;
             let list_node = self.get_node_mut(list).unwrap() as *mut Node<K, V>;
             unsafe {
@@ -753,6 +952,7 @@ where
         let node = Node::new(key, value);
         let position = self.add_node(node);
         self.root = self.add_root_to_list(position, self.root);
+        count_op!(self, pushes);
     }
 
     /// Returns the highest priority element of a [`RankPairingHeap`] (or None)
@@ -811,6 +1011,7 @@ where
         let root = self.root;
         if root.is_some() {
             let next_root = self.get_next_root(root);
+            count_op!(self, pops);
             self.remove(root).map(|removed| {
                 let head = self.concatenate_lists(next_root, removed.left);
                 self.root = self.combine_ranks(head);
@@ -820,6 +1021,25 @@ where
             None
         }
     }
+
+    /// Removes all elements from the [`RankPairingHeap`], keeping the allocated storage
+    ///
+    /// ```rust
+    /// use heapz::{RankPairingHeap, Heap};
+    ///
+    /// let mut heap = RankPairingHeap::multi_pass_min2();
+    ///
+    /// heap.push("Hello".to_string(), 5);
+    /// heap.clear();
+    ///
+    /// assert_eq!(heap.size(), 0);
+    /// assert_eq!(heap.top(), None);
+    /// ```
+    fn clear(&mut self) {
+        self.root = None;
+        self.list.clear();
+        self.keys.clear();
+    }
 }
 
 impl<K, V> DecreaseKey<K, V> for RankPairingHeap<K, V>
@@ -861,8 +1081,11 @@ where
                 (node.root, can_update, node.left, node.parent, node.next)
             })
             .map(|(is_root, can_update, left, parent, next)| {
+                count_op!(self, comparisons);
                 if can_update {
+                    count_op!(self, decrease_keys);
                     if is_root {
+                        count_op!(self, comparisons);
                         if self.compare(position, self.root) {
                             self.root = position;
                         }
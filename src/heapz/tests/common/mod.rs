@@ -214,6 +214,24 @@ pub mod size {
     }
 }
 
+pub mod clear {
+    use super::{generate_numbers, Heap};
+
+    pub fn removes_all_elements_from_the_heap<T: Heap<i32, i32>>(mut heap: T) {
+        generate_numbers().into_iter().for_each(|n| {
+            let _ = &mut heap.push(n, n);
+        });
+        heap.clear();
+        assert_eq!(heap.size(), 0);
+        assert!(heap.is_empty());
+        assert_eq!(heap.top(), None);
+        assert_eq!(heap.pop(), None);
+        heap.push(5, 5);
+        assert_eq!(heap.size(), 1);
+        assert_eq!(heap.top(), Some(&5));
+    }
+}
+
 pub mod update {
     use super::{generate_numbers, DecreaseKey};
     use std::cmp::min;
@@ -121,3 +121,13 @@ mod size {
         );
     }
 }
+
+mod clear {
+    use super::common;
+    use heapz::PairingHeap;
+
+    #[test]
+    fn removes_all_elements_from_the_heap() {
+        common::clear::removes_all_elements_from_the_heap(PairingHeap::min());
+    }
+}
@@ -125,6 +125,16 @@ mod multi_pass_min {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::multi_pass_min());
+        }
+    }
 }
 
 mod multi_pass_min2 {
@@ -250,6 +260,16 @@ mod multi_pass_min2 {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::multi_pass_min2());
+        }
+    }
 }
 
 mod single_pass_min {
@@ -375,6 +395,16 @@ mod single_pass_min {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::single_pass_min());
+        }
+    }
 }
 
 mod single_pass_min2 {
@@ -499,6 +529,16 @@ mod single_pass_min2 {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::single_pass_min2());
+        }
+    }
 }
 
 mod multi_pass_max {
@@ -624,6 +664,16 @@ mod multi_pass_max {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::multi_pass_max());
+        }
+    }
 }
 
 mod multi_pass_max2 {
@@ -749,6 +799,16 @@ mod multi_pass_max2 {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::multi_pass_max2());
+        }
+    }
 }
 
 mod single_pass_max {
@@ -874,6 +934,16 @@ mod single_pass_max {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::single_pass_max());
+        }
+    }
 }
 
 mod single_pass_max2 {
@@ -999,4 +1069,14 @@ mod single_pass_max2 {
             );
         }
     }
+
+    mod clear {
+        use super::super::common;
+        use heapz::RankPairingHeap;
+
+        #[test]
+        fn removes_all_elements_from_the_heap() {
+            common::clear::removes_all_elements_from_the_heap(RankPairingHeap::single_pass_max2());
+        }
+    }
 }
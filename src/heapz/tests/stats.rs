@@ -0,0 +1,61 @@
+#![cfg(feature = "stats")]
+
+extern crate heapz;
+
+use heapz::{DecreaseKey, Heap, OpCounts, PairingHeap, RankPairingHeap};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const SIZE: usize = 1000;
+const UPDATES: usize = 200;
+
+fn generate_values() -> Vec<i32> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..SIZE).map(|_| rng.gen_range(0..1_000_000)).collect()
+}
+
+/// a comparison based heap needs at least `SIZE - 1` comparisons and should not need more than a few `SIZE * log2(SIZE)`
+fn assert_comparisons_within_bounds(counts: OpCounts) {
+    let log_size = (SIZE as f64).log2().ceil() as usize;
+    assert!(counts.comparisons >= SIZE - 1, "{counts:?}");
+    assert!(counts.comparisons <= 4 * SIZE * log_size, "{counts:?}");
+}
+
+#[test]
+fn pairing_heap_counts_operations() {
+    let mut heap = PairingHeap::min();
+    generate_values()
+        .into_iter()
+        .enumerate()
+        .for_each(|(key, value)| heap.push(key, value));
+    while heap.pop().is_some() {}
+    let counts = heap.op_counts();
+    assert_eq!(counts.pushes, SIZE);
+    assert_eq!(counts.pops, SIZE);
+    assert_eq!(counts.decrease_keys, 0);
+    assert!(counts.melds <= counts.comparisons);
+    assert_comparisons_within_bounds(counts);
+}
+
+#[test]
+fn rank_pairing_heap_counts_operations() {
+    let mut heap = RankPairingHeap::multi_pass_min2();
+    let values = generate_values();
+    values.iter().enumerate().for_each(|(key, &value)| heap.push(key, value));
+    // pop a few elements first so that the updated ones are no longer all roots
+    (0..10).for_each(|_| {
+        heap.pop();
+    });
+    let mut updated = 0;
+    for key in (0..SIZE).step_by(SIZE / UPDATES) {
+        heap.update(&key, -(key as i32));
+        updated += 1;
+    }
+    while heap.pop().is_some() {}
+    let counts = heap.op_counts();
+    assert_eq!(counts.pushes, SIZE);
+    assert_eq!(counts.pops, SIZE);
+    assert!(counts.decrease_keys <= updated);
+    assert!(counts.decrease_keys >= updated - 10);
+    assert_comparisons_within_bounds(counts);
+}
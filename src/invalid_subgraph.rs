@@ -3,6 +3,7 @@ use crate::derivative::Derivative;
 use crate::matrix::*;
 use crate::plugin::EchelonMatrix;
 use crate::util::*;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeSet;
@@ -10,7 +11,7 @@ use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 /// an invalid subgraph $S = (V_S, E_S)$, also store the hair $\delta(S)$
-#[derive(Clone, PartialEq, Eq, Derivative)]
+#[derive(Clone, PartialEq, Eq, Derivative, Serialize, Deserialize)]
 #[derivative(Debug)]
 pub struct InvalidSubgraph {
     /// the hash value calculated by other fields
@@ -24,11 +24,17 @@ extern crate urlencoding;
 #[cfg(feature = "wasm_binding")]
 extern crate wasm_bindgen;
 
+// the incremental LP modifies the internal data of the highs problem, which the slp solver does not expose;
+// building with both features fails with this error instead of panicking at runtime
+#[cfg(all(feature = "slp", feature = "incr_lp"))]
+compile_error!("the `slp` feature does not support `incr_lp`, please enable only one of them");
+
 pub mod cli;
 pub mod cluster;
 pub mod decoding_hypergraph;
 pub mod dual_module;
 pub mod dual_module_pq;
+pub mod dual_module_serial;
 pub mod example_codes;
 pub mod html_export;
 pub mod invalid_subgraph;
@@ -37,6 +43,7 @@ pub mod model_hypergraph;
 pub mod mwpf_solver;
 pub mod ordered_float;
 pub mod plugin;
+pub mod plugin_greedy_match;
 pub mod plugin_single_hair;
 pub mod plugin_union_find;
 pub mod pointers;
@@ -4,8 +4,5 @@ use crate::clap::Parser;
 use mwpf::cli::*;
 
 pub fn main() {
-    #[cfg(all(feature = "slp", feature = "incr_lp"))]
-    panic!("slp does not support incr_lp!");
-
     Cli::parse().run();
 }
@@ -15,6 +15,27 @@ pub struct BasicMatrix {
     /// variable index map to edge index
     pub variables: Vec<EdgeIndex>,
     pub constraints: Vec<ParityRow>,
+    /// the vertex index of each row, i.e. the row labels; they permute together with the rows
+    pub row_labels: Vec<VertexIndex>,
+}
+
+impl BasicMatrix {
+    /// the vertex-by-edge incidence matrix of the whole decoding hypergraph over GF(2), i.e. its parity-check matrix;
+    /// row `i` corresponds to vertex `i` and variable `j` to edge `j`, with all right-hand sides being zero
+    pub fn from_initializer(initializer: &SolverInitializer) -> Self {
+        let mut matrix = Self::new();
+        let mut incident_edges = vec![vec![]; initializer.vertex_num];
+        for (edge_index, hyperedge) in initializer.weighted_edges.iter().enumerate() {
+            matrix.add_variable(edge_index);
+            for &vertex_index in hyperedge.vertices.iter() {
+                incident_edges[vertex_index].push(edge_index);
+            }
+        }
+        for (vertex_index, incident_edges) in incident_edges.iter().enumerate() {
+            matrix.add_constraint(vertex_index, incident_edges, false);
+        }
+        matrix
+    }
 }
 
 impl MatrixBasic for BasicMatrix {
@@ -55,6 +76,7 @@ impl MatrixBasic for BasicMatrix {
         }
         row.set_right(parity);
         self.constraints.push(row);
+        self.row_labels.push(vertex_index);
         var_indices
     }
 
@@ -65,6 +87,7 @@ impl MatrixBasic for BasicMatrix {
 
     fn swap_row(&mut self, a: RowIndex, b: RowIndex) {
         self.constraints.swap(a, b);
+        self.row_labels.swap(a, b);
     }
 
     fn get_lhs(&self, row: RowIndex, var_index: VarIndex) -> bool {
@@ -83,6 +106,10 @@ impl MatrixBasic for BasicMatrix {
         self.edges.get(&edge_index).cloned()
     }
 
+    fn row_to_vertex_index(&self, row: RowIndex) -> VertexIndex {
+        self.row_labels[row]
+    }
+
     fn get_vertices(&self) -> BTreeSet<VertexIndex> {
         self.vertices.clone()
     }
@@ -170,6 +197,39 @@ pub mod tests {
         assert_eq!(matrix.get_view_edges(), [1, 4, 12, 345]);
     }
 
+    #[test]
+    fn basic_matrix_from_initializer() {
+        // cargo test basic_matrix_from_initializer -- --nocapture
+        use crate::example_codes::*;
+        // 4 stabilizers in a chain, with the two boundary edges 3 and 4 incident to a single vertex
+        let initializer = CodeCapacityRepetitionCode::new(5, 0.1).get_initializer();
+        let mut matrix = BasicMatrix::from_initializer(&initializer);
+        matrix.printstd();
+        assert_eq!(matrix.rows(), initializer.vertex_num);
+        assert_eq!(matrix.columns(), initializer.weighted_edges.len());
+        assert_eq!((matrix.rows(), matrix.columns()), (4, 5));
+        assert!(matrix.get_lhs(0, 0) && matrix.get_lhs(1, 0) && !matrix.get_lhs(2, 0));
+        assert!(matrix.get_lhs(0, 3) && !matrix.get_lhs(1, 3));
+        assert!(matrix.get_lhs(3, 4) && !matrix.get_lhs(0, 4));
+        for (edge_index, hyperedge) in initializer.weighted_edges.iter().enumerate() {
+            assert_eq!(matrix.edge_to_var_index(edge_index), Some(edge_index));
+            for row in 0..matrix.rows() {
+                assert_eq!(matrix.get_lhs(row, edge_index), hyperedge.vertices.contains(&row));
+            }
+        }
+        // every vertex of the repetition code is incident to two edges
+        for row in 0..matrix.rows() {
+            assert_eq!(matrix.row_to_vertex_index(row), row);
+            assert_eq!(
+                (0..matrix.columns())
+                    .filter(|&var_index| matrix.get_lhs(row, var_index))
+                    .count(),
+                2
+            );
+            assert!(!matrix.get_rhs(row));
+        }
+    }
+
     #[test]
     fn basic_matrix_should_not_add_repeated_constraint() {
         // cargo test --features=colorful basic_matrix_should_not_add_repeated_constraint -- --nocapture
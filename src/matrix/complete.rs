@@ -16,6 +16,8 @@ pub struct CompleteMatrix {
     /// variable index map to edge index
     variables: Vec<EdgeIndex>,
     constraints: Vec<ParityRow>,
+    /// the vertex index of each row, i.e. the row labels; they permute together with the rows
+    row_labels: Vec<VertexIndex>,
 }
 
 impl MatrixBasic for CompleteMatrix {
@@ -53,6 +55,7 @@ impl MatrixBasic for CompleteMatrix {
         }
         row.set_right(parity);
         self.constraints.push(row);
+        self.row_labels.push(vertex_index);
         // never add new edges
         None
     }
@@ -64,6 +67,7 @@ impl MatrixBasic for CompleteMatrix {
 
     fn swap_row(&mut self, a: RowIndex, b: RowIndex) {
         self.constraints.swap(a, b);
+        self.row_labels.swap(a, b);
     }
 
     fn get_lhs(&self, row: RowIndex, var_index: VarIndex) -> bool {
@@ -82,6 +86,10 @@ impl MatrixBasic for CompleteMatrix {
         self.edges.get(&edge_index).cloned()
     }
 
+    fn row_to_vertex_index(&self, row: RowIndex) -> VertexIndex {
+        self.row_labels[row]
+    }
+
     fn get_vertices(&self) -> BTreeSet<VertexIndex> {
         self.vertices.clone()
     }
@@ -89,6 +89,9 @@ impl<M: MatrixView> MatrixBasic for Echelon<M> {
     fn edge_to_var_index(&self, edge_index: EdgeIndex) -> Option<VarIndex> {
         self.get_base().edge_to_var_index(edge_index)
     }
+    fn row_to_vertex_index(&self, row: RowIndex) -> VertexIndex {
+        self.get_base().row_to_vertex_index(row)
+    }
     fn get_vertices(&self) -> BTreeSet<VertexIndex> {
         self.get_base().get_vertices()
     }
@@ -97,112 +100,119 @@ impl<M: MatrixView> MatrixBasic for Echelon<M> {
     }
 }
 
-impl<M: MatrixView> Echelon<M> {
-    fn force_update_echelon_info(&mut self) {
-        let width = self.base.columns();
-        let height = self.base.rows();
-        if width != self.info.columns.len() {
-            self.info.columns.resize_with(width, Default::default);
-        }
-        if height != self.info.rows.len() {
-            self.info.rows.resize_with(height, Default::default);
-        }
-        if width == 0 {
-            // no variable to satisfy any requirement
-            // if any RHS=1, it cannot be satisfied
-            for row in 0..height {
-                if self.base.get_rhs(row) {
-                    self.info.satisfiable = false;
-                    self.base.swap_row(0, row); // make it the first row
-                    self.info.effective_rows = 1;
-                    self.info.rows.truncate(1);
-                    self.info.rows[0].set_no_leading();
-                    return;
+/// reduce the rows of `base` into the reduced row echelon form over GF(2), pivoting on the columns in the order of the
+/// view; the result is written to `info`, reusing its allocation. The rows are permuted and xor-ed in place, while the
+/// columns are never touched, so a view like [`super::Tail`] decides which columns are eliminated first
+pub(crate) fn eliminate<M: MatrixView>(base: &mut M, info: &mut EchelonInfo) {
+    let width = base.columns();
+    let height = base.rows();
+    if width != info.columns.len() {
+        info.columns.resize_with(width, Default::default);
+    }
+    if height != info.rows.len() {
+        info.rows.resize_with(height, Default::default);
+    }
+    if width == 0 {
+        // no variable to satisfy any requirement
+        // if any RHS=1, it cannot be satisfied
+        for row in 0..height {
+            if base.get_rhs(row) {
+                info.satisfiable = false;
+                base.swap_row(0, row); // make it the first row
+                info.effective_rows = 1;
+                info.rows.truncate(1);
+                info.rows[0].set_no_leading();
+                return;
+            }
+        }
+        info.satisfiable = true;
+        info.effective_rows = 0;
+        info.rows.truncate(0);
+        return;
+    }
+    // prepare info
+    info.satisfiable = false;
+    let mut lead = 0;
+    for r in 0..height {
+        if lead >= width {
+            // no more variables
+            info.satisfiable = (r..height).all(|row| !base.get_rhs(row));
+            if info.satisfiable {
+                info.effective_rows = r;
+                info.rows.truncate(r);
+                return;
+            }
+            // find an unsatisfiable row with rhs=1 and make it the row[r]
+            for row in r..height {
+                if base.get_rhs(row) {
+                    base.swap_row(r, row);
+                    break;
                 }
             }
-            self.info.satisfiable = true;
-            self.info.effective_rows = 0;
-            self.info.rows.truncate(0);
+            debug_assert!(base.get_rhs(r));
+            debug_assert!(!info.satisfiable);
+            info.effective_rows = r + 1;
+            info.rows.truncate(r + 1);
+            info.rows[r].set_no_leading();
             return;
         }
-        // prepare info
-        self.info.satisfiable = false;
-        let mut lead = 0;
-        for r in 0..height {
-            if lead >= width {
-                // no more variables
-                self.info.satisfiable = (r..height).all(|row| !self.base.get_rhs(row));
-                if self.info.satisfiable {
-                    self.info.effective_rows = r;
-                    self.info.rows.truncate(r);
-                    return;
-                }
-                // find an unsatisfiable row with rhs=1 and make it the row[r]
-                for row in r..height {
-                    if self.base.get_rhs(row) {
-                        self.base.swap_row(r, row);
-                        break;
+        let mut i = r;
+        // find first non-zero lead and make it the row[r]
+        while !base.get_lhs(i, base.column_to_var_index(lead)) {
+            i += 1;
+            if i == height {
+                i = r;
+                // couldn't find a leading 1 in this column, indicating this variable is an independent variable
+                info.columns[lead].set_not_dependent();
+                lead += 1; // consider the next lead
+                if lead >= width {
+                    info.satisfiable = (r..height).all(|row| !base.get_rhs(row));
+                    if info.satisfiable {
+                        info.effective_rows = r;
+                        info.rows.truncate(r);
+                        return;
                     }
-                }
-                debug_assert!(self.base.get_rhs(r));
-                debug_assert!(!self.info.satisfiable);
-                self.info.effective_rows = r + 1;
-                self.info.rows.truncate(r + 1);
-                self.info.rows[r].set_no_leading();
-                return;
-            }
-            let mut i = r;
-            // find first non-zero lead and make it the row[r]
-            while !self.base.get_lhs(i, self.base.column_to_var_index(lead)) {
-                i += 1;
-                if i == height {
-                    i = r;
-                    // couldn't find a leading 1 in this column, indicating this variable is an independent variable
-                    self.info.columns[lead].set_not_dependent();
-                    lead += 1; // consider the next lead
-                    if lead >= width {
-                        self.info.satisfiable = (r..height).all(|row| !self.base.get_rhs(row));
-                        if self.info.satisfiable {
-                            self.info.effective_rows = r;
-                            self.info.rows.truncate(r);
-                            return;
-                        }
-                        // find a row with rhs=1 and swap with r row
-                        for row in r..height {
-                            if self.base.get_rhs(row) {
-                                self.base.swap_row(r, row);
-                                break;
-                            }
+                    // find a row with rhs=1 and swap with r row
+                    for row in r..height {
+                        if base.get_rhs(row) {
+                            base.swap_row(r, row);
+                            break;
                         }
-                        debug_assert!(self.base.get_rhs(r));
-                        debug_assert!(!self.info.satisfiable);
-                        self.info.effective_rows = r + 1;
-                        self.info.rows.truncate(r + 1);
-                        self.info.rows[r].set_no_leading();
-                        return;
                     }
+                    debug_assert!(base.get_rhs(r));
+                    debug_assert!(!info.satisfiable);
+                    info.effective_rows = r + 1;
+                    info.rows.truncate(r + 1);
+                    info.rows[r].set_no_leading();
+                    return;
                 }
             }
-            if i != r {
-                // implies r < i
-                self.base.swap_row(r, i);
-            }
-            for j in 0..height {
-                if j != r && self.base.get_lhs(j, self.base.column_to_var_index(lead)) {
-                    self.base.xor_row(j, r);
-                }
-            }
-            self.info.rows[r].set(lead);
-            self.info.columns[lead].set(r);
-            self.info.effective_rows = r + 1;
-            lead += 1;
         }
-        while lead < width {
-            self.info.columns[lead].set_not_dependent();
-            lead += 1;
+        if i != r {
+            // implies r < i
+            base.swap_row(r, i);
         }
-        self.info.rows.truncate(self.info.effective_rows);
-        self.info.satisfiable = true;
+        for j in 0..height {
+            if j != r && base.get_lhs(j, base.column_to_var_index(lead)) {
+                base.xor_row(j, r);
+            }
+        }
+        info.rows[r].set(lead);
+        info.columns[lead].set(r);
+        info.effective_rows = r + 1;
+        lead += 1;
+    }
+    while lead < width {
+        info.columns[lead].set_not_dependent();
+        lead += 1;
+    }
+    info.rows.truncate(info.effective_rows);
+    info.satisfiable = true;
+}
+
+impl<M: MatrixView> Echelon<M> {
+    fn force_update_echelon_info(&mut self) {
+        eliminate(&mut self.base, &mut self.info);
     }
 
     fn echelon_info_lazy_update(&mut self) {
@@ -299,6 +309,154 @@ impl<M: MatrixView> VizTrait for Echelon<M> {
     }
 }
 
+/// a dense matrix reduced over the prime field GF(p) with the modulus chosen at runtime, for generalized (qudit) codes;
+/// binary stabilizer codes should keep using [`Echelon`], whose bit-packed GF(2) rows are much faster
+#[derive(Clone, Debug)]
+pub struct PrimeFieldEchelon {
+    modulus: u64,
+    columns: usize,
+    /// the coefficients of each row, all in `0..modulus`
+    lhs: Vec<Vec<u64>>,
+    /// the right-hand side of each row, in `0..modulus`
+    rhs: Vec<u64>,
+    /// echelon form is invalidated on any changes to the matrix
+    is_info_outdated: bool,
+    info: EchelonInfo,
+}
+
+impl PrimeFieldEchelon {
+    pub fn new(modulus: u64, columns: usize) -> Self {
+        assert!(
+            modulus >= 2 && (2..).take_while(|&d| d <= modulus / d).all(|d| modulus % d != 0),
+            "modulus {modulus} is not a prime"
+        );
+        Self {
+            modulus,
+            columns,
+            lhs: vec![],
+            rhs: vec![],
+            is_info_outdated: true,
+            info: EchelonInfo::new(),
+        }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// add a row `lhs · x = rhs`; the values are reduced modulo p
+    pub fn add_row(&mut self, lhs: &[u64], rhs: u64) {
+        assert_eq!(lhs.len(), self.columns, "row must have exactly one coefficient per column");
+        self.is_info_outdated = true;
+        self.lhs.push(lhs.iter().map(|value| value % self.modulus).collect());
+        self.rhs.push(rhs % self.modulus);
+    }
+
+    /// the coefficient at the given row and column, in echelon form once [`Self::get_echelon_info`] is called
+    pub fn get_lhs(&self, row: RowIndex, column: ColumnIndex) -> u64 {
+        self.lhs[row][column]
+    }
+
+    pub fn get_rhs(&self, row: RowIndex) -> u64 {
+        self.rhs[row]
+    }
+
+    pub fn get_echelon_info(&mut self) -> &EchelonInfo {
+        if self.is_info_outdated {
+            self.eliminate();
+            self.is_info_outdated = false;
+        }
+        &self.info
+    }
+
+    /// the number of linearly independent rows of the coefficient matrix
+    pub fn rank(&mut self) -> usize {
+        self.get_echelon_info()
+            .rows
+            .iter()
+            .filter(|row_info| row_info.has_leading())
+            .count()
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 * b as u128) % self.modulus as u128) as u64
+    }
+
+    /// `a - b` for `a` and `b` in `0..modulus`, without overflowing even if the modulus exceeds `u64::MAX / 2`
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a + (self.modulus - b)
+        }
+    }
+
+    /// the multiplicative inverse by Fermat's little theorem, `a^(p-2)`
+    fn inverse(&self, a: u64) -> u64 {
+        debug_assert!(a != 0);
+        let (mut base, mut exponent, mut result) = (a, self.modulus - 2, 1);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// `row[target] -= factor * row[source]`
+    fn subtract_row(&mut self, target: RowIndex, source: RowIndex, factor: u64) {
+        for column in 0..self.columns {
+            let delta = self.mul(factor, self.lhs[source][column]);
+            self.lhs[target][column] = self.sub(self.lhs[target][column], delta);
+        }
+        let delta = self.mul(factor, self.rhs[source]);
+        self.rhs[target] = self.sub(self.rhs[target], delta);
+    }
+
+    /// same as [`eliminate`] but over GF(p): every pivot is scaled to 1 before clearing its column in the other rows
+    fn eliminate(&mut self) {
+        let height = self.lhs.len();
+        self.info.columns.clear();
+        self.info.columns.resize_with(self.columns, ColumnInfo::not_dependent);
+        self.info.rows.clear();
+        let mut r = 0;
+        for lead in 0..self.columns {
+            let Some(i) = (r..height).find(|&i| self.lhs[i][lead] != 0) else {
+                continue; // an independent variable
+            };
+            self.lhs.swap(r, i);
+            self.rhs.swap(r, i);
+            let scale = self.inverse(self.lhs[r][lead]);
+            for column in 0..self.columns {
+                self.lhs[r][column] = self.mul(scale, self.lhs[r][column]);
+            }
+            self.rhs[r] = self.mul(scale, self.rhs[r]);
+            for j in 0..height {
+                let factor = self.lhs[j][lead];
+                if j != r && factor != 0 {
+                    self.subtract_row(j, r, factor);
+                }
+            }
+            let mut row_info = RowInfo::new();
+            row_info.set(lead);
+            self.info.rows.push(row_info);
+            self.info.columns[lead].set(r);
+            r += 1;
+        }
+        // all the remaining rows have zero coefficients
+        self.info.satisfiable = true;
+        if let Some(row) = (r..height).find(|&row| self.rhs[row] != 0) {
+            self.lhs.swap(r, row);
+            self.rhs.swap(r, row);
+            self.info.satisfiable = false;
+            self.info.rows.push(RowInfo::no_leading());
+        }
+        self.info.effective_rows = self.info.rows.len();
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::super::basic::*;
@@ -395,6 +553,56 @@ pub mod tests {
         matrix.printstd();
     }
 
+    #[test]
+    fn echelon_matrix_row_labels() {
+        // cargo test --features=colorful echelon_matrix_row_labels -- --nocapture
+        let mut matrix = Echelon::<Tail<BasicMatrix>>::new();
+        matrix.add_constraint(7, &[4, 9], false);
+        matrix.add_constraint(3, &[1, 4, 6], true);
+        matrix.add_constraint(5, &[1, 9], true);
+        let viz_table = matrix.viz_table().with_row_labels();
+        assert_eq!(viz_table.edges, [4, 9, 1, 6]);
+        assert_eq!(viz_table.vertices, [7, 3, 5]);
+        assert_eq!(viz_table.snapshot()["vertices"], json!([7, 3, 5]));
+        assert_eq!(
+            Table::from(viz_table).to_string().replace("\r", ""),
+            "\
+┌──┬─┬─┬─┬─┬───┬─┐
+┊ E┊4┊9┊1┊6┊ = ┊▼┊
+╞══╪═╪═╪═╪═╪═══╪═╡
+┊ 7┊1┊ ┊1┊ ┊ 1 ┊4┊
+├──┼─┼─┼─┼─┼───┼─┤
+┊ 3┊ ┊1┊1┊ ┊ 1 ┊9┊
+├──┼─┼─┼─┼─┼───┼─┤
+┊ 5┊ ┊ ┊ ┊1┊   ┊6┊
+├──┼─┼─┼─┼─┼───┼─┤
+┊ ▶┊0┊1┊*┊2┊◀  ┊▲┊
+└──┴─┴─┴─┴─┴───┴─┘
+"
+        );
+        // column labels permute with the columns when the tail edges change
+        matrix.set_tail_edges([4, 9].into_iter());
+        let viz_table = matrix.viz_table().with_row_labels();
+        assert_eq!(viz_table.edges, [1, 6, 4, 9]);
+        assert_eq!(viz_table.vertices, [7, 5, 3]);
+        assert_eq!(
+            Table::from(viz_table).to_string().replace("\r", ""),
+            "\
+┌──┬─┬─┬─┬─┬───┬─┐
+┊ E┊1┊6┊4┊9┊ = ┊▼┊
+╞══╪═╪═╪═╪═╪═══╪═╡
+┊ 7┊1┊ ┊ ┊1┊ 1 ┊1┊
+├──┼─┼─┼─┼─┼───┼─┤
+┊ 5┊ ┊1┊ ┊ ┊   ┊6┊
+├──┼─┼─┼─┼─┼───┼─┤
+┊ 3┊ ┊ ┊1┊1┊   ┊4┊
+├──┼─┼─┼─┼─┼───┼─┤
+┊ ▶┊0┊1┊2┊*┊◀  ┊▲┊
+└──┴─┴─┴─┴─┴───┴─┘
+"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn echelon_matrix_should_not_xor() {
@@ -879,4 +1087,83 @@ pub mod tests {
 "
         );
     }
+
+    #[test]
+    fn echelon_matrix_prime_field() {
+        // cargo test echelon_matrix_prime_field -- --nocapture
+        let mut matrix = PrimeFieldEchelon::new(3, 3);
+        matrix.add_row(&[2, 2, 0], 1); // scaled by 2 from [1, 1, 0 | 2]
+        matrix.add_row(&[2, 2, 1], 0);
+        matrix.add_row(&[1, 1, 1], 1);
+        assert_eq!(matrix.rank(), 2);
+        let info = matrix.get_echelon_info().clone();
+        assert!(info.satisfiable);
+        assert_eq!(info.effective_rows, 2);
+        assert_eq!(info.rows, vec![RowInfo { column: 0 }, RowInfo { column: 2 }]);
+        assert_eq!(
+            info.columns,
+            vec![ColumnInfo { row: 0 }, ColumnInfo::not_dependent(), ColumnInfo { row: 1 }]
+        );
+        let expected = [([1, 1, 0], 2), ([0, 0, 1], 2), ([0, 0, 0], 0)];
+        for (row, (lhs, rhs)) in expected.iter().enumerate() {
+            for (column, &value) in lhs.iter().enumerate() {
+                assert_eq!(matrix.get_lhs(row, column), value);
+            }
+            assert_eq!(matrix.get_rhs(row), *rhs);
+        }
+        // an inconsistent row makes it unsatisfiable, reported as a row without leading column
+        matrix.add_row(&[0, 0, 2], 2);
+        assert_eq!(matrix.rank(), 2);
+        let info = matrix.get_echelon_info();
+        assert!(!info.satisfiable);
+        assert_eq!(info.effective_rows, 3);
+        assert!(!info.rows[2].has_leading());
+    }
+
+    #[test]
+    fn echelon_matrix_prime_field_binary() {
+        // cargo test echelon_matrix_prime_field_binary -- --nocapture
+        // over GF(2) it agrees with the bit-packed reduction
+        let mut rng = DeterministicRng::seed_from_u64(123);
+        for _ in 0..20 {
+            let mut matrix = EchelonMatrix::new();
+            let mut dense = PrimeFieldEchelon::new(2, 6);
+            for edge_index in 0..6 {
+                matrix.add_tight_variable(edge_index);
+            }
+            let parity_checks = generate_random_parity_checks(&mut rng, 6, 5);
+            for (vertex_index, (incident_edges, parity)) in parity_checks.iter().enumerate() {
+                matrix.add_constraint(vertex_index, incident_edges, *parity);
+                let row: Vec<u64> = (0..6).map(|edge_index| incident_edges.contains(&edge_index) as u64).collect();
+                dense.add_row(&row, *parity as u64);
+            }
+            let info = matrix.get_echelon_info().clone();
+            let dense_info = dense.get_echelon_info();
+            assert_eq!(info.satisfiable, dense_info.satisfiable);
+            assert_eq!(info.rows, dense_info.rows);
+            assert_eq!(info.columns, dense_info.columns);
+        }
+    }
+
+    #[test]
+    fn echelon_matrix_prime_field_large_modulus() {
+        // cargo test echelon_matrix_prime_field_large_modulus -- --nocapture
+        assert_eq!(PrimeFieldEchelon::new(4_294_967_291, 1).modulus(), 4_294_967_291);
+        // the largest prime below 2^64, set directly since checking it by trial division takes too long
+        let p = 18_446_744_073_709_551_557;
+        let mut matrix = PrimeFieldEchelon {
+            modulus: p,
+            ..PrimeFieldEchelon::new(2, 2)
+        };
+        matrix.add_row(&[1, 1], 1);
+        matrix.add_row(&[p - 1, p - 2], p - 3);
+        assert_eq!(matrix.rank(), 2);
+        let expected = [([1, 0], p - 1), ([0, 1], 2)];
+        for (row, (lhs, rhs)) in expected.iter().enumerate() {
+            for (column, &value) in lhs.iter().enumerate() {
+                assert_eq!(matrix.get_lhs(row, column), value);
+            }
+            assert_eq!(matrix.get_rhs(row), *rhs);
+        }
+    }
 }
@@ -132,6 +132,9 @@ impl<'a, M: MatrixTail + MatrixEchelon> MatrixBasic for HairView<'a, M> {
     fn edge_to_var_index(&self, edge_index: EdgeIndex) -> Option<VarIndex> {
         self.get_base().edge_to_var_index(edge_index)
     }
+    fn row_to_vertex_index(&self, row: RowIndex) -> VertexIndex {
+        self.get_base().row_to_vertex_index(row + self.row_bias)
+    }
     fn get_vertices(&self) -> BTreeSet<VertexIndex> {
         self.get_base().get_vertices()
     }
@@ -53,6 +53,10 @@ pub trait MatrixBasic {
 
     fn edge_to_var_index(&self, edge_index: EdgeIndex) -> Option<VarIndex>;
 
+    /// get the vertex index that the row is created from, i.e. the row label;
+    /// note that the label permutes with the row on `swap_row` but is not changed by `xor_row`
+    fn row_to_vertex_index(&self, row: RowIndex) -> VertexIndex;
+
     fn exists_edge(&self, edge_index: EdgeIndex) -> bool {
         self.edge_to_var_index(edge_index).is_some()
     }
@@ -235,6 +239,52 @@ pub trait MatrixEchelon: MatrixView {
         }
         Some(solution.into_iter().collect())
     }
+
+    /// enumerate the solutions by xor-ing the particular solution with every combination of the nullspace basis,
+    /// one basis vector per independent variable; at most `limit` solutions are returned
+    fn get_solutions(&mut self, limit: usize) -> Vec<Subgraph> {
+        self.get_echelon_info(); // make sure it's in echelon form
+        let info = self.get_echelon_info_immutable();
+        if !info.satisfiable {
+            return vec![]; // no solution
+        }
+        let mut solution = BTreeSet::new();
+        for (row, row_info) in info.rows.iter().enumerate() {
+            debug_assert!(row_info.has_leading());
+            if self.get_rhs(row) {
+                solution.insert(self.column_to_edge_index(row_info.column));
+            }
+        }
+        let mut nullspace_basis: Vec<Vec<EdgeIndex>> = vec![];
+        for (column, column_info) in info.columns.iter().enumerate() {
+            if column_info.is_dependent() {
+                continue;
+            }
+            let var_index = self.column_to_var_index(column);
+            let mut basis = vec![self.var_to_edge_index(var_index)];
+            for row in 0..info.rows.len() {
+                if self.get_lhs(row, var_index) {
+                    basis.push(self.column_to_edge_index(info.rows[row].column));
+                }
+            }
+            nullspace_basis.push(basis);
+        }
+        // iterate in Gray code order so that each step only flips a single basis vector
+        let mut solutions = vec![];
+        for step in 1usize.. {
+            solutions.push(solution.iter().cloned().collect());
+            let flip = step.trailing_zeros() as usize;
+            if solutions.len() >= limit || flip >= nullspace_basis.len() {
+                break;
+            }
+            for edge_index in nullspace_basis[flip].iter() {
+                if !solution.remove(edge_index) {
+                    solution.insert(*edge_index);
+                }
+            }
+        }
+        solutions
+    }
 }
 
 #[derive(Clone, Debug, Derivative)]
@@ -472,6 +522,40 @@ pub mod tests {
         assert_eq!(weights.get_solution_local_minimum(&mut matrix), Some(vec![5, 6, 9]));
     }
 
+    #[test]
+    fn matrix_interface_echelon_all_solutions() {
+        // cargo test matrix_interface_echelon_all_solutions -- --nocapture
+        let mut matrix = Echelon::<Tail<BasicMatrix>>::new();
+        let parity_checks = [
+            (vec![0, 1], false),
+            (vec![1, 2], false),
+            (vec![2, 3, 7, 8], false),
+            (vec![3, 4], false),
+            (vec![4, 5], true),
+            (vec![5, 6, 7], false),
+            (vec![6, 9], false),
+            (vec![0, 8, 9], true),
+        ];
+        for (vertex_index, (incident_edges, parity)) in parity_checks.iter().enumerate() {
+            matrix.add_constraint(vertex_index, incident_edges, *parity);
+        }
+        let mut solutions = matrix.get_solutions(usize::MAX);
+        // 10 variables and 7 independent constraints
+        assert_eq!(solutions.len(), 8);
+        for solution in solutions.iter_mut() {
+            solution.sort();
+            for (incident_edges, parity) in parity_checks.iter() {
+                let count = incident_edges.iter().filter(|edge| solution.contains(edge)).count();
+                assert_eq!(count % 2 == 1, *parity);
+            }
+        }
+        assert_eq!(solutions.iter().collect::<BTreeSet<_>>().len(), solutions.len());
+        for expected in [vec![0, 1, 2, 3, 4], vec![5, 7, 8], vec![3, 4, 8], vec![5, 6, 9]] {
+            assert!(solutions.contains(&expected));
+        }
+        assert_eq!(matrix.get_solutions(3).len(), 3);
+    }
+
     #[test]
     fn matrix_interface_echelon_no_solution() {
         // cargo test matrix_interface_echelon_no_solution -- --nocapture
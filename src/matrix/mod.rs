@@ -10,7 +10,7 @@ pub mod visualize;
 
 pub use basic::BasicMatrix;
 pub use complete::CompleteMatrix;
-pub use echelon::Echelon;
+pub use echelon::{Echelon, PrimeFieldEchelon};
 pub use hair::HairView;
 pub use interface::*;
 pub use tail::Tail;
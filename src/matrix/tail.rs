@@ -1,3 +1,4 @@
+use super::echelon::eliminate;
 use super::interface::*;
 use super::visualize::*;
 use crate::util::*;
@@ -36,6 +37,21 @@ impl<M: MatrixView> Tail<M> {
     }
 }
 
+impl<M: MatrixView> Tail<M> {
+    /// eliminate the rows so that the non-tail columns come first in the reduced row echelon form, i.e. the same row
+    /// operations as [`super::Echelon`] on top of this view, done once and in place on the base matrix.
+    ///
+    /// Contract: no column is removed, the view keeps all the non-tail columns followed by the tail columns. Every
+    /// pivot on a non-tail column lies above every pivot on a tail column, so the rows from the corner (the pivot row of
+    /// the first tail column) downwards only involve the tail columns; these are the rows the primal module inspects to
+    /// decide whether the tail edges are needed. The returned echelon info describes the pivots in view columns
+    pub fn eliminate(&mut self) -> EchelonInfo {
+        let mut info = EchelonInfo::new();
+        eliminate(self, &mut info);
+        info
+    }
+}
+
 impl<M: MatrixView> MatrixTail for Tail<M> {
     fn get_tail_edges(&self) -> &BTreeSet<EdgeIndex> {
         &self.tail_edges
@@ -92,6 +108,9 @@ impl<M: MatrixView> MatrixBasic for Tail<M> {
     fn edge_to_var_index(&self, edge_index: EdgeIndex) -> Option<VarIndex> {
         self.get_base().edge_to_var_index(edge_index)
     }
+    fn row_to_vertex_index(&self, row: RowIndex) -> VertexIndex {
+        self.get_base().row_to_vertex_index(row)
+    }
     fn get_vertices(&self) -> BTreeSet<VertexIndex> {
         self.get_base().get_vertices()
     }
@@ -149,6 +168,7 @@ impl<M: MatrixView> VizTrait for Tail<M> {
 #[cfg(test)]
 pub mod tests {
     use super::super::basic::*;
+    use super::super::echelon::*;
     use super::super::tight::*;
     use super::*;
 
@@ -215,6 +235,46 @@ pub mod tests {
         assert_eq!(matrix.edge_to_var_index(4), Some(1));
     }
 
+    #[test]
+    fn tail_matrix_eliminate() {
+        // cargo test --features=colorful tail_matrix_eliminate -- --nocapture
+        let mut matrix = TailMatrix::new();
+        matrix.add_constraint(0, &[1, 4, 6], true);
+        matrix.add_constraint(1, &[4, 9], false);
+        matrix.add_constraint(2, &[1, 9], true);
+        for edge_index in [1, 4, 6, 9] {
+            matrix.update_edge_tightness(edge_index, true);
+        }
+        matrix.set_tail_edges([1, 6].into_iter());
+        let mut echelon = Echelon::from_base(matrix.clone());
+        let info = matrix.eliminate();
+        matrix.printstd();
+        assert_eq!(
+            matrix.clone().printstd_str(),
+            "\
+┌─┬─┬─┬─┬─┬───┐
+┊ ┊4┊9┊1┊6┊ = ┊
+╞═╪═╪═╪═╪═╪═══╡
+┊0┊1┊ ┊1┊ ┊ 1 ┊
+├─┼─┼─┼─┼─┼───┤
+┊1┊ ┊1┊1┊ ┊ 1 ┊
+├─┼─┼─┼─┼─┼───┤
+┊2┊ ┊ ┊ ┊1┊   ┊
+└─┴─┴─┴─┴─┴───┘
+"
+        );
+        // the non-tail columns 4 and 9 are pivoted first, the tail edge 1 is free and the tail edge 6 pivots last
+        assert!(info.satisfiable);
+        assert_eq!(info.effective_rows, 3);
+        assert_eq!(
+            info.rows.iter().map(|row_info| row_info.column).collect::<Vec<_>>(),
+            [0, 1, 3]
+        );
+        assert!(!info.columns[2].is_dependent());
+        // the same as the lazily computed echelon form
+        assert_eq!(format!("{:?}", echelon.get_echelon_info()), format!("{info:?}"));
+    }
+
     #[test]
     #[should_panic]
     fn tail_matrix_cannot_call_dirty_column() {
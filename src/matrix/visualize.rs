@@ -16,7 +16,10 @@ use prettytable::*;
 pub struct VizTable {
     pub title: Row,
     pub rows: Vec<Row>,
+    /// the column labels: edge index of each column
     pub edges: Vec<EdgeIndex>,
+    /// the row labels: vertex index of each row
+    pub vertices: Vec<VertexIndex>,
 }
 
 impl VizTable {
@@ -27,6 +30,16 @@ impl VizTable {
             .flat_map(|(idx, c)| if idx == 0 { vec![c] } else { vec!['\n', c] })
             .collect()
     }
+
+    /// replace the positional row numbers on the left with the row labels, i.e. the vertex indices
+    pub fn with_row_labels(mut self) -> Self {
+        for (table_row, vertex_index) in self.rows.iter_mut().zip(self.vertices.iter()) {
+            table_row
+                .set_cell(Cell::new(vertex_index.to_string().as_str()).style_spec("brFc"), 0)
+                .unwrap();
+        }
+        self
+    }
 }
 
 lazy_static! {
@@ -65,7 +78,9 @@ impl<M: MatrixView> From<&mut M> for VizTable {
         title.add_cell(Cell::new(" = "));
         // create body rows
         let mut rows: Vec<Row> = vec![];
+        let mut vertices = vec![];
         for row in 0..matrix.rows() {
+            vertices.push(matrix.row_to_vertex_index(row));
             let mut table_row = Row::empty();
             table_row.add_cell(Cell::new(row.to_string().as_str()).style_spec("brFb"));
             for column in 0..matrix.columns() {
@@ -75,7 +90,12 @@ impl<M: MatrixView> From<&mut M> for VizTable {
             table_row.add_cell(Cell::new(if matrix.get_rhs(row) { " 1 " } else { "   " }));
             rows.push(table_row);
         }
-        VizTable { title, rows, edges }
+        VizTable {
+            title,
+            rows,
+            edges,
+            vertices,
+        }
     }
 }
 
@@ -135,6 +155,7 @@ impl VizTable {
             "version": env!("CARGO_PKG_VERSION"),
             "table": serde_json::Value::from(self.clone()),
             "edges": self.edges,
+            "vertices": self.vertices,
         })
     }
 }
@@ -52,6 +52,21 @@ impl ModelHyperGraph {
     pub fn matches_subgraph_syndrome(&self, subgraph: &OutputSubgraph, defect_vertices: &[VertexIndex]) -> bool {
         self.initializer.matches_subgraph_syndrome(subgraph, defect_vertices)
     }
+
+    /// the maximum number of vertices incident to a single hyperedge, 0 if there is no edge
+    pub fn max_edge_degree(&self) -> usize {
+        self.initializer
+            .weighted_edges
+            .iter()
+            .map(|hyperedge| hyperedge.vertices.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// the maximum number of hyperedges incident to a single vertex, 0 if there is no vertex
+    pub fn max_vertex_degree(&self) -> usize {
+        self.vertices.iter().map(|vertex| vertex.edges.len()).max().unwrap_or(0)
+    }
 }
 
 impl MWPSVisualizer for ModelHyperGraph {
@@ -96,4 +111,23 @@ pub mod tests {
         }
         assert_eq!(edge_reference_initializer, edge_reference_hyper_model_graph);
     }
+
+    #[test]
+    fn hyper_model_graph_max_degree() {
+        // cargo test hyper_model_graph_max_degree -- --nocapture
+        let model_graph = CodeCapacityTailoredCode::new(5, 0., 0.01).get_model_graph();
+        assert_eq!(model_graph.max_edge_degree(), 4);
+        let max_vertex_degree = model_graph.max_vertex_degree();
+        assert!(model_graph
+            .vertices
+            .iter()
+            .all(|vertex| vertex.edges.len() <= max_vertex_degree));
+        assert!(model_graph
+            .vertices
+            .iter()
+            .any(|vertex| vertex.edges.len() == max_vertex_degree));
+        // the color code has at most weight-3 hyperedges
+        let model_graph = CodeCapacityColorCode::new(5, 0.1).get_model_graph();
+        assert_eq!(model_graph.max_edge_degree(), 3);
+    }
 }
@@ -283,6 +283,12 @@ macro_rules! inherit_solver_plugin_methods {
             pub fn get_cluster(&self, vertex_index: VertexIndex) -> Cluster {
                 self.0.get_cluster(vertex_index)
             }
+            pub fn solve_partial(&mut self, syndrome_pattern: SyndromePattern, plugins: PluginVec) -> PartialSolution {
+                self.0.solve_partial(syndrome_pattern, plugins)
+            }
+            pub fn refine(&mut self, partial: PartialSolution, more_plugins: PluginVec) {
+                self.0.refine(partial, more_plugins)
+            }
         }
     };
 }
@@ -294,6 +300,12 @@ pub struct SolverSerialPluginsConfig {
     flatten_primal: PrimalModuleSerialConfig,
     /// legacy config
     primal: Option<PrimalModuleSerialConfig>,
+    /// the coefficients of the cluster affinity
+    #[serde(default)]
+    affinity: AffinityConfig,
+    /// verify that every returned subgraph reproduces the input syndrome, panicking on a mismatch
+    #[serde(default)]
+    assert_correction: bool,
 }
 
 #[derive(Clone)]
@@ -304,6 +316,27 @@ pub struct SolverSerialPlugins {
     model_graph: Arc<ModelHyperGraph>,
     pub config: SolverSerialPluginsConfig,
     syndrome_loaded: bool,
+    /// the defect vertices of the input syndrome, before any flipping due to negative weights
+    defect_vertices: Vec<VertexIndex>,
+}
+
+/// a decoding with only some of the plugins, taken out of the solver by [`SolverSerialPlugins::solve_partial`] so that
+/// it can be inspected and later continued by [`SolverSerialPlugins::refine`]
+pub struct PartialSolution {
+    dual_module: DualModulePQ,
+    primal_module: PrimalModuleSerial,
+    interface_ptr: DualModuleInterfacePtr,
+    defect_vertices: Vec<VertexIndex>,
+}
+
+impl PartialSolution {
+    pub fn subgraph_range(&mut self) -> (OutputSubgraph, WeightRange) {
+        self.primal_module.subgraph_range(&self.interface_ptr, &mut self.dual_module)
+    }
+
+    pub fn sum_dual_variables(&self) -> Rational {
+        self.interface_ptr.sum_dual_variables()
+    }
 }
 
 impl MWPSVisualizer for SolverSerialPlugins {
@@ -322,13 +355,16 @@ impl SolverSerialPlugins {
         let config: SolverSerialPluginsConfig = serde_json::from_value(config).unwrap();
         primal_module.plugins = plugins;
         primal_module.config = config.primal.as_ref().unwrap_or(&config.flatten_primal).clone();
+        let mut dual_module = DualModulePQ::new_empty(initializer);
+        dual_module.affinity_config = config.affinity.clone();
         Self {
-            dual_module: DualModulePQ::new_empty(initializer),
+            dual_module,
             primal_module,
             interface_ptr: DualModuleInterfacePtr::new(model_graph.clone()),
             model_graph,
             config,
             syndrome_loaded: false,
+            defect_vertices: vec![],
         }
     }
 
@@ -343,6 +379,7 @@ impl SolverSerialPlugins {
             self.clear(); // automatic clear before loading new syndrome in case user forgets to call `clear`
         }
         self.syndrome_loaded = true;
+        self.defect_vertices = syndrome_pattern.defect_vertices.clone();
 
         if !skip_initial_duals {
             self.interface_ptr
@@ -368,6 +405,46 @@ impl SolverSerialPlugins {
         }
     }
 
+    /// decode with `plugins` instead of the plugins of this solver, e.g. a fast approximation, and take the state out
+    /// of the solver so that it can be continued by [`Self::refine`]; the solver is left cleared
+    pub fn solve_partial(&mut self, syndrome_pattern: SyndromePattern, plugins: PluginVec) -> PartialSolution {
+        let original_plugins = std::mem::replace(&mut self.primal_module.plugins, Arc::new(plugins));
+        self.solve(syndrome_pattern);
+        let initializer = &self.model_graph.initializer;
+        let mut primal_module = PrimalModuleSerial::new_empty(initializer);
+        primal_module.plugins = original_plugins;
+        primal_module.config = self.primal_module.config.clone();
+        let mut dual_module = DualModulePQ::new_empty(initializer);
+        dual_module.affinity_config = self.config.affinity.clone();
+        self.syndrome_loaded = false;
+        PartialSolution {
+            dual_module: std::mem::replace(&mut self.dual_module, dual_module),
+            primal_module: std::mem::replace(&mut self.primal_module, primal_module),
+            interface_ptr: std::mem::replace(&mut self.interface_ptr, DualModuleInterfacePtr::new(self.model_graph.clone())),
+            defect_vertices: std::mem::take(&mut self.defect_vertices),
+        }
+    }
+
+    /// continue a [`PartialSolution`] with `more_plugins` appended to the plugins it was solved with, instead of
+    /// decoding from scratch; the result is then loaded in this solver as if it was solved by [`SolverTrait::solve`]
+    pub fn refine(&mut self, partial: PartialSolution, more_plugins: PluginVec) {
+        let original_plugins = self.primal_module.plugins.clone();
+        self.dual_module = partial.dual_module;
+        self.primal_module = partial.primal_module;
+        self.interface_ptr = partial.interface_ptr;
+        self.defect_vertices = partial.defect_vertices;
+        self.syndrome_loaded = true;
+        self.primal_module.extend_plugins(more_plugins);
+        self.primal_module.solve_tune(&self.interface_ptr, &mut self.dual_module);
+        // the clusters keep the extended plugins, while the next decoding uses the plugins of this solver again
+        self.primal_module.plugins = original_plugins;
+    }
+
+    /// whether the loaded syndrome has a valid subgraph; it does not if a defect lies on an isolated vertex
+    pub fn is_feasible(&self) -> bool {
+        self.primal_module.is_feasible()
+    }
+
     /// get the cluster information of a vertex
     pub fn get_cluster(&self, vertex_index: VertexIndex) -> Cluster {
         let mut cluster = Cluster::new();
@@ -409,6 +486,20 @@ impl SolverSerialPlugins {
         }
         cluster
     }
+
+    /// check that the correction reproduces the loaded syndrome, the same check as [`ExampleCode::validate_correction`]
+    /// but against the model graph, so it also works for solvers constructed without an example code
+    pub fn validate_correction(&self, correction: &OutputSubgraph) -> Result<(), String> {
+        if self.model_graph.matches_subgraph_syndrome(correction, &self.defect_vertices) {
+            Ok(())
+        } else {
+            Err(format!(
+                "invalid correction: parity check does not match input, defect vertices: {:?}, correction: {:?}",
+                self.defect_vertices,
+                correction.iter().collect::<Vec<_>>()
+            ))
+        }
+    }
 }
 
 impl SolverTrait for SolverSerialPlugins {
@@ -417,26 +508,30 @@ impl SolverTrait for SolverSerialPlugins {
         self.dual_module.clear();
         self.interface_ptr.clear();
         self.syndrome_loaded = false;
+        self.defect_vertices.clear();
     }
     fn solve_visualizer(&mut self, syndrome_pattern: SyndromePattern, visualizer: Option<&mut Visualizer>) {
         if self.syndrome_loaded {
             self.clear(); // automatic clear before loading new syndrome in case user forgets to call `clear`
         }
         self.syndrome_loaded = true;
+        self.defect_vertices = syndrome_pattern.defect_vertices.clone();
 
-        let syndrome_pattern = self.primal_module.weight_preprocessing(
-            Arc::new(syndrome_pattern),
+        let syndrome_pattern = Arc::new(syndrome_pattern);
+        // the vertices incident to negative edges are flipped; the output subgraph is flipped back accordingly
+        let flipped_syndrome_pattern = self.primal_module.weight_preprocessing(
+            syndrome_pattern.clone(),
             &mut self.dual_module,
             &self.model_graph.initializer,
         );
         self.primal_module.solve_visualizer(
             &self.interface_ptr,
-            syndrome_pattern.clone(),
+            flipped_syndrome_pattern,
             &mut self.dual_module,
             visualizer,
         );
         debug_assert!(
-            {
+            !self.is_feasible() || {
                 let subgraph = self.subgraph();
                 self.model_graph
                     .matches_subgraph_syndrome(&subgraph, &syndrome_pattern.defect_vertices)
@@ -446,6 +541,11 @@ impl SolverTrait for SolverSerialPlugins {
     }
     fn subgraph_range_visualizer(&mut self, visualizer: Option<&mut Visualizer>) -> (OutputSubgraph, WeightRange) {
         let (subgraph, weight_range) = self.primal_module.subgraph_range(&self.interface_ptr, &mut self.dual_module);
+        if self.config.assert_correction && self.is_feasible() {
+            if let Err(message) = self.validate_correction(&subgraph) {
+                panic!("{message}");
+            }
+        }
         if let Some(visualizer) = visualizer {
             visualizer
                 .snapshot_combined(
@@ -1179,6 +1279,85 @@ pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     Ok(())
 }
 
-fn p_of_weight(w: f64) -> f64 {
-    1.0 / (w.exp() + 1.0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Zero;
+
+    #[test]
+    fn mwpf_solver_assert_correction() {
+        // cargo test mwpf_solver_assert_correction -- --nocapture
+        let code = CodeCapacityColorCode::new(5, 0.1);
+        let initializer = Arc::new(code.get_initializer());
+        let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({ "assert_correction": true }));
+        solver.solve(SyndromePattern::new_vertices(vec![1, 4, 8]));
+        let subgraph = solver.subgraph();
+        assert!(solver.0.validate_correction(&subgraph).is_ok());
+        // flipping one more edge of the correction must be caught
+        let mut corrupted: Vec<EdgeIndex> = subgraph.iter().cloned().collect();
+        let extra_edge = (0..initializer.weighted_edges.len())
+            .find(|edge_index| !corrupted.contains(edge_index))
+            .unwrap();
+        corrupted.push(extra_edge);
+        assert!(solver.0.validate_correction(&OutputSubgraph::from(corrupted)).is_err());
+    }
+
+    #[test]
+    fn mwpf_solver_refine_partial_solution() {
+        // cargo test mwpf_solver_refine_partial_solution -- --nocapture
+        let code = CodeCapacityColorCode::new(9, 0.1);
+        let initializer = Arc::new(code.get_initializer());
+        let syndrome = SyndromePattern::new_vertices(vec![1, 4, 9, 13, 16, 22, 25, 28]);
+        let single_hair_plugins = || {
+            vec![
+                PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+                PluginSingleHair::entry_with_strategy(RepeatStrategy::Multiple {
+                    max_repetition: usize::MAX,
+                }),
+            ]
+        };
+        let mut full_solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+        full_solver.solve(syndrome.clone());
+        let (_, full_range) = full_solver.subgraph_range();
+        // a fast union-find decoding first, then continued with the single-hair plugins
+        let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+        let mut partial = solver.solve_partial(syndrome.clone(), vec![PluginUnionFind::entry()]);
+        let (partial_subgraph, partial_range) = partial.subgraph_range();
+        assert!(solver
+            .0
+            .model_graph
+            .matches_subgraph_syndrome(&partial_subgraph, &syndrome.defect_vertices));
+        assert!(partial_range.upper >= full_range.upper);
+        println!("partial {partial_range:?}, full {full_range:?}");
+        solver.refine(partial, single_hair_plugins());
+        let (subgraph, range) = solver.subgraph_range();
+        solver.0.validate_correction(&subgraph).unwrap();
+        assert_eq!(range.lower, full_range.lower);
+        assert_eq!(range.upper, full_range.upper);
+        // the solver keeps its own plugins for the next decoding
+        solver.clear();
+        solver.solve(syndrome);
+        assert_eq!(solver.subgraph_range().1.upper, full_range.upper);
+    }
+
+    #[test]
+    fn mwpf_solver_weight_range_exact() {
+        // cargo test mwpf_solver_weight_range_exact -- --nocapture
+        let code = CodeCapacityColorCode::new(5, 0.1);
+        let initializer = Arc::new(code.get_initializer());
+        let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+        solver.solve(SyndromePattern::new_vertices(vec![1, 4, 8]));
+        let (subgraph, weight_range) = solver.subgraph_range();
+        // the bounds are the exact dual objective and primal weight, not rounded through f64
+        assert_eq!(weight_range.lower, solver.sum_dual_variables());
+        assert_eq!(weight_range.upper, initializer.get_subgraph_total_weight(&subgraph));
+        assert_eq!(weight_range.gap(), &weight_range.upper - &weight_range.lower);
+        assert_eq!(weight_range.is_optimal(), weight_range.gap().is_zero());
+        // and so is the snapshot, which also carries numerators and denominators
+        let snapshot = weight_range.snapshot(false);
+        assert_eq!(snapshot["weight_range"]["ln"], json!(numer_of(&weight_range.lower)));
+        assert_eq!(snapshot["weight_range"]["ld"], json!(denom_of(&weight_range.lower)));
+        assert_eq!(snapshot["weight_range"]["un"], json!(numer_of(&weight_range.upper)));
+        assert_eq!(snapshot["weight_range"]["ud"], json!(denom_of(&weight_range.upper)));
+    }
 }
@@ -12,7 +12,8 @@ use crate::matrix::*;
 use crate::plugin_union_find::*;
 use crate::relaxer::*;
 use crate::relaxer_forest::*;
-use num_traits::Signed;
+use crate::util::*;
+use num_traits::{Signed, Zero};
 use parking_lot::RwLock;
 use std::sync::Arc;
 
@@ -28,6 +29,12 @@ pub trait PluginImpl: std::fmt::Debug {
         positive_dual_nodes: &[DualNodePtr],
     ) -> RelaxerVec;
 
+    /// optionally suggest a valid subgraph of the cluster using only the tight edges; if its weight meets
+    /// the sum of dual variables in the cluster, the primal module regards the cluster as optimal
+    fn suggest_subgraph(&self, _decoding_graph: &DecodingHyperGraph, _matrix: &mut EchelonMatrix) -> Option<Subgraph> {
+        None
+    }
+
     /// create a plugin entry with default settings
     fn entry() -> PluginEntry
     where
@@ -65,6 +72,7 @@ pub enum RepeatStrategy {
 }
 
 /// describes what plugins to enable and also the recursive strategy
+#[derive(Clone)]
 pub struct PluginEntry {
     /// the implementation of a plugin
     pub plugin: Arc<dyn PluginImpl + Send + Sync>,
@@ -124,11 +132,19 @@ pub struct PluginManager {
     /// the plugin manager will stop at this index; this is helpful when we want
     /// to execute the first plugin for all clusters, and then the second plugin for all, and so on.
     pub plugin_count: Arc<RwLock<usize>>,
+    /// the number of relaxer searches on this cluster, only tracked for the tests
+    #[cfg(test)]
+    pub(crate) find_relaxer_count: usize,
 }
 
 impl PluginManager {
     pub fn new(plugins: Arc<PluginVec>, plugin_count: Arc<RwLock<usize>>) -> Self {
-        Self { plugins, plugin_count }
+        Self {
+            plugins,
+            plugin_count,
+            #[cfg(test)]
+            find_relaxer_count: 0,
+        }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -141,6 +157,10 @@ impl PluginManager {
         matrix: &mut EchelonMatrix,
         positive_dual_nodes: &[DualNodePtr],
     ) -> Option<Relaxer> {
+        #[cfg(test)]
+        {
+            self.find_relaxer_count += 1;
+        }
         let mut relaxer_forest = RelaxerForest::new(
             matrix.get_view_edges().into_iter(),
             positive_dual_nodes
@@ -155,4 +175,22 @@ impl PluginManager {
         // add a union find relaxer finder as the last resort if nothing is reported
         PluginUnionFind::entry().execute(decoding_graph, matrix, positive_dual_nodes, &mut relaxer_forest)
     }
+
+    /// the lightest subgraph among those suggested by the enabled plugins
+    pub fn suggest_subgraph<F>(
+        &self,
+        decoding_graph: &DecodingHyperGraph,
+        matrix: &mut EchelonMatrix,
+        weight_of: F,
+    ) -> Option<Subgraph>
+    where
+        F: Fn(EdgeIndex) -> Weight,
+    {
+        let subgraph_weight = |subgraph: &Subgraph| subgraph.iter().fold(Weight::zero(), |sum, &e| sum + weight_of(e));
+        self.plugins
+            .iter()
+            .take(*self.plugin_count.read_recursive())
+            .filter_map(|plugin_entry| plugin_entry.plugin.suggest_subgraph(decoding_graph, matrix))
+            .min_by_key(subgraph_weight)
+    }
 }
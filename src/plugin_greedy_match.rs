@@ -0,0 +1,177 @@
+//! greedy matching pairs nearby defects via shortest tight paths, as a fast warm-start of the exact solver
+//!
+//! It behaves like the union-find plugin when looking for relaxers, but additionally suggests the greedy
+//! subgraph to the primal module. Whenever the weight of this subgraph meets the sum of dual variables in
+//! the cluster, the cluster is proven optimal and later plugins no longer need to search relaxers on it.
+//!
+
+use crate::decoding_hypergraph::*;
+use crate::dual_module::*;
+use crate::matrix::*;
+use crate::plugin::*;
+use crate::plugin_union_find::*;
+use crate::relaxer::*;
+use crate::util::*;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+#[derive(Debug, Clone, Default)]
+pub struct PluginGreedyMatch {}
+
+impl PluginGreedyMatch {
+    /// greedily pair each defect with its nearest unmatched defect (or a boundary edge) using only tight edges,
+    /// returning `None` if some defect cannot be paired or the resulting subgraph violates any parity constraint
+    pub fn greedy_subgraph(decoding_graph: &DecodingHyperGraph, matrix: &mut EchelonMatrix) -> Option<Subgraph> {
+        let tight_edges: BTreeSet<EdgeIndex> = matrix.get_view_edges().into_iter().collect();
+        let vertices = matrix.get_vertices();
+        let mut unmatched: BTreeSet<VertexIndex> = vertices
+            .iter()
+            .cloned()
+            .filter(|&vertex_index| decoding_graph.is_vertex_defect(vertex_index))
+            .collect();
+        let mut subgraph_edges = BTreeSet::<EdgeIndex>::new();
+        let mut toggle = |edge_index: EdgeIndex| {
+            if !subgraph_edges.remove(&edge_index) {
+                subgraph_edges.insert(edge_index);
+            }
+        };
+        while let Some(source) = unmatched.pop_first() {
+            // breadth-first search for the nearest unmatched defect or boundary edge
+            let mut predecessors = BTreeMap::<VertexIndex, (VertexIndex, EdgeIndex)>::new();
+            let mut queue = VecDeque::from([source]);
+            let mut visited = BTreeSet::from([source]);
+            let mut found = None;
+            'search: while let Some(vertex_index) = queue.pop_front() {
+                if vertex_index != source && unmatched.contains(&vertex_index) {
+                    found = Some((vertex_index, None));
+                    break;
+                }
+                for &edge_index in decoding_graph.get_vertex_neighbors(vertex_index).iter() {
+                    if !tight_edges.contains(&edge_index) {
+                        continue;
+                    }
+                    let incident_vertices = decoding_graph.get_edge_neighbors(edge_index);
+                    if incident_vertices.len() == 1 {
+                        found = Some((vertex_index, Some(edge_index)));
+                        break 'search;
+                    }
+                    for &peer in incident_vertices.iter() {
+                        if visited.insert(peer) {
+                            predecessors.insert(peer, (vertex_index, edge_index));
+                            queue.push_back(peer);
+                        }
+                    }
+                }
+            }
+            let (mut vertex_index, boundary_edge) = found?;
+            if let Some(edge_index) = boundary_edge {
+                toggle(edge_index);
+            } else {
+                unmatched.remove(&vertex_index);
+            }
+            while let Some(&(previous, edge_index)) = predecessors.get(&vertex_index) {
+                toggle(edge_index);
+                vertex_index = previous;
+            }
+        }
+        // hyperedges along the paths may flip other vertices, in which case the greedy guess is not valid
+        for &vertex_index in vertices.iter() {
+            let degree = decoding_graph
+                .get_vertex_neighbors(vertex_index)
+                .iter()
+                .filter(|edge_index| subgraph_edges.contains(edge_index))
+                .count();
+            if (degree % 2 == 1) != decoding_graph.is_vertex_defect(vertex_index) {
+                return None;
+            }
+        }
+        Some(subgraph_edges.into_iter().collect())
+    }
+}
+
+impl PluginImpl for PluginGreedyMatch {
+    fn find_relaxers(
+        &self,
+        decoding_graph: &DecodingHyperGraph,
+        matrix: &mut EchelonMatrix,
+        _positive_dual_nodes: &[DualNodePtr],
+    ) -> Vec<Relaxer> {
+        if let Some(relaxer) = PluginUnionFind::find_single_relaxer(decoding_graph, matrix) {
+            vec![relaxer]
+        } else {
+            vec![]
+        }
+    }
+
+    fn suggest_subgraph(&self, decoding_graph: &DecodingHyperGraph, matrix: &mut EchelonMatrix) -> Option<Subgraph> {
+        Self::greedy_subgraph(decoding_graph, matrix)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::dual_module_pq::*;
+    use crate::example_codes::*;
+    use crate::plugin_single_hair::*;
+    use crate::primal_module_serial::tests::*;
+    use crate::primal_module_serial::*;
+
+    fn relaxer_search_rounds(primal_module: &PrimalModuleSerial) -> usize {
+        primal_module
+            .clusters
+            .iter()
+            .map(|cluster_ptr| cluster_ptr.read_recursive().plugin_manager.find_relaxer_count)
+            .sum()
+    }
+
+    #[test]
+    fn plugin_greedy_match_clustered_syndrome() {
+        // cargo test plugin_greedy_match_clustered_syndrome -- --nocapture
+        // union-find alone is already optimal on repetition codes and then skips all the later plugins, so use a
+        // planar code syndrome where only some of the clusters are proven optimal by the greedy matching
+        let code = CodeCapacityPlanarCode::new(7, 0.1);
+        let defect_vertices = vec![0, 1, 4, 7, 8, 10, 11, 13];
+        let solve = |plugins: PluginVec| {
+            use crate::primal_module::PrimalModuleImpl;
+            let model_graph = code.get_model_graph();
+            let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+            primal_module.plugins = std::sync::Arc::new(plugins);
+            let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+            let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), defect_vertices.clone());
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(&interface_ptr, decoding_graph.syndrome_pattern.clone(), &mut dual_module);
+            let subgraph = primal_module.subgraph(&interface_ptr, &mut dual_module);
+            assert!(model_graph.matches_subgraph_syndrome(&subgraph, &defect_vertices));
+            relaxer_search_rounds(&primal_module)
+        };
+        let baseline_rounds = solve(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let greedy_rounds = solve(vec![
+            PluginGreedyMatch::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        println!("relaxer search rounds: baseline {baseline_rounds}, greedy {greedy_rounds}");
+        assert!(greedy_rounds < baseline_rounds);
+    }
+
+    #[test]
+    fn plugin_greedy_match_tailored_code() {
+        // cargo test plugin_greedy_match_tailored_code -- --nocapture
+        let code = CodeCapacityTailoredCode::new(5, 0., 0.01);
+        let model_graph = code.get_model_graph();
+        primal_module_serial_basic_standard_syndrome_optional_viz(
+            code,
+            vec![10, 11, 12, 15, 16, 17, 18],
+            Rational::from_float(18.38047940053836).unwrap(),
+            vec![
+                PluginGreedyMatch::entry(),
+                PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+            ],
+            DualModulePQ::new_empty(&model_graph.initializer),
+            model_graph,
+            None,
+        );
+    }
+}
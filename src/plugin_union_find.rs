@@ -68,4 +68,53 @@ pub mod tests {
             vec![PluginUnionFind::entry()],
         );
     }
+
+    /// records how many times it is asked for relaxers
+    #[derive(Debug, Default)]
+    struct PluginCounter {
+        count: std::sync::atomic::AtomicUsize,
+    }
+
+    impl PluginImpl for PluginCounter {
+        fn find_relaxers(
+            &self,
+            _decoding_graph: &DecodingHyperGraph,
+            _matrix: &mut EchelonMatrix,
+            _positive_dual_nodes: &[DualNodePtr],
+        ) -> Vec<Relaxer> {
+            self.count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            vec![]
+        }
+    }
+
+    #[test]
+    fn plugin_union_find_early_exit() {
+        // cargo test plugin_union_find_early_exit -- --nocapture
+        use crate::dual_module_pq::DualModulePQ;
+        use crate::primal_module::*;
+        use crate::primal_module_serial::PrimalModuleSerial;
+        use std::sync::Arc;
+        let code = CodeCapacityRepetitionCode::new(11, 0.1);
+        let model_graph = code.get_model_graph();
+        let counter = Arc::new(PluginCounter::default());
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        primal_module.plugins = Arc::new(vec![
+            PluginUnionFind::entry(),
+            PluginEntry {
+                plugin: counter.clone(),
+                repeat_strategy: RepeatStrategy::Once,
+            },
+        ]);
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let defect_vertices = vec![2, 3, 7, 8];
+        let decoding_graph = DecodingHyperGraph::new_defects(model_graph.clone(), defect_vertices.clone());
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(&interface_ptr, decoding_graph.syndrome_pattern.clone(), &mut dual_module);
+        // union-find is optimal on these even clusters, so the second plugin is never invoked
+        assert!(primal_module.all_clusters_optimal(&mut dual_module));
+        assert_eq!(counter.count.load(std::sync::atomic::Ordering::Relaxed), 0);
+        let (subgraph, weight_range) = primal_module.subgraph_range(&interface_ptr, &mut dual_module);
+        assert!(model_graph.matches_subgraph_syndrome(&subgraph, &defect_vertices));
+        assert_eq!(weight_range.lower, weight_range.upper);
+    }
 }
@@ -13,7 +13,6 @@ use crate::primal_module_serial::ClusterAffinity;
 use crate::relaxer_optimizer::OptimizerResult;
 use crate::util::*;
 use crate::visualize::*;
-use hashbrown::HashSet;
 
 pub type Affinity = OrderedFloat;
 
@@ -149,7 +148,7 @@ pub trait PrimalModuleImpl {
             .defect_vertices
             .iter()
             .cloned()
-            .collect::<HashSet<VertexIndex>>();
+            .collect::<BTreeSet<VertexIndex>>();
         for to_flip in flip_vertices.iter() {
             if moved_out_set.contains(to_flip) {
                 moved_out_set.remove(to_flip);
@@ -203,12 +202,24 @@ pub trait PrimalModuleImpl {
                 Some(length) => dual_module.grow(length),
                 None => {
                     self.resolve(dual_report, interface, dual_module);
+                    #[cfg(feature = "validate_state")]
+                    dual_module.validate_state().unwrap();
                 }
             }
             dual_report = dual_module.report();
         }
 
         // from here, all states should be syncronized
+        self.solve_tune(interface, dual_module);
+    }
+
+    /// the tuning phase of [`PrimalModuleImpl::solve_step_callback_interface_loaded`], which applies the remaining
+    /// plugins once the search phase finds every cluster valid; calling it again after adding plugins continues from
+    /// the current dual solution
+    fn solve_tune<D: DualModuleImpl>(&mut self, interface: &DualModuleInterfacePtr, dual_module: &mut D) {
+        if self.all_clusters_optimal(dual_module) {
+            return; // no plugin can improve an optimal solution
+        }
         let mut start = true;
 
         // starting with unbounded state here: All edges and nodes are not growing as of now
@@ -217,7 +228,10 @@ pub trait PrimalModuleImpl {
             PYTHON_SIGNAL_CHECKER.check().unwrap();
             if start {
                 start = false;
-                dual_module.advance_mode();
+                // already in tune mode when continuing with more plugins
+                if *dual_module.mode() == DualModuleMode::Search {
+                    dual_module.advance_mode();
+                }
             }
             self.update_sorted_clusters_aff(dual_module);
             let cluster_affs = self.get_sorted_clusters_aff();
@@ -306,6 +320,12 @@ pub trait PrimalModuleImpl {
         false
     }
 
+    /// whether every cluster already has a subgraph certified optimal by the search with the first plugin, normally
+    /// union-find, e.g. on syndromes whose clusters are all even and tight; then the remaining plugins can be skipped
+    fn all_clusters_optimal<D: DualModuleImpl>(&mut self, _dual_module: &mut D) -> bool {
+        false
+    }
+
     /// in "tune" mode, return the list of clusters that need to be resolved
     fn pending_clusters(&mut self) -> Vec<usize> {
         panic!("not implemented `pending_clusters`");
@@ -9,6 +9,7 @@ use crate::invalid_subgraph::*;
 use crate::matrix::*;
 use crate::num_traits::{One, Zero};
 use crate::plugin::*;
+use crate::plugin_union_find::PluginUnionFind;
 use crate::pointers::*;
 use crate::primal_module::*;
 use crate::relaxer_optimizer::*;
@@ -48,6 +49,24 @@ pub struct PrimalModuleSerial {
     #[cfg(feature = "incr_lp")]
     /// parameter indicating if the primal module has initialized states necessary for `incr_lp` slack calculation
     pub cluster_weights_initialized: bool,
+    /// the cluster lifecycle trace, only recorded when [`PrimalModuleSerialConfig::record_cluster_events`] is set
+    cluster_events: Vec<ClusterEvent>,
+    /// whether any cluster fell back to union-find because of [`PrimalModuleSerialConfig::max_cluster_size`]
+    approximate: bool,
+}
+
+/// an entry of the cluster lifecycle trace, see [`PrimalModuleSerial::cluster_events`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClusterEvent {
+    /// a cluster is created for a defect vertex when loading the syndrome
+    Create {
+        cluster_index: NodeIndex,
+        vertex_index: VertexIndex,
+    },
+    /// cluster `absorbed` is merged into cluster `into` and no longer exists
+    Merge { into: NodeIndex, absorbed: NodeIndex },
+    /// no relaxer is found for a cluster, so it holds a valid subgraph until it's merged or grown again
+    Resolve { cluster_index: NodeIndex },
 }
 
 #[derive(Eq, Debug, Clone)]
@@ -103,6 +122,40 @@ pub struct PrimalModuleSerialConfig {
     ///     might want to speed it up by disabling the feature and instead only solve primal problem once at the end
     #[serde(default = "primal_serial_default_configs::only_solve_primal_once")]
     pub only_solve_primal_once: bool,
+    /// the maximum number of subgraphs enumerated by `subgraph_range_all`, both per cluster and in total
+    #[serde(default = "primal_serial_default_configs::max_optimal_subgraphs")]
+    pub max_optimal_subgraphs: usize,
+    /// the order of resolving the clusters touched by the obstacles reported at the same time
+    #[serde(default)]
+    pub resolve_order: ResolveOrder,
+    /// record the creation, merging and resolution of clusters, see [`PrimalModuleSerial::cluster_events`]
+    #[serde(default)]
+    pub record_cluster_events: bool,
+    /// a cluster with more vertices than this is only grown by the union-find decoder, ignoring the plugins and the
+    ///     tuning phase; the result is then marked as approximate, see [`PrimalModuleSerial::is_approximate`]
+    #[serde(default = "primal_serial_default_configs::max_cluster_size")]
+    pub max_cluster_size: usize,
+    /// write the LP of every relaxer optimization to this file before solving it, for offline debugging with `slp`;
+    ///     see [`RelaxerOptimizer::lp_file`]
+    #[serde(default)]
+    pub relaxer_lp_file: Option<String>,
+}
+
+/// the order of resolving the clusters touched by simultaneous obstacles, which changes which relaxers are found first
+/// but not the optimum
+#[cfg_attr(feature = "python_binding", pyclass(module = "mwpf", eq, eq_int))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ResolveOrder {
+    /// ascending cluster index
+    #[default]
+    ClusterIndex,
+    /// ascending smallest edge index in the cluster
+    EdgeIndex,
+    /// descending cluster affinity, see [`DualModuleImpl::calculate_cluster_affinity`];
+    /// clusters without an affinity (zero primal-dual gap) come last
+    Affinity,
+    /// ascending smallest non-zero slack of the edges in the cluster, i.e. the cluster closest to its next conflict first
+    Slack,
 }
 
 pub mod primal_serial_default_configs {
@@ -115,6 +168,12 @@ pub mod primal_serial_default_configs {
     pub fn only_solve_primal_once() -> bool {
         false
     }
+    pub fn max_optimal_subgraphs() -> usize {
+        100
+    }
+    pub fn max_cluster_size() -> usize {
+        (2 << 53) - 1 // maximum integer that can be stored in JSON number without loss
+    }
 }
 
 pub struct PrimalModuleSerialNode {
@@ -140,6 +199,8 @@ pub struct PrimalCluster {
     pub matrix: EchelonMatrix,
     /// the parity subgraph result, only valid when it's solved
     pub subgraph: Option<Subgraph>,
+    /// the subgraph suggested by a plugin meets the sum of dual variables, so no relaxer search is needed
+    pub optimal: bool,
     /// plugin manager helps to execute the plugin and find an executable relaxer
     pub plugin_manager: PluginManager,
     /// optimizing the direction of relaxers
@@ -152,6 +213,27 @@ pub struct PrimalCluster {
 pub type PrimalClusterPtr = ArcRwLock<PrimalCluster>;
 pub type PrimalClusterWeak = WeakRwLock<PrimalCluster>;
 
+impl PrimalCluster {
+    /// the contribution of this cluster to the dual objective, i.e. the sum of its dual variables
+    pub fn dual_objective(&self) -> Rational {
+        self.nodes.iter().fold(Rational::zero(), |sum, primal_node_ptr| {
+            sum + primal_node_ptr
+                .read_recursive()
+                .dual_node_ptr
+                .read_recursive()
+                .get_dual_variable()
+        })
+    }
+
+    /// the weight of the subgraph chosen for this cluster, or `None` if it is not solved yet;
+    /// the cluster is optimal when this meets [`PrimalCluster::dual_objective`]
+    pub fn primal_weight(&self, dual_module: &impl DualModuleImpl) -> Option<Rational> {
+        self.subgraph
+            .as_ref()
+            .map(|subgraph| dual_module.get_subgraph_weight(subgraph))
+    }
+}
+
 impl PrimalModuleImpl for PrimalModuleSerial {
     fn new_empty(_initializer: &Arc<SolverInitializer>) -> Self {
         Self {
@@ -166,6 +248,8 @@ impl PrimalModuleImpl for PrimalModuleSerial {
             sorted_clusters_aff: None,
             #[cfg(feature = "incr_lp")]
             cluster_weights_initialized: false,
+            cluster_events: vec![],
+            approximate: false,
         }
     }
 
@@ -179,6 +263,8 @@ impl PrimalModuleImpl for PrimalModuleSerial {
         self.sorted_clusters_aff = None;
         #[cfg(feature = "incr_lp")]
         self.uninit_cluster_weight();
+        self.cluster_events.clear();
+        self.approximate = false;
     }
 
     #[allow(clippy::unnecessary_cast)]
@@ -205,6 +291,8 @@ impl PrimalModuleImpl for PrimalModuleSerial {
                 "must load defect nodes in order, did you forget to call solver.clear()?"
             );
             // construct cluster and its parity matrix (will be reused over all iterations)
+            let mut relaxer_optimizer = RelaxerOptimizer::new();
+            relaxer_optimizer.lp_file = self.config.relaxer_lp_file.clone();
             let primal_cluster_ptr = PrimalClusterPtr::new_value(PrimalCluster {
                 cluster_index: self.clusters.len() as NodeIndex,
                 nodes: vec![],
@@ -212,8 +300,9 @@ impl PrimalModuleImpl for PrimalModuleSerial {
                 vertices: node.invalid_subgraph.vertices.clone(),
                 matrix: node.invalid_subgraph.generate_matrix(&interface.decoding_graph),
                 subgraph: None,
+                optimal: false,
                 plugin_manager: PluginManager::new(self.plugins.clone(), self.plugin_count.clone()),
-                relaxer_optimizer: RelaxerOptimizer::new(),
+                relaxer_optimizer,
                 #[cfg(all(feature = "incr_lp", feature = "highs"))]
                 incr_solution: None,
             });
@@ -226,6 +315,10 @@ impl PrimalModuleImpl for PrimalModuleSerial {
             // add to self
             self.nodes.push(primal_node_ptr);
             self.clusters.push(primal_cluster_ptr);
+            self.record_cluster_event(ClusterEvent::Create {
+                cluster_index: index,
+                vertex_index: node.invalid_subgraph.vertices.first().cloned().unwrap(),
+            });
         }
     }
 
@@ -304,6 +397,17 @@ impl PrimalModuleImpl for PrimalModuleSerial {
         };
     }
 
+    /// a cluster is certified optimal if the weight of its subgraph meets the sum of its dual variables
+    fn all_clusters_optimal<D: DualModuleImpl>(&mut self, dual_module: &mut D) -> bool {
+        self.clusters.iter().all(|cluster_ptr| {
+            let cluster = cluster_ptr.read_recursive();
+            if cluster.nodes.is_empty() {
+                return true; // no longer a cluster
+            }
+            cluster.primal_weight(dual_module) == Some(cluster.dual_objective())
+        })
+    }
+
     /// get the pending clusters
     fn pending_clusters(&mut self) -> Vec<usize> {
         self.plugin_pending_clusters.clone()
@@ -331,14 +435,20 @@ impl PrimalModuleImpl for PrimalModuleSerial {
         }
         // update the matrix with new tight edges
         let cluster = &mut *cluster;
+        cluster.optimal = false;
         for &edge_index in cluster.edges.iter() {
             cluster
                 .matrix
                 .update_edge_tightness(edge_index, dual_module.is_edge_tight(edge_index));
         }
+        let fallback = cluster.vertices.len() > self.config.max_cluster_size;
+        self.approximate |= fallback;
 
-        // find an executable relaxer from the plugin manager
-        let relaxer = {
+        // find an executable relaxer from the plugin manager, or only from union-find for an oversized cluster
+        let relaxer = if fallback {
+            let decoding_graph = &interface_ptr.read_recursive().decoding_graph;
+            PluginUnionFind::find_single_relaxer(decoding_graph, &mut cluster.matrix)
+        } else {
             let positive_dual_variables: Vec<DualNodePtr> = cluster
                 .nodes
                 .iter()
@@ -372,12 +482,28 @@ impl PrimalModuleImpl for PrimalModuleSerial {
             return false;
         }
 
-        // TODO idea: plugins can suggest subgraph (ideally, a global maximum), if so, then it will adopt th
-        // subgraph with minimum weight from all plugins as the starting point to do local minimum
-
         // find a local minimum (hopefully a global minimum)
         let weight_of = |edge_index: EdgeIndex| dual_module.get_edge_weight(edge_index);
-        cluster.subgraph = Some(cluster.matrix.get_solution_local_minimum(weight_of).expect("satisfiable"));
+        let mut subgraph = cluster.matrix.get_solution_local_minimum(weight_of).expect("satisfiable");
+        // plugins may suggest a subgraph, e.g. from greedy matching; if its weight meets the sum of dual variables,
+        // then it's optimal and the cluster no longer needs any relaxer search when more plugins are enabled
+        let decoding_graph = &interface_ptr.read_recursive().decoding_graph;
+        let suggested = if fallback {
+            None
+        } else {
+            cluster
+                .plugin_manager
+                .suggest_subgraph(decoding_graph, &mut cluster.matrix, weight_of)
+        };
+        if let Some(suggested) = suggested {
+            let suggested_weight = dual_module.get_subgraph_weight(&suggested);
+            cluster.optimal = suggested_weight == cluster.dual_objective();
+            if suggested_weight <= dual_module.get_subgraph_weight(&subgraph) {
+                subgraph = suggested;
+            }
+        }
+        cluster.subgraph = Some(subgraph);
+        self.record_cluster_event(ClusterEvent::Resolve { cluster_index });
         true
     }
 
@@ -403,6 +529,16 @@ impl PrimalModuleImpl for PrimalModuleSerial {
         if cluster_temp.nodes.len() >= self.config.cluster_node_limit {
             return (true, optimizer_result);
         }
+        if cluster_temp.vertices.len() > self.config.max_cluster_size {
+            return (true, optimizer_result); // the union-find fallback has nothing to tune
+        }
+        if cluster_temp.edges.is_empty() {
+            // defect on an isolated vertex: no relaxer or subgraph exists, reported by `infeasible_clusters`
+            return (true, optimizer_result);
+        }
+        if cluster_temp.optimal {
+            return (true, optimizer_result);
+        }
         // update the matrix with new tight edges
         #[cfg(feature = "incr_lp")]
         let mut cluster = &mut *cluster_temp;
@@ -651,6 +787,7 @@ impl PrimalModuleImpl for PrimalModuleSerial {
             cluster.subgraph = Some(cluster.matrix.get_solution_local_minimum(weight_of).expect("satisfiable"));
         }
 
+        self.record_cluster_event(ClusterEvent::Resolve { cluster_index });
         (true, optimizer_result)
     }
 
@@ -711,7 +848,7 @@ impl PrimalModuleSerial {
     // union the cluster of two dual nodes
     #[allow(clippy::unnecessary_cast)]
     pub fn union(
-        &self,
+        &mut self,
         dual_node_ptr_1: &DualNodePtr,
         dual_node_ptr_2: &DualNodePtr,
         decoding_graph: &DecodingHyperGraph,
@@ -846,7 +983,44 @@ impl PrimalModuleSerial {
             }
         }
         cluster_1.relaxer_optimizer.append(&mut cluster_2.relaxer_optimizer);
+        cluster_1.optimal = false;
         cluster_2.vertices.clear();
+        let event = ClusterEvent::Merge {
+            into: cluster_1.cluster_index,
+            absorbed: cluster_2.cluster_index,
+        };
+        drop(cluster_1);
+        drop(cluster_2);
+        self.record_cluster_event(event);
+    }
+
+    /// the cluster lifecycle trace of the current decoding, in the order of the events; empty unless
+    /// [`PrimalModuleSerialConfig::record_cluster_events`] is set
+    pub fn cluster_events(&self) -> &[ClusterEvent] {
+        &self.cluster_events
+    }
+
+    /// append `plugins` after the current ones, also for the existing clusters, so that
+    /// [`PrimalModuleImpl::solve_tune`] continues the decoding with them
+    pub fn extend_plugins(&mut self, plugins: PluginVec) {
+        let mut extended = (*self.plugins).clone();
+        extended.extend(plugins);
+        self.plugins = Arc::new(extended);
+        for cluster_ptr in self.clusters.iter() {
+            cluster_ptr.write().plugin_manager.plugins = self.plugins.clone();
+        }
+    }
+
+    /// whether the last decoding let any cluster larger than [`PrimalModuleSerialConfig::max_cluster_size`] fall back to
+    /// the union-find decoder, in which case the subgraph is valid but not necessarily of minimum weight
+    pub fn is_approximate(&self) -> bool {
+        self.approximate
+    }
+
+    fn record_cluster_event(&mut self, event: ClusterEvent) {
+        if self.config.record_cluster_events {
+            self.cluster_events.push(event);
+        }
     }
 
     #[allow(clippy::unnecessary_cast)]
@@ -909,7 +1083,7 @@ impl PrimalModuleSerial {
             *self.plugin_count.write() = 0; // force only the first plugin
         }
         let mut all_solved = true;
-        for &cluster_index in active_clusters.iter() {
+        for cluster_index in self.ordered_clusters(&active_clusters, dual_module) {
             let solved = self.resolve_cluster(cluster_index, interface_ptr, dual_module);
             all_solved &= solved;
         }
@@ -920,6 +1094,48 @@ impl PrimalModuleSerial {
         true
     }
 
+    /// sort the clusters to be resolved according to [`PrimalModuleSerialConfig::resolve_order`]; ties are broken by
+    /// ascending cluster index
+    pub fn ordered_clusters(
+        &self,
+        cluster_indices: &BTreeSet<NodeIndex>,
+        dual_module: &mut impl DualModuleImpl,
+    ) -> Vec<NodeIndex> {
+        let mut ordered: Vec<NodeIndex> = cluster_indices.iter().cloned().collect();
+        match self.config.resolve_order {
+            ResolveOrder::ClusterIndex => {}
+            ResolveOrder::EdgeIndex => {
+                ordered.sort_by_cached_key(|&cluster_index| {
+                    let cluster = self.clusters[cluster_index].read_recursive();
+                    cluster.edges.first().cloned().unwrap_or(EdgeIndex::MAX)
+                });
+            }
+            ResolveOrder::Affinity => {
+                let affinities: BTreeMap<NodeIndex, Option<Affinity>> = ordered
+                    .iter()
+                    .map(|&cluster_index| {
+                        let cluster_ptr = self.clusters[cluster_index].clone();
+                        (cluster_index, dual_module.calculate_cluster_affinity(cluster_ptr))
+                    })
+                    .collect();
+                // `None` is smaller than any affinity, so it comes last in the descending order
+                ordered.sort_by(|a, b| affinities[b].cmp(&affinities[a]));
+            }
+            ResolveOrder::Slack => {
+                ordered.sort_by_cached_key(|&cluster_index| {
+                    let cluster = self.clusters[cluster_index].read_recursive();
+                    let min_slack = (cluster.matrix.get_edges().into_iter())
+                        .map(|edge_index| dual_module.get_edge_slack(edge_index))
+                        .filter(|slack| !slack.is_zero())
+                        .min();
+                    // clusters whose edges are all tight come last
+                    (min_slack.is_none(), min_slack)
+                });
+            }
+        }
+        ordered
+    }
+
     #[allow(clippy::unnecessary_cast)]
     /// for backwards-compatibility
     fn old_resolve_core(
@@ -1101,6 +1317,81 @@ impl PrimalModuleSerial {
         (all_obstacles, all_solved)
     }
 
+    /// clusters that remain unsolvable after solving, e.g. one containing a defect on an isolated vertex;
+    /// a syndrome is infeasible if and only if this is not empty
+    pub fn infeasible_clusters(&self) -> Vec<NodeIndex> {
+        self.clusters
+            .iter()
+            .map(|cluster_ptr| cluster_ptr.read_recursive())
+            .filter(|cluster| !cluster.nodes.is_empty() && cluster.subgraph.is_none())
+            .map(|cluster| cluster.cluster_index)
+            .collect()
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.infeasible_clusters().is_empty()
+    }
+
+    /// all the minimum-weight subgraphs, enumerated from the nullspace of each cluster's tight matrix; this requires
+    /// a zero primal-dual gap, otherwise only the current subgraph is returned because its optimality is not proven.
+    /// An empty vector is returned if any cluster is unsolvable.
+    pub fn subgraph_range_all(
+        &self,
+        interface: &DualModuleInterfacePtr,
+        dual_module: &impl DualModuleImpl,
+    ) -> Vec<OutputSubgraph> {
+        let limit = self.config.max_optimal_subgraphs;
+        let mut solved_clusters = vec![];
+        let mut total_weight = Rational::zero();
+        for cluster_ptr in self.clusters.iter() {
+            let cluster = cluster_ptr.read_recursive();
+            if cluster.nodes.is_empty() {
+                continue;
+            }
+            let Some(subgraph) = cluster.subgraph.clone() else {
+                return vec![];
+            };
+            let weight = dual_module.get_subgraph_weight(&subgraph);
+            total_weight += weight.clone();
+            solved_clusters.push((cluster_ptr, subgraph, weight));
+        }
+        let mut subgraphs: Vec<Subgraph> = vec![vec![]];
+        let is_optimal = total_weight == interface.sum_dual_variables();
+        for (cluster_ptr, subgraph, weight) in solved_clusters {
+            let mut solutions = vec![];
+            if is_optimal {
+                // with a zero gap, every optimal subgraph only consists of tight edges
+                let cluster = cluster_ptr.read_recursive();
+                let mut matrix = cluster.matrix.clone();
+                for &edge_index in cluster.edges.iter() {
+                    let is_tight = match dual_module.mode() {
+                        DualModuleMode::Search => dual_module.is_edge_tight(edge_index),
+                        DualModuleMode::Tune => dual_module.is_edge_tight_tune(edge_index),
+                    };
+                    matrix.update_edge_tightness(edge_index, is_tight);
+                }
+                solutions = matrix
+                    .get_solutions(limit)
+                    .into_iter()
+                    .filter(|solution| dual_module.get_subgraph_weight(solution) == weight)
+                    .collect();
+            }
+            if solutions.is_empty() {
+                solutions.push(subgraph);
+            }
+            subgraphs = subgraphs
+                .iter()
+                .cartesian_product(solutions.iter())
+                .map(|(partial, solution)| partial.iter().chain(solution.iter()).cloned().collect())
+                .take(limit)
+                .collect();
+        }
+        subgraphs
+            .into_iter()
+            .map(|subgraph| OutputSubgraph::new(subgraph, dual_module.get_negative_edges()))
+            .collect()
+    }
+
     pub fn print_clusters(&self) {
         let mut vertices = BTreeSet::new();
         let mut edges = BTreeSet::new();
@@ -1161,7 +1452,6 @@ pub mod tests {
     use super::super::example_codes::*;
     use super::*;
     use crate::plugin_single_hair::PluginSingleHair;
-    use crate::plugin_union_find::PluginUnionFind;
 
     #[allow(clippy::too_many_arguments)]
     pub fn primal_module_serial_basic_standard_syndrome_optional_viz(
@@ -1191,6 +1481,7 @@ pub mod tests {
             visualizer.as_mut(),
         );
 
+        dual_module.validate_state().unwrap();
         let (subgraph, weight_range) = primal_module.subgraph_range(&interface_ptr, &mut dual_module);
         if let Some(visualizer) = visualizer.as_mut() {
             visualizer
@@ -1403,4 +1694,315 @@ pub mod tests {
             ],
         );
     }
+
+    /// a defect on an isolated (degree-zero) vertex can never be matched; it should be reported as infeasible
+    #[test]
+    fn primal_module_serial_isolated_defect_vertex() {
+        // cargo test primal_module_serial_isolated_defect_vertex -- --nocapture
+        // vertex 3 is an unused ancilla without any incident edge
+        let initializer = Arc::new(SolverInitializer::new(
+            4,
+            vec![
+                HyperEdge::new(vec![0, 1], Rational::one()),
+                HyperEdge::new(vec![1, 2], Rational::one()),
+            ],
+        ));
+        initializer.sanity_check().unwrap();
+        let model_graph = Arc::new(crate::model_hypergraph::ModelHyperGraph::new(initializer.clone()));
+        for defect_vertices in [vec![3], vec![0, 2, 3]] {
+            let mut primal_module = PrimalModuleSerial::new_empty(&initializer);
+            primal_module.plugins = Arc::new(vec![
+                PluginUnionFind::entry(),
+                PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+            ]);
+            let mut dual_module = DualModulePQ::new_empty(&initializer);
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(
+                &interface_ptr,
+                Arc::new(SyndromePattern::new_vertices(defect_vertices.clone())),
+                &mut dual_module,
+            );
+            assert!(!primal_module.is_feasible());
+            let infeasible_clusters = primal_module.infeasible_clusters();
+            assert_eq!(infeasible_clusters.len(), 1);
+            let cluster = primal_module.clusters[infeasible_clusters[0] as usize].read_recursive();
+            assert_eq!(cluster.vertices, [3].into());
+            assert!(cluster.edges.is_empty());
+        }
+        // the solvable part of the graph is unaffected
+        let mut primal_module = PrimalModuleSerial::new_empty(&initializer);
+        let mut dual_module = DualModulePQ::new_empty(&initializer);
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(
+            &interface_ptr,
+            Arc::new(SyndromePattern::new_vertices(vec![0, 2])),
+            &mut dual_module,
+        );
+        assert!(primal_module.is_feasible());
+        let subgraph = primal_module.subgraph(&interface_ptr, &mut dual_module);
+        assert!(model_graph.matches_subgraph_syndrome(&subgraph, &[0, 2]));
+    }
+
+    /// negative edge weights are handled by flipping the syndrome in `weight_preprocessing`; the decoded subgraph
+    /// should be un-flipped and be the minimum-weight one under the original (negative) weights
+    #[test]
+    fn primal_module_serial_negative_weights() {
+        // cargo test primal_module_serial_negative_weights -- --nocapture
+        use crate::mwpf_solver::{SolverSerialJointSingleHair, SolverTrait};
+        let mut initializer = CodeCapacityRepetitionCode::new(7, 0.1).get_initializer();
+        let weights = [3., -2., 1.5, 4., 2., -0.5, 1.];
+        for (edge, weight) in initializer.weighted_edges.iter_mut().zip(weights) {
+            edge.weight = Rational::from_float(weight).unwrap();
+        }
+        let edge_num = initializer.weighted_edges.len();
+        let negative_weight_sum = Rational::from_float(-2.5).unwrap();
+        let initializer = Arc::new(initializer);
+        // brute-force the minimum weight of each syndrome
+        let mut min_weights = BTreeMap::<BTreeSet<VertexIndex>, Weight>::new();
+        for mask in 0..(1usize << edge_num) {
+            let subgraph = OutputSubgraph::new((0..edge_num).filter(|i| mask & (1 << i) != 0).collect(), Default::default());
+            let syndrome = initializer.get_subgraph_syndrome(&subgraph);
+            let weight = initializer.get_subgraph_total_weight(&subgraph);
+            if min_weights.get(&syndrome).is_none_or(|min_weight| &weight < min_weight) {
+                min_weights.insert(syndrome, weight);
+            }
+        }
+        let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+        for (syndrome, min_weight) in min_weights.iter() {
+            let defect_vertices: Vec<VertexIndex> = syndrome.iter().cloned().collect();
+            solver.solve(SyndromePattern::new_vertices(defect_vertices.clone()));
+            let (subgraph, weight_range) = solver.subgraph_range();
+            assert!(
+                initializer.matches_subgraph_syndrome(&subgraph, &defect_vertices),
+                "the un-flipped subgraph should generate the original syndrome"
+            );
+            assert_eq!(&initializer.get_subgraph_total_weight(&subgraph), min_weight);
+            assert_eq!(&weight_range.upper, min_weight);
+            assert!(weight_range.lower <= weight_range.upper);
+            // the lower bound accounts for the negative weights that are always taken
+            assert!(weight_range.lower >= negative_weight_sum);
+        }
+    }
+
+    #[test]
+    fn primal_module_serial_negative_weights_deterministic_output() {
+        // cargo test primal_module_serial_negative_weights_deterministic_output -- --nocapture
+        use crate::mwpf_solver::{SolverSerialJointSingleHair, SolverTrait};
+        let mut initializer = CodeCapacityRepetitionCode::new(7, 0.1).get_initializer();
+        let weights = [3., -2., 1.5, -4., 2., -0.5, 1.];
+        for (edge, weight) in initializer.weighted_edges.iter_mut().zip(weights) {
+            edge.weight = Rational::from_float(weight).unwrap();
+        }
+        let initializer = Arc::new(initializer);
+        let decode = || {
+            let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+            solver.solve(SyndromePattern::new_vertices(vec![0, 3, 5]));
+            let (subgraph, _) = solver.subgraph_range();
+            (
+                subgraph.iter().cloned().collect::<Vec<_>>(),
+                subgraph.flip_edge_indices.iter().cloned().collect::<Vec<_>>(),
+                format!("{subgraph:?}"),
+            )
+        };
+        let expected = decode();
+        assert_eq!(expected.1, vec![1, 3, 5]);
+        for _ in 0..10 {
+            assert_eq!(decode(), expected);
+        }
+    }
+
+    /// a defect pair on opposite corners of a square has two equal-weight corrections
+    #[test]
+    fn primal_module_serial_subgraph_range_all() {
+        // cargo test primal_module_serial_subgraph_range_all -- --nocapture
+        let initializer = Arc::new(SolverInitializer::new(
+            4,
+            vec![
+                HyperEdge::new(vec![0, 1], Rational::one()),
+                HyperEdge::new(vec![1, 2], Rational::one()),
+                HyperEdge::new(vec![2, 3], Rational::one()),
+                HyperEdge::new(vec![3, 0], Rational::one()),
+            ],
+        ));
+        let model_graph = Arc::new(crate::model_hypergraph::ModelHyperGraph::new(initializer.clone()));
+        let mut primal_module = PrimalModuleSerial::new_empty(&initializer);
+        primal_module.plugins = Arc::new(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let mut dual_module = DualModulePQ::new_empty(&initializer);
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(
+            &interface_ptr,
+            Arc::new(SyndromePattern::new_vertices(vec![0, 2])),
+            &mut dual_module,
+        );
+        let mut subgraphs: Vec<Subgraph> = primal_module
+            .subgraph_range_all(&interface_ptr, &dual_module)
+            .into_iter()
+            .map(|subgraph| subgraph.into_iter().sorted().collect())
+            .collect();
+        subgraphs.sort();
+        assert_eq!(subgraphs, vec![vec![0, 1], vec![2, 3]]);
+        // the cap bounds the enumeration
+        primal_module.config.max_optimal_subgraphs = 1;
+        assert_eq!(primal_module.subgraph_range_all(&interface_ptr, &dual_module).len(), 1);
+    }
+
+    #[test]
+    fn primal_module_serial_cluster_events() {
+        // cargo test primal_module_serial_cluster_events -- --nocapture
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let solve = |primal_module: &mut PrimalModuleSerial, dual_module: &mut DualModulePQ| {
+            primal_module.clear();
+            dual_module.clear();
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(
+                &interface_ptr,
+                Arc::new(SyndromePattern::new_vertices(vec![1, 2])),
+                dual_module,
+            );
+        };
+        // not recorded by default
+        solve(&mut primal_module, &mut dual_module);
+        assert!(primal_module.cluster_events().is_empty());
+        primal_module.config.record_cluster_events = true;
+        solve(&mut primal_module, &mut dual_module);
+        println!("{:?}", primal_module.cluster_events());
+        assert_eq!(
+            primal_module.cluster_events(),
+            [
+                ClusterEvent::Create {
+                    cluster_index: 0,
+                    vertex_index: 1
+                },
+                ClusterEvent::Create {
+                    cluster_index: 1,
+                    vertex_index: 2
+                },
+                ClusterEvent::Merge { into: 0, absorbed: 1 },
+                ClusterEvent::Resolve { cluster_index: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn primal_module_serial_max_cluster_size() {
+        // cargo test primal_module_serial_max_cluster_size -- --nocapture
+        let code = CodeCapacityColorCode::new(9, 0.1);
+        let model_graph = code.get_model_graph();
+        let defect_vertices = vec![1, 4, 9, 13, 16, 22, 25, 28];
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        primal_module.plugins = Arc::new(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let mut solve = |primal_module: &mut PrimalModuleSerial| {
+            primal_module.clear();
+            dual_module.clear();
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(
+                &interface_ptr,
+                Arc::new(SyndromePattern::new_vertices(defect_vertices.clone())),
+                &mut dual_module,
+            );
+            let (subgraph, weight_range) = primal_module.subgraph_range(&interface_ptr, &mut dual_module);
+            assert!(model_graph.matches_subgraph_syndrome(&subgraph, &defect_vertices));
+            weight_range
+        };
+        let exact = solve(&mut primal_module);
+        assert!(!primal_module.is_approximate());
+        primal_module.config.max_cluster_size = 3;
+        let approximate = solve(&mut primal_module);
+        assert!(primal_module.is_approximate());
+        // the union-find fallback still gives a valid, but possibly heavier, subgraph
+        assert!(approximate.upper >= exact.upper);
+        // the flag is reset for the next decoding
+        primal_module.config.max_cluster_size = primal_serial_default_configs::max_cluster_size();
+        solve(&mut primal_module);
+        assert!(!primal_module.is_approximate());
+    }
+
+    #[test]
+    fn primal_module_serial_cluster_dual_objective() {
+        // cargo test primal_module_serial_cluster_dual_objective -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let defect_vertices = vec![3, 12];
+        let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+        primal_module.plugins = Arc::new(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+        primal_module.solve(
+            &interface_ptr,
+            Arc::new(SyndromePattern::new_vertices(defect_vertices.clone())),
+            &mut dual_module,
+        );
+        let (subgraph, weight_range) = primal_module.subgraph_range(&interface_ptr, &mut dual_module);
+        assert!(model_graph.matches_subgraph_syndrome(&subgraph, &defect_vertices));
+        assert_eq!(weight_range.lower, weight_range.upper);
+        let mut solved_clusters = 0;
+        for cluster_ptr in primal_module.clusters.iter() {
+            let cluster = cluster_ptr.read_recursive();
+            if cluster.nodes.is_empty() {
+                continue;
+            }
+            let primal_weight = cluster.primal_weight(&dual_module).unwrap();
+            println!("cluster {}: {primal_weight:?}", cluster.cluster_index);
+            assert_eq!(cluster.dual_objective(), primal_weight);
+            solved_clusters += 1;
+        }
+        assert!(solved_clusters > 0);
+    }
+
+    #[test]
+    fn primal_module_serial_resolve_order() {
+        // cargo test primal_module_serial_resolve_order -- --nocapture
+        let code = CodeCapacityPlanarCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let mut orders = vec![];
+        let mut weights = vec![];
+        for resolve_order in [
+            ResolveOrder::ClusterIndex,
+            ResolveOrder::EdgeIndex,
+            ResolveOrder::Affinity,
+            ResolveOrder::Slack,
+        ] {
+            let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+            primal_module.plugins = Arc::new(vec![
+                PluginUnionFind::entry(),
+                PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+            ]);
+            primal_module.config.resolve_order = resolve_order;
+            primal_module.config.record_cluster_events = true;
+            let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(
+                &interface_ptr,
+                Arc::new(SyndromePattern::new_vertices(vec![30, 3, 17, 40, 9])),
+                &mut dual_module,
+            );
+            // the sequence in which the clusters are resolved during the search
+            let order: Vec<NodeIndex> = (primal_module.cluster_events().iter())
+                .filter_map(|event| match event {
+                    ClusterEvent::Resolve { cluster_index } => Some(*cluster_index),
+                    _ => None,
+                })
+                .collect();
+            orders.push(order);
+            let (_, weight_range) = primal_module.subgraph_range(&interface_ptr, &mut dual_module);
+            weights.push(weight_range.upper);
+        }
+        println!("orders: {orders:?}");
+        println!("weights: {weights:?}");
+        assert!(weights.iter().all(|weight| weight == &weights[0]));
+        assert!(orders.iter().any(|order| order != &orders[0]));
+    }
 }
@@ -52,6 +52,11 @@ impl UnionNodeTrait for PrimalModuleUnionFindNode {
         (true, result)
     }
     #[inline]
+    fn tie(left: &Self, right: &Self) -> bool {
+        // clusters that have absorbed the same number of edges are equally good roots
+        left.internal_edges.len() == right.internal_edges.len()
+    }
+    #[inline]
     fn clear(&mut self) {
         panic!("clear a node is meaningless here, call `remove_all` instead");
     }
@@ -392,4 +397,37 @@ pub mod tests {
             Rational::from_float(11.777755916665761).unwrap(),
         );
     }
+
+    #[test]
+    fn primal_module_union_find_tie_break() {
+        // cargo test primal_module_union_find_tie_break -- --nocapture
+        let code = CodeCapacityPlanarCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let defect_vertices = vec![1, 2, 4, 5, 6, 7, 10, 12];
+        let mut results = vec![];
+        for tie_break in [UnionTieBreak::LowerIndex, UnionTieBreak::Left] {
+            let mut primal_module = PrimalModuleUnionFind::new_empty(&model_graph.initializer);
+            primal_module.union_find.tie_break = tie_break;
+            let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(
+                &interface_ptr,
+                Arc::new(SyndromePattern::new_vertices(defect_vertices.clone())),
+                &mut dual_module,
+            );
+            // the clusters that tie are merged under a different root, so the new dual nodes are created in another order
+            let dual_nodes: Vec<BTreeSet<EdgeIndex>> = (interface_ptr.read_recursive().nodes.iter())
+                .map(|dual_node_ptr| dual_node_ptr.read_recursive().invalid_subgraph.edges.clone())
+                .collect();
+            let subgraph: Vec<EdgeIndex> = primal_module
+                .subgraph(&interface_ptr, &mut dual_module)
+                .iter()
+                .cloned()
+                .collect();
+            results.push((dual_nodes, subgraph));
+        }
+        // only the dual nodes depend on the tie-break, not the correction
+        assert_ne!(results[0].0, results[1].0);
+        assert_eq!(results[0].1, results[1].1);
+    }
 }
@@ -2,13 +2,14 @@ use crate::derivative::Derivative;
 use crate::invalid_subgraph::*;
 use crate::util::*;
 use num_traits::{Signed, Zero};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-#[derive(Clone, PartialEq, Eq, Derivative)]
+#[derive(Clone, PartialEq, Eq, Derivative, Serialize, Deserialize)]
 #[derivative(Debug)]
 pub struct Relaxer {
     /// the hash value calculated by other fields
@@ -8,12 +8,16 @@ use crate::num_traits::Zero;
 use crate::relaxer::*;
 use crate::util::*;
 use num_traits::Signed;
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 pub type RelaxerVec = Vec<Relaxer>;
 
-/// a forest of relaxers that possibly depend on each other
+/// a forest of relaxers that possibly depend on each other;
+/// it can be serialized and later merged into a fresh forest using [`RelaxerForest::merge_cached`],
+/// note that the maps are keyed by non-string values, so use a format like CBOR instead of JSON
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RelaxerForest {
     /// keep track of the remaining tight edges for quick validation:
     /// these edges cannot grow unless untightened by some relaxers
@@ -80,6 +84,49 @@ impl RelaxerForest {
         }
     }
 
+    /// merge the relaxers of a cached forest (e.g. from decoding a similar syndrome) into this fresh forest;
+    /// only the relaxers that remain valid given the tight edges and shrinkable subgraphs of this forest are kept,
+    /// together with their expanded results if those do not grow any tight edge of this forest.
+    /// returns the number of relaxers merged
+    pub fn merge_cached(&mut self, cached: &RelaxerForest) -> usize {
+        let mut pending: Vec<Arc<Relaxer>> = vec![];
+        for (relaxer, _) in cached.edge_untightener.values() {
+            if !pending.contains(relaxer) {
+                pending.push(relaxer.clone());
+            }
+        }
+        // a relaxer may only become valid after another one untightens its growing edges
+        let mut merged = 0;
+        loop {
+            let (valid, invalid): (Vec<_>, Vec<_>) = pending.into_iter().partition(|relaxer| self.validate(relaxer).is_ok());
+            if valid.is_empty() {
+                break;
+            }
+            merged += valid.len();
+            for relaxer in valid {
+                self.add(relaxer);
+            }
+            pending = invalid;
+        }
+        for (relaxer, expanded) in cached.expanded_relaxers.iter() {
+            if self.expanded_relaxers.contains_key(relaxer) || self.validate(relaxer).is_err() {
+                continue;
+            }
+            let expanded_valid = expanded
+                .get_growing_edges()
+                .keys()
+                .all(|edge_index| !self.tight_edges.contains(edge_index))
+                && expanded
+                    .get_direction()
+                    .iter()
+                    .all(|(subgraph, speed)| !speed.is_negative() || self.shrinkable_subgraphs.contains(subgraph));
+            if expanded_valid {
+                self.expanded_relaxers.insert(relaxer.clone(), expanded.clone());
+            }
+        }
+        merged
+    }
+
     fn compute_expanded(&mut self, relaxer: &Arc<Relaxer>) {
         if self.expanded_relaxers.contains_key(relaxer) {
             return;
@@ -328,4 +375,61 @@ pub mod tests {
         );
         relaxer_forest.validate(&relaxer).unwrap();
     }
+
+    fn example_seeded_forest() -> (RelaxerForest, [Arc<InvalidSubgraph>; 2], Arc<Relaxer>, Arc<Relaxer>) {
+        let tight_edges = [0, 1, 2, 3, 4, 5, 6];
+        let shrinkable_subgraphs = [
+            Arc::new(InvalidSubgraph::new_raw([].into(), [].into(), [1, 2, 3].into())),
+            Arc::new(InvalidSubgraph::new_raw([].into(), [].into(), [4, 5].into())),
+        ];
+        let mut relaxer_forest = RelaxerForest::new(tight_edges.into_iter(), shrinkable_subgraphs.iter().cloned());
+        let invalid_subgraph_1 = Arc::new(InvalidSubgraph::new_raw([].into(), [].into(), [7, 8, 9].into()));
+        let relaxer_1 = Arc::new(Relaxer::new_raw(
+            [
+                (invalid_subgraph_1, Rational::one()),
+                (shrinkable_subgraphs[0].clone(), -Rational::one()),
+            ]
+            .into(),
+        ));
+        relaxer_forest.add(relaxer_1.clone());
+        let invalid_subgraph_2 = Arc::new(InvalidSubgraph::new_raw([].into(), [].into(), [1, 2, 7].into()));
+        let relaxer_2 = Arc::new(Relaxer::new_raw([(invalid_subgraph_2, Rational::one())].into()));
+        relaxer_forest.expand(&relaxer_2);
+        (relaxer_forest, shrinkable_subgraphs, relaxer_1, relaxer_2)
+    }
+
+    #[test]
+    fn relaxer_forest_serialization() {
+        // cargo test relaxer_forest_serialization -- --nocapture
+        let (relaxer_forest, _, relaxer_1, relaxer_2) = example_seeded_forest();
+        let mut buffer = vec![];
+        ciborium::ser::into_writer(&relaxer_forest, &mut buffer).unwrap();
+        let restored: RelaxerForest = ciborium::de::from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(restored.tight_edges, relaxer_forest.tight_edges);
+        assert_eq!(restored.shrinkable_subgraphs, relaxer_forest.shrinkable_subgraphs);
+        assert_eq!(restored.edge_untightener, relaxer_forest.edge_untightener);
+        assert_eq!(restored.expanded_relaxers, relaxer_forest.expanded_relaxers);
+        assert_eq!(restored.edge_untightener.get(&1).unwrap().0, relaxer_1);
+        assert!(restored.expanded_relaxers.contains_key(&relaxer_2));
+    }
+
+    #[test]
+    fn relaxer_forest_merge_cached() {
+        // cargo test relaxer_forest_merge_cached -- --nocapture
+        let (cached, shrinkable_subgraphs, relaxer_1, relaxer_2) = example_seeded_forest();
+        // a similar syndrome: the same tight edges and shrinkable subgraphs
+        let mut relaxer_forest = RelaxerForest::new([0, 1, 2, 3, 4, 5, 6].into_iter(), shrinkable_subgraphs.iter().cloned());
+        assert_eq!(relaxer_forest.merge_cached(&cached), 1);
+        assert_eq!(relaxer_forest.edge_untightener.get(&2).unwrap().0, relaxer_1);
+        // the expanded relaxer is reused instead of being constructed again
+        let expanded_count = relaxer_forest.expanded_relaxers.len();
+        let expanded_2 = relaxer_forest.expand(&relaxer_2);
+        assert_eq!(relaxer_forest.expanded_relaxers.len(), expanded_count);
+        assert_eq!(&expanded_2, cached.expanded_relaxers.get(&relaxer_2).unwrap());
+        // a dissimilar syndrome where the cached relaxers are no longer valid: nothing is merged
+        let mut relaxer_forest = RelaxerForest::new([0, 1, 2, 3, 4, 5, 6].into_iter(), [].into_iter());
+        assert_eq!(relaxer_forest.merge_cached(&cached), 0);
+        assert!(relaxer_forest.edge_untightener.is_empty());
+        assert!(relaxer_forest.expanded_relaxers.is_empty());
+    }
 }
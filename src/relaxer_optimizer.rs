@@ -15,10 +15,8 @@ use std::sync::Arc;
 
 use derivative::Derivative;
 
-use num_traits::{Signed, Zero};
+use num_traits::{One, Signed, Zero};
 
-#[cfg(feature = "slp")]
-use num_traits::One;
 #[cfg(feature = "incr_lp")]
 use parking_lot::Mutex;
 #[cfg(feature = "incr_lp")]
@@ -74,6 +72,9 @@ impl OptimizerResult {
 pub struct RelaxerOptimizer {
     /// the set of existing relaxers
     relaxers: BTreeSet<Relaxer>,
+    /// if set, the LP of every [`RelaxerOptimizer::optimize`] is written to this file before solving, see
+    /// [`RelaxerOptimizer::lp_input`]; each LP overwrites the previous one, so that the file holds the latest LP
+    pub lp_file: Option<String>,
 }
 
 #[derive(Derivative)]
@@ -83,10 +84,16 @@ pub struct ConstraintLine {
     pub rhs: Rational,
 }
 
+#[cfg(not(feature = "float_lp"))]
 fn rational_to_str(value: &Rational) -> String {
     format!("{}/{}", value.numer(), value.denom())
 }
 
+#[cfg(feature = "float_lp")]
+fn rational_to_str(value: &Rational) -> String {
+    value.numer().to_string()
+}
+
 impl std::fmt::Display for ConstraintLine {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let lhs_str_vec: Vec<String> = self
@@ -124,18 +131,13 @@ impl RelaxerOptimizer {
         true
     }
 
-    #[cfg(not(feature = "float_lp"))]
-    pub fn optimize(
-        &mut self,
-        relaxer: Relaxer,
-        edge_slacks: BTreeMap<EdgeIndex, Rational>,
-        mut dual_variables: BTreeMap<Arc<InvalidSubgraph>, Rational>,
-    ) -> (Relaxer, bool) {
-        for invalid_subgraph in relaxer.get_direction().keys() {
-            if !dual_variables.contains_key(invalid_subgraph) {
-                dual_variables.insert(invalid_subgraph.clone(), Rational::zero());
-            }
-        }
+    /// the LP solved by [`RelaxerOptimizer::optimize`] in the input format of `slp`, so that it can be re-run offline:
+    /// the direction of every invalid subgraph `S` is `xS - yS` with `xS, yS >= 0`, which must neither shrink its dual
+    /// variable below zero nor grow any edge beyond its slack, and the objective is the sum of all the directions
+    pub fn lp_input(
+        edge_slacks: &BTreeMap<EdgeIndex, Rational>,
+        dual_variables: &BTreeMap<Arc<InvalidSubgraph>, Rational>,
+    ) -> String {
         // look at all existing invalid subgraphs and propose a best direction
         // each invalid subgraph corresponds to a variable
         // each edge_slack or dual_variable correspond to a constraint
@@ -143,7 +145,6 @@ impl RelaxerOptimizer {
         let mut x_vars = vec![];
         let mut y_vars = vec![];
         let mut constraints = vec![];
-        let mut invalid_subgraphs = Vec::with_capacity(dual_variables.len());
         let mut edge_contributor: BTreeMap<EdgeIndex, Vec<usize>> =
             edge_slacks.keys().map(|&edge_index| (edge_index, vec![])).collect();
         for (var_index, (invalid_subgraph, dual_variable)) in dual_variables.iter().enumerate() {
@@ -158,7 +159,6 @@ impl RelaxerOptimizer {
             constraint.lhs.push((Rational::one(), var_y.clone()));
             constraint.rhs = dual_variable.clone();
             constraints.push(constraint);
-            invalid_subgraphs.push(invalid_subgraph);
             for &edge_index in invalid_subgraph.hair.iter() {
                 edge_contributor.get_mut(&edge_index).unwrap().push(var_index);
             }
@@ -181,7 +181,7 @@ impl RelaxerOptimizer {
                 .collect::<Vec<_>>()
                 .join(", ");
         let max_line = "max ".to_string() + &x_vars.to_vec().join("+") + "-" + &y_vars.to_vec().join("-");
-        let input = vars_line
+        vars_line
             + "\n"
             + &max_line
             + "\n"
@@ -190,7 +190,30 @@ impl RelaxerOptimizer {
                 .iter()
                 .map(|constraint| constraint.to_string())
                 .collect::<Vec<_>>()
-                .join(",\n");
+                .join(",\n")
+    }
+
+    /// write the LP to [`RelaxerOptimizer::lp_file`], if set
+    fn write_lp_file(&self, input: &str) {
+        if let Some(lp_file) = &self.lp_file {
+            std::fs::write(lp_file, input).expect("cannot write the relaxer LP file");
+        }
+    }
+
+    #[cfg(not(feature = "float_lp"))]
+    pub fn optimize(
+        &mut self,
+        relaxer: Relaxer,
+        edge_slacks: BTreeMap<EdgeIndex, Rational>,
+        mut dual_variables: BTreeMap<Arc<InvalidSubgraph>, Rational>,
+    ) -> (Relaxer, bool) {
+        for invalid_subgraph in relaxer.get_direction().keys() {
+            if !dual_variables.contains_key(invalid_subgraph) {
+                dual_variables.insert(invalid_subgraph.clone(), Rational::zero());
+            }
+        }
+        let input = Self::lp_input(&edge_slacks, &dual_variables);
+        self.write_lp_file(&input);
 
         // println!("\n input:\n {}\n", input);
 
@@ -202,8 +225,9 @@ impl RelaxerOptimizer {
                 if !optimal_objective.is_positive() {
                     return (relaxer, true);
                 }
+                let var_num = dual_variables.len();
                 for (var_index, (invalid_subgraph, _)) in dual_variables.into_iter().enumerate() {
-                    let overall_growth = model[var_index].clone() - model[var_index + x_vars.len()].clone();
+                    let overall_growth = model[var_index].clone() - model[var_index + var_num].clone();
                     if !overall_growth.is_zero() {
                         // println!("overall_growth: {:?}", overall_growth);
                         direction.insert(invalid_subgraph, overall_growth);
@@ -235,6 +259,8 @@ impl RelaxerOptimizer {
             }
         }
 
+        self.write_lp_file(&Self::lp_input(&edge_slacks, &dual_variables));
+
         let mut model = RowProblem::default().optimise(Sense::Maximise);
         model.set_option("parallel", "off");
         model.set_option("threads", 1);
@@ -522,6 +548,53 @@ pub mod tests {
     //     let mut relaxer_optimizer = RelaxerOptimizer::new();
     // }
 
+    #[test]
+    fn relaxer_optimizer_lp_file() {
+        // cargo test relaxer_optimizer_lp_file -- --nocapture
+        use super::*;
+        use crate::invalid_subgraph::InvalidSubgraph;
+        use num_traits::FromPrimitive;
+        let invalid_subgraph_1 = Arc::new(InvalidSubgraph::new_raw([1].into(), BTreeSet::new(), [0, 1].into()));
+        let invalid_subgraph_2 = Arc::new(InvalidSubgraph::new_raw([2].into(), BTreeSet::new(), [1, 2].into()));
+        let relaxer = Relaxer::new(
+            [
+                (invalid_subgraph_1.clone(), -Rational::one()),
+                (invalid_subgraph_2.clone(), Rational::one()),
+            ]
+            .into(),
+        );
+        let edge_slacks: BTreeMap<EdgeIndex, Rational> = [
+            (0, Rational::zero()),
+            (1, Rational::one()),
+            (2, Rational::from_usize(2).unwrap()),
+        ]
+        .into();
+        let dual_variables = [(invalid_subgraph_1, Rational::one()), (invalid_subgraph_2, Rational::zero())].into();
+        let lp_file = tempfile::NamedTempFile::new().unwrap();
+        let mut relaxer_optimizer = RelaxerOptimizer::new();
+        relaxer_optimizer.lp_file = Some(lp_file.path().to_str().unwrap().to_string());
+        let (optimized_relaxer, early_returned) = relaxer_optimizer.optimize(relaxer, edge_slacks, dual_variables);
+        assert!(!early_returned);
+        let optimum = optimized_relaxer
+            .get_direction()
+            .values()
+            .fold(Rational::zero(), |sum, growth| sum + growth);
+        assert!(rational_approx_eq(&optimum, &Rational::one()));
+        // solve the emitted LP again, which should reach the same optimum
+        let input = std::fs::read_to_string(lp_file.path()).unwrap();
+        println!("{input}");
+        #[cfg(not(feature = "float_lp"))]
+        let solution = slp::Solver::<Rational>::new(&input).solve();
+        #[cfg(feature = "float_lp")]
+        let solution = slp::Solver::<f64>::new(&input).solve();
+        match solution {
+            slp::Solution::Optimal(objective, _) => {
+                assert!(rational_approx_eq(&Rational::from(objective), &optimum));
+            }
+            _ => panic!("the emitted LP should have an optimal solution"),
+        }
+    }
+
     #[cfg(feature = "slp")]
     #[test]
     fn lp_solver_simple() {
@@ -1,5 +1,7 @@
+use crate::num_traits::float::FloatCore;
 use crate::num_traits::{One, Zero};
 use crate::*;
+use alloc::vec::Vec;
 
 /// Number trait used in this library.
 pub trait Number:
@@ -8,19 +10,19 @@ pub trait Number:
     + Sync
     + One
     + Zero
-    + std::str::FromStr
-    + std::ops::Neg<Output = Self>
-    + std::ops::Add<Output = Self>
-    + std::ops::Sub<Output = Self>
-    + std::ops::Mul<Output = Self>
-    + std::ops::Div<Output = Self>
-    + std::ops::AddAssign
-    + std::ops::SubAssign
-    + std::ops::MulAssign
-    + std::ops::DivAssign
-    + std::cmp::PartialOrd
-    + std::fmt::Debug
-    + std::fmt::Display
+    + core::str::FromStr
+    + core::ops::Neg<Output = Self>
+    + core::ops::Add<Output = Self>
+    + core::ops::Sub<Output = Self>
+    + core::ops::Mul<Output = Self>
+    + core::ops::Div<Output = Self>
+    + core::ops::AddAssign
+    + core::ops::SubAssign
+    + core::ops::MulAssign
+    + core::ops::DivAssign
+    + core::cmp::PartialOrd
+    + core::fmt::Debug
+    + core::fmt::Display
 {
     /// Returns greatest integer less than or equal to.
     fn floor(&self) -> Self;
@@ -28,29 +30,58 @@ pub trait Number:
     fn ceil(&self) -> Self;
     /// Checks if it is an integer.
     fn is_integer(&self) -> bool;
+    /// Parses a number literal, returning `None` if it is malformed or not representable
+    /// (e.g. an integer overflowing a `Rational64`).
+    fn try_from_str(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+    /// Formats the number with the given number of decimals.
+    ///
+    /// Exact number types ignore the precision and are always printed exactly.
+    fn fmt_with_precision(
+        &self,
+        f: &mut core::fmt::Formatter,
+        _precision: usize,
+    ) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
 }
 
 impl Number for f32 {
     fn floor(&self) -> Self {
-        f32::floor(*self)
+        FloatCore::floor(*self)
     }
     fn ceil(&self) -> Self {
-        f32::ceil(*self)
+        FloatCore::ceil(*self)
     }
     fn is_integer(&self) -> bool {
-        self.fract().abs() <= std::f32::EPSILON
+        FloatCore::abs(FloatCore::fract(*self)) <= f32::EPSILON
+    }
+    fn fmt_with_precision(
+        &self,
+        f: &mut core::fmt::Formatter,
+        precision: usize,
+    ) -> core::fmt::Result {
+        write!(f, "{:.*}", precision, self)
     }
 }
 
 impl Number for f64 {
     fn floor(&self) -> Self {
-        f64::floor(*self)
+        FloatCore::floor(*self)
     }
     fn ceil(&self) -> Self {
-        f64::ceil(*self)
+        FloatCore::ceil(*self)
     }
     fn is_integer(&self) -> bool {
-        self.fract().abs() <= std::f64::EPSILON
+        FloatCore::abs(FloatCore::fract(*self)) <= f64::EPSILON
+    }
+    fn fmt_with_precision(
+        &self,
+        f: &mut core::fmt::Formatter,
+        precision: usize,
+    ) -> core::fmt::Result {
+        write!(f, "{:.*}", precision, self)
     }
 }
 
@@ -103,12 +134,98 @@ pub enum Solution<T> {
     Optimal(T, Vec<T>),
 }
 
+/// A variable added by the pricing oracle of [column generation](crate::Solver::solve_with_pricing).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column<T> {
+    /// Coefficient of the variable in the objective.
+    pub objective: T,
+    /// Coefficient of the variable in each constraint, in the order of the constraints and in
+    /// their `<=` form, i.e. negated for a `>=` constraint.
+    pub coefficients: Vec<T>,
+}
+
+/// An optimal solution of the restricted problem passed to the pricing oracle of
+/// [column generation](crate::Solver::solve_with_pricing).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DualSolution<T> {
+    /// Optimal value of the objective.
+    pub objective: T,
+    /// Optimal assignment of the variables, including the columns added so far.
+    pub model: Vec<T>,
+    /// Dual value of each constraint: a column `(c, a)` improves the objective if
+    /// `c - duals · a` is positive when maximizing, or negative when minimizing.
+    pub duals: Vec<T>,
+}
+
+impl<N: Number> Solution<N> {
+    /// Returns a displayable view of the solution, where floating point values are
+    /// rounded to `precision` decimals and exact values are printed exactly.
+    pub fn display_with(&self, precision: usize) -> SolutionDisplay<'_, N> {
+        SolutionDisplay {
+            solution: self,
+            precision,
+        }
+    }
+}
+
+/// Displays a [`Solution`] with a given precision, see [`Solution::display_with`].
+pub struct SolutionDisplay<'a, N> {
+    solution: &'a Solution<N>,
+    precision: usize,
+}
+
+impl<'a, N: Number> core::fmt::Display for SolutionDisplay<'a, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.solution {
+            Solution::Infeasible => write!(f, "INFEASIBLE"),
+            Solution::Unbounded => write!(f, "UNBOUNDED"),
+            Solution::Optimal(obj, model) => {
+                write!(f, "OPTIMAL ")?;
+                obj.fmt_with_precision(f, self.precision)?;
+                write!(f, "\nSOLUTION")?;
+                for v in model {
+                    write!(f, " ")?;
+                    v.fmt_with_precision(f, self.precision)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Solver settings that can be passed to the solver instance.
 pub enum SolverSettings {
-    /// Enables data parallelism while solving.
+    /// Enables data parallelism while solving: column pricing and pivoting are
+    /// parallelized using rayon once the tableau is large enough.
     EnableDataParallelism,
 }
 
+#[cfg(feature = "std")]
 pub(crate) struct SolverOptions {
     pub parallel: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn solution_display_with_precision() {
+        let solution = Solution::Optimal(5.0000001f64, vec![4.999999, 0.]);
+        assert_eq!(
+            solution.display_with(2).to_string(),
+            "OPTIMAL 5.00\nSOLUTION 5.00 0.00"
+        );
+        let solution = Solution::Optimal(Rational64::new(28, 3), vec![Rational64::new(1, 3)]);
+        assert_eq!(
+            solution.display_with(2).to_string(),
+            "OPTIMAL 28/3\nSOLUTION 1/3"
+        );
+        assert_eq!(
+            Solution::<f64>::Infeasible.display_with(2).to_string(),
+            "INFEASIBLE"
+        );
+    }
+}
@@ -5,6 +5,7 @@
 //! ## An example
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
 //! fn main() {
 //!     use slp::*;
 //!     use slp::Rational64;
@@ -36,24 +37,40 @@
 //!         }
 //!     }
 //! }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //! ```
+//!
+//! ## `no_std` support
+//!
+//! The simplex kernel in [`lp`] only needs `alloc`; building with `default-features = false`
+//! drops the parser, the [`Solver`] front-end and data parallelism, which all need `std`.
 
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate pest_derive;
 
 mod common;
 pub use common::*;
-mod lp;
+pub mod lp;
+#[cfg(feature = "std")]
+mod parallel;
 
 pub use num_bigint::BigInt;
 pub use num_rational::{BigRational, Ratio, Rational32, Rational64};
 pub use num_traits;
 
 /// A General Linear Programming Solver.
+#[cfg(feature = "std")]
 mod solver;
+#[cfg(feature = "std")]
 pub use solver::*;
 
 /// Parser module for Linear Programming Problems.
+#[cfg(feature = "std")]
 pub mod parser;
@@ -1,87 +1,100 @@
+//! The simplex kernel, which only needs `alloc`.
+//!
+//! The data parallel code paths live in the `std`-only `parallel` module; without `std`,
+//! the `parallel` flags are ignored and everything is solved serially.
+
+#[cfg(feature = "std")]
+use crate::parallel;
 use crate::{Number, Solution};
-use rayon::prelude::*;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Minimum number of tableau columns for which pricing and pivoting are parallelized,
+/// smaller problems are solved serially even if data parallelism is enabled.
+pub const PARALLEL_COLUMNS_THRESHOLD: usize = 64;
 
 /// Represents an LP instance.
 pub struct LP<N> {
+    /// Number of constraints, i.e. rows of the tableau besides the objective row.
     pub n_constraints: usize,
+    /// Number of variables, excluding the slack variables.
     pub n_vars: usize,
+    /// Basic variable of each row, the entry of the objective row is unused.
     pub basic_indices: Vec<usize>,
-    pub tableau: Vec<Vec<N>>, // Row major format
+    /// Row major tableau, the first row is the objective and the first column is the right hand side.
+    pub tableau: Vec<Vec<N>>,
+    /// Number of pivots performed on this instance.
+    pub n_iterations: usize,
 }
 
 impl<N: Number> LP<N> {
+    /// Creates an instance maximizing `objective · x` subject to `a · x <= b` for each
+    /// `(a, b)` in `constraints` and `x >= 0`, with one slack variable per constraint.
+    pub fn new(objective: Vec<N>, constraints: Vec<(Vec<N>, N)>) -> Self {
+        let n_constraints = constraints.len();
+        let n_vars = objective.len();
+        let mut tableau = Vec::with_capacity(n_constraints + 1);
+        let mut basic_indices = vec![0];
+        let mut obj = vec![N::zero()];
+        obj.extend(objective.into_iter().map(|c| -c));
+        obj.extend((0..n_constraints).map(|_| N::zero()));
+        tableau.push(obj);
+        for (i, (coefficients, rhs)) in constraints.into_iter().enumerate() {
+            let mut row = vec![rhs];
+            row.extend(coefficients);
+            row.extend((0..n_constraints).map(|j| if i == j { N::one() } else { N::zero() }));
+            tableau.push(row);
+            basic_indices.push(n_vars + i + 1);
+        }
+        LP {
+            n_constraints,
+            n_vars,
+            basic_indices,
+            tableau,
+            n_iterations: 0,
+        }
+    }
+
+    /// Solves the LP, using the dual simplex method if the initial basis is dual feasible
+    /// and the two phase simplex method otherwise.
     pub fn solve(&mut self, parallel: bool) -> Solution<N> {
         if self.is_dual_feasible() {
             return self.dual_simplex(parallel);
         }
 
         let mut is_b_negative = vec![false; self.n_constraints + 1];
-        let no_b_negative = if parallel {
-            is_b_negative[1..]
-                .par_iter_mut()
-                .zip(&self.tableau[1..])
-                .map(|(is_b_i_neg, constr)| {
-                    if constr[0] < N::zero() {
-                        *is_b_i_neg = true;
-                        1
-                    } else {
-                        0
-                    }
-                })
-                .sum()
-        } else {
-            is_b_negative[1..]
-                .iter_mut()
-                .zip(&self.tableau[1..])
-                .map(|(is_b_i_neg, constr)| {
-                    if constr[0] < N::zero() {
-                        *is_b_i_neg = true;
-                        1
-                    } else {
-                        0
-                    }
-                })
-                .sum()
-        };
+        let no_b_negative = self.mark_negative_rhs(&mut is_b_negative[1..], parallel);
 
         let tot_col = self.tableau[0].len();
         if no_b_negative != 0 {
             let mut auxi_lp = self.create_auxi_lp(is_b_negative, no_b_negative);
-            match auxi_lp.simplex(parallel) {
+            let auxi_solution = auxi_lp.simplex(parallel);
+            self.n_iterations += auxi_lp.n_iterations;
+            match auxi_solution {
                 Solution::Infeasible => return Solution::Infeasible,
                 Solution::Unbounded => return Solution::Unbounded,
                 Solution::Optimal(obj, _) => {
                     if obj != N::zero() {
                         return Solution::Infeasible;
                     }
-                    if parallel {
-                        self.tableau[1..=self.n_constraints]
-                            .par_iter_mut()
-                            .zip(&auxi_lp.tableau[1..=self.n_constraints])
-                            .for_each(|(t, a)| {
-                                t[..tot_col].clone_from_slice(&a[..tot_col]);
-                            });
-                        self.basic_indices
-                            .par_iter_mut()
-                            .zip(&auxi_lp.basic_indices)
-                            .for_each(|(b, &a)| {
-                                *b = a;
-                            });
-                    } else {
-                        self.tableau[1..=self.n_constraints]
-                            .iter_mut()
-                            .zip(&auxi_lp.tableau[1..=self.n_constraints])
-                            .for_each(|(t, a)| {
-                                t[..tot_col].clone_from_slice(&a[..tot_col]);
-                            });
-                        self.basic_indices
-                            .iter_mut()
-                            .zip(&auxi_lp.basic_indices)
-                            .for_each(|(b, &a)| {
-                                *b = a;
-                            });
+                    // drive the artificial variables that remain basic (at zero level) out of the basis;
+                    // a row without any nonzero original column is redundant and keeps its artificial variable
+                    for i in 1..=self.n_constraints {
+                        if auxi_lp.basic_indices[i] >= tot_col {
+                            if let Some(j) =
+                                (1..tot_col).find(|&j| auxi_lp.tableau[i][j] != N::zero())
+                            {
+                                LP::pivot(&mut auxi_lp.tableau, j, i, parallel);
+                                auxi_lp.basic_indices[i] = j;
+                                self.n_iterations += 1;
+                            }
+                        }
                     }
+                    self.copy_basis(&auxi_lp, tot_col, parallel);
                     for i in 1..=self.n_constraints {
+                        if self.basic_indices[i] >= tot_col {
+                            continue; // redundant row
+                        }
                         let multipler = self.tableau[0][self.basic_indices[i]].clone();
                         for j in 0..tot_col {
                             let num_to_sub = multipler.clone() * self.tableau[i][j].clone();
@@ -94,6 +107,56 @@ impl<N: Number> LP<N> {
         self.simplex(parallel)
     }
 
+    /// Marks the constraints with a negative right hand side, returning how many there are.
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    fn mark_negative_rhs(&self, is_b_negative: &mut [bool], parallel: bool) -> usize {
+        #[cfg(feature = "std")]
+        if parallel {
+            return parallel::mark_negative_rhs(is_b_negative, &self.tableau[1..]);
+        }
+        is_b_negative
+            .iter_mut()
+            .zip(&self.tableau[1..])
+            .map(|(is_b_i_neg, constr)| {
+                if constr[0] < N::zero() {
+                    *is_b_i_neg = true;
+                    1
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    /// Copies the constraint rows and the basis found by the auxiliary LP of phase one.
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    fn copy_basis(&mut self, auxi_lp: &LP<N>, tot_col: usize, parallel: bool) {
+        let rows = &mut self.tableau[1..=self.n_constraints];
+        let auxi_rows = &auxi_lp.tableau[1..=self.n_constraints];
+        #[cfg(feature = "std")]
+        if parallel {
+            parallel::copy_basis(
+                rows,
+                &mut self.basic_indices,
+                auxi_rows,
+                &auxi_lp.basic_indices,
+                tot_col,
+            );
+            return;
+        }
+        rows.iter_mut().zip(auxi_rows).for_each(|(t, a)| {
+            t[..tot_col].clone_from_slice(&a[..tot_col]);
+        });
+        self.basic_indices
+            .iter_mut()
+            .zip(&auxi_lp.basic_indices)
+            .for_each(|(b, &a)| {
+                *b = a;
+            });
+    }
+
+    /// Creates the auxiliary LP of phase one, with an artificial variable
+    /// for each constraint that has a negative right hand side.
     pub fn create_auxi_lp(&self, is_b_negative: Vec<bool>, no_b_negative: usize) -> LP<N> {
         let mut tableau = vec![];
         let tot_col = self.tableau[0].len();
@@ -153,17 +216,14 @@ impl<N: Number> LP<N> {
             n_vars: self.n_vars + no_b_negative,
             basic_indices: auxi_basic_indices,
             tableau,
+            n_iterations: 0,
         }
     }
 
+    /// Runs the primal simplex method from a primal feasible basis.
     pub fn simplex(&mut self, parallel: bool) -> Solution<N> {
         loop {
-            let mut entering_var = 1;
-            for (i, v) in self.tableau[0].iter().enumerate() {
-                if *v < N::zero() && i != 0 && *v < self.tableau[0][entering_var] {
-                    entering_var = i;
-                }
-            }
+            let entering_var = self.price_entering_var(parallel);
 
             if self.tableau[0][entering_var] >= N::zero() {
                 let mut model = vec![];
@@ -202,9 +262,29 @@ impl<N: Number> LP<N> {
 
             LP::pivot(&mut self.tableau, entering_var, leaving_var, parallel);
             self.basic_indices[leaving_var] = entering_var;
+            self.n_iterations += 1;
+        }
+    }
+
+    /// Chooses the entering variable with the most negative reduced cost,
+    /// the first one is chosen on ties; returns 1 if no reduced cost is negative.
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    pub fn price_entering_var(&self, parallel: bool) -> usize {
+        let objective = &self.tableau[0];
+        #[cfg(feature = "std")]
+        if parallel && objective.len() >= PARALLEL_COLUMNS_THRESHOLD {
+            return parallel::price_entering_var(objective);
         }
+        let mut entering_var = 1;
+        for (i, v) in objective.iter().enumerate() {
+            if *v < N::zero() && i != 0 && *v < objective[entering_var] {
+                entering_var = i;
+            }
+        }
+        entering_var
     }
 
+    /// Runs the dual simplex method from a dual feasible basis.
     pub fn dual_simplex(&mut self, parallel: bool) -> Solution<N> {
         loop {
             let mut leaving_var = 1;
@@ -233,30 +313,91 @@ impl<N: Number> LP<N> {
                 break Solution::Optimal(self.tableau[0][0].clone(), model);
             }
 
-            let mut entering_var = 1;
+            // only a negative coefficient can make the leaving row nonnegative
+            let mut entering_var = None;
             for i in 1..self.tableau[0].len() {
-                if self.tableau[leaving_var][entering_var] == N::zero() {
-                    entering_var = i;
-                    continue;
-                }
                 if self.tableau[leaving_var][i] < N::zero()
-                    && (-self.tableau[0][i].clone() / self.tableau[leaving_var][i].clone()
-                        < -self.tableau[0][entering_var].clone()
-                            / self.tableau[leaving_var][entering_var].clone())
+                    && entering_var.is_none_or(|e: usize| {
+                        -self.tableau[0][i].clone() / self.tableau[leaving_var][i].clone()
+                            < -self.tableau[0][e].clone() / self.tableau[leaving_var][e].clone()
+                    })
                 {
-                    entering_var = i;
+                    entering_var = Some(i);
                 }
             }
-
-            if self.tableau[leaving_var][entering_var] >= N::zero() {
-                break Solution::Infeasible;
-            }
+            let entering_var = match entering_var {
+                Some(entering_var) => entering_var,
+                None => break Solution::Infeasible,
+            };
 
             LP::pivot(&mut self.tableau, entering_var, leaving_var, parallel);
             self.basic_indices[leaving_var] = entering_var;
+            self.n_iterations += 1;
         }
     }
 
+    /// Adds a variable with the given objective and constraint coefficients to an instance
+    /// solved to optimality, keeping the basis so that [`simplex`](Self::simplex) can resume.
+    ///
+    /// The column is expressed in the current basis through the slack columns, which hold the
+    /// inverse of the basis; the new variable is placed before the slack variables.
+    pub fn add_column(&mut self, objective: N, coefficients: Vec<N>) {
+        assert_eq!(
+            coefficients.len(),
+            self.n_constraints,
+            "a column must have a coefficient for every constraint"
+        );
+        let slack_start = self.n_vars + 1;
+        let column: Vec<N> = self
+            .tableau
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut v = if i == 0 {
+                    -objective.clone()
+                } else {
+                    N::zero()
+                };
+                for (k, a) in coefficients.iter().enumerate() {
+                    v += row[slack_start + k].clone() * a.clone();
+                }
+                v
+            })
+            .collect();
+        for (row, v) in self.tableau.iter_mut().zip(column) {
+            row.insert(slack_start, v);
+        }
+        for basic_index in self.basic_indices.iter_mut().skip(1) {
+            if *basic_index >= slack_start {
+                *basic_index += 1;
+            }
+        }
+        self.n_vars += 1;
+    }
+
+    /// Replaces the right hand sides of the constraints, keeping the basis so that
+    /// [`solve`](Self::solve) can resume from it, by the dual simplex method if it was optimal.
+    ///
+    /// As in [`add_column`](Self::add_column), the new right hand sides are expressed in the
+    /// current basis through the slack columns, and so is the objective value.
+    pub fn update_rhs(&mut self, rhs: &[N]) {
+        assert_eq!(
+            rhs.len(),
+            self.n_constraints,
+            "must provide a right hand side for every constraint"
+        );
+        let slack_start = self.n_vars + 1;
+        for row in self.tableau.iter_mut() {
+            let mut v = N::zero();
+            for (k, b) in rhs.iter().enumerate() {
+                v += row[slack_start + k].clone() * b.clone();
+            }
+            row[0] = v;
+        }
+    }
+
+    /// Pivots the tableau on the given entering variable (column) and leaving variable (row).
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
     pub fn pivot(
         tableau: &mut Vec<Vec<N>>,
         entering_var: usize,
@@ -264,14 +405,13 @@ impl<N: Number> LP<N> {
         parallel: bool,
     ) {
         let pivot_coeff = tableau[leaving_var][entering_var].clone();
-        if parallel {
-            tableau[leaving_var].par_iter_mut().for_each(|v| {
-                *v /= pivot_coeff.clone();
-            });
-        } else {
-            tableau[leaving_var].iter_mut().for_each(|v| {
-                *v /= pivot_coeff.clone();
-            });
+        tableau[leaving_var].iter_mut().for_each(|v| {
+            *v /= pivot_coeff.clone();
+        });
+        #[cfg(feature = "std")]
+        if parallel && tableau[leaving_var].len() >= PARALLEL_COLUMNS_THRESHOLD {
+            parallel::eliminate(tableau, entering_var, leaving_var);
+            return;
         }
         for k in 0..tableau.len() {
             if k != leaving_var {
@@ -284,6 +424,7 @@ impl<N: Number> LP<N> {
         }
     }
 
+    /// Checks whether no reduced cost is negative.
     pub fn is_dual_feasible(&self) -> bool {
         for v in &self.tableau[0] {
             if *v < N::zero() {
@@ -292,4 +433,42 @@ impl<N: Number> LP<N> {
         }
         true
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rational64;
+
+    #[test]
+    fn lp_kernel_solve() {
+        // the same problem as the crate example, built without the parser so that it also runs without `std`
+        let constraints = [(vec![2, 1], 18), (vec![6, 5], 60), (vec![2, 5], 40)];
+        let build = |convert: fn(i64) -> Rational64| {
+            LP::new(
+                vec![convert(2), convert(3)],
+                constraints
+                    .iter()
+                    .map(|(a, b)| (a.iter().map(|&v| convert(v)).collect(), convert(*b)))
+                    .collect(),
+            )
+        };
+        let mut lp = build(Rational64::from_integer);
+        assert_eq!(
+            lp.solve(false),
+            Solution::Optimal(
+                Rational64::from_integer(28),
+                vec![Rational64::from_integer(5), Rational64::from_integer(6)]
+            )
+        );
+        assert!(lp.n_iterations > 0);
+        // a negative right hand side goes through the auxiliary LP of phase one: x1 + x2 >= 2, x1 <= 3
+        let mut lp = LP::new(
+            vec![-1., -2.],
+            vec![(vec![-1., -1.], -2.), (vec![1., 0.], 3.)],
+        );
+        assert_eq!(lp.solve(false), Solution::Optimal(-2., vec![2., 0.]));
+        let mut lp = LP::new(vec![1., 1.], vec![(vec![1., -1.], 1.)]);
+        assert_eq!(lp.solve(false), Solution::Unbounded);
+    }
+}
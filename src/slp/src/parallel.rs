@@ -0,0 +1,69 @@
+//! Data parallel versions of the tableau operations in [`crate::lp`], built on rayon.
+
+use crate::Number;
+use rayon::prelude::*;
+
+/// Marks the constraints with a negative right hand side, returning how many there are.
+pub(crate) fn mark_negative_rhs<N: Number>(is_b_negative: &mut [bool], rows: &[Vec<N>]) -> usize {
+    is_b_negative
+        .par_iter_mut()
+        .zip(rows)
+        .map(|(is_b_i_neg, constr)| {
+            if constr[0] < N::zero() {
+                *is_b_i_neg = true;
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Copies the first `tot_col` columns of the constraint rows and the basic indices.
+pub(crate) fn copy_basis<N: Number>(
+    rows: &mut [Vec<N>],
+    basic_indices: &mut [usize],
+    auxi_rows: &[Vec<N>],
+    auxi_basic_indices: &[usize],
+    tot_col: usize,
+) {
+    rows.par_iter_mut().zip(auxi_rows).for_each(|(t, a)| {
+        t[..tot_col].clone_from_slice(&a[..tot_col]);
+    });
+    basic_indices
+        .par_iter_mut()
+        .zip(auxi_basic_indices)
+        .for_each(|(b, &a)| {
+            *b = a;
+        });
+}
+
+/// Chooses the entering variable with the most negative reduced cost,
+/// the first one is chosen on ties; returns 1 if no reduced cost is negative.
+pub(crate) fn price_entering_var<N: Number>(objective: &[N]) -> usize {
+    objective
+        .par_iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, v)| **v < N::zero())
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+        .unwrap_or(1)
+}
+
+/// Eliminates the entering variable from every row but the (already normalized) pivot row.
+pub(crate) fn eliminate<N: Number>(
+    tableau: &mut [Vec<N>],
+    entering_var: usize,
+    leaving_var: usize,
+) {
+    let pivot_row = tableau[leaving_var].clone();
+    tableau.par_iter_mut().enumerate().for_each(|(k, row)| {
+        if k != leaving_var {
+            let multiplier = row[entering_var].clone();
+            for (v, p) in row.iter_mut().zip(&pivot_row) {
+                *v -= multiplier.clone() * p.clone();
+            }
+        }
+    });
+}
@@ -0,0 +1,547 @@
+use super::{parse_number_literal, LpProblem, ObjectiveType};
+use crate::Number;
+use pest::error::{Error, ErrorVariant};
+use pest::iterators::Pair;
+use pest::{Parser, Span};
+use std::collections::HashMap;
+
+mod lp_file_parser {
+    #[derive(Parser)]
+    #[grammar = "parser/lp_file.pest"]
+    pub struct LpFileParser;
+}
+
+use lp_file_parser::*;
+
+/// Sparse linear expression as a list of (variable index, coefficient).
+pub(super) type Terms<N> = Vec<(usize, N)>;
+
+#[derive(PartialEq)]
+enum OperatorType {
+    LtEq,
+    GtEq,
+    Eq,
+}
+
+enum BoundValue<N> {
+    Finite(N),
+    PosInfinity,
+    NegInfinity,
+}
+
+/// Variables are declared implicitly in the LP file format, indexed in the order they first appear.
+pub(super) struct Variables<N> {
+    pub names: Vec<String>,
+    indices: HashMap<String, usize>,
+    pub is_int_constraints: Vec<bool>,
+    /// `None` for the default lower bound of 0
+    pub lower_bounds: Vec<Option<N>>,
+    /// `None` for no upper bound
+    pub upper_bounds: Vec<Option<N>>,
+}
+
+impl<N: Number> Variables<N> {
+    pub fn new() -> Self {
+        Variables {
+            names: vec![],
+            indices: HashMap::new(),
+            is_int_constraints: vec![],
+            lower_bounds: vec![],
+            upper_bounds: vec![],
+        }
+    }
+
+    pub fn index_of(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+        let index = self.names.len();
+        self.names.push(name.to_string());
+        self.indices.insert(name.to_string(), index);
+        self.is_int_constraints.push(false);
+        self.lower_bounds.push(None);
+        self.upper_bounds.push(None);
+        index
+    }
+
+    pub fn dense(&self, terms: &Terms<N>) -> Vec<N> {
+        let mut dense = vec![N::zero(); self.names.len()];
+        for (index, coefficient) in terms.iter() {
+            dense[*index] += coefficient.clone();
+        }
+        dense
+    }
+
+    fn unit(&self, index: usize, coefficient: N) -> Vec<N> {
+        let mut dense = vec![N::zero(); self.names.len()];
+        dense[index] = coefficient;
+        dense
+    }
+
+    /// Converts the variable bounds other than `x >= 0` into `<=` constraints.
+    pub fn bound_constraints(&self) -> Vec<(Vec<N>, N)> {
+        let mut constraints = vec![];
+        for index in 0..self.names.len() {
+            if let Some(lower) = self.lower_bounds[index].clone() {
+                if lower > N::zero() {
+                    constraints.push((self.unit(index, -N::one()), -lower));
+                }
+            }
+            if let Some(upper) = self.upper_bounds[index].clone() {
+                constraints.push((self.unit(index, N::one()), upper));
+            }
+        }
+        constraints
+    }
+}
+
+fn custom_error(message: String, span: Span) -> Error<Rule> {
+    Error::new_from_span(ErrorVariant::CustomError { message }, span)
+}
+
+fn parse_number<N: Number>(pair: Pair<Rule>) -> Result<N, Error<Rule>> {
+    parse_number_literal(pair.as_span())
+}
+
+fn parse_sign(pair: &Pair<Rule>) -> bool {
+    pair.as_rule() == Rule::sign && pair.as_str() == "-"
+}
+
+fn parse_operator(pair: Pair<Rule>) -> OperatorType {
+    match pair.into_inner().next().unwrap().as_rule() {
+        Rule::lteq => OperatorType::LtEq,
+        Rule::gteq => OperatorType::GtEq,
+        Rule::eq => OperatorType::Eq,
+        _ => unreachable!(),
+    }
+}
+
+fn parse_signed_number<N>(pair: Pair<Rule>) -> Result<N, Error<Rule>>
+where
+    N: Number,
+    N::Err: std::fmt::Debug,
+{
+    let mut is_negative = false;
+    let mut value = N::zero();
+    for rule in pair.into_inner() {
+        match rule.as_rule() {
+            Rule::sign => is_negative = parse_sign(&rule),
+            Rule::number => value = parse_number(rule)?,
+            _ => unreachable!(),
+        }
+    }
+    Ok(if is_negative { -value } else { value })
+}
+
+fn parse_bound_value<N>(pair: Pair<Rule>) -> Result<BoundValue<N>, Error<Rule>>
+where
+    N: Number,
+    N::Err: std::fmt::Debug,
+{
+    let mut is_negative = false;
+    let mut value = BoundValue::<N>::PosInfinity;
+    for rule in pair.into_inner() {
+        match rule.as_rule() {
+            Rule::sign => is_negative = parse_sign(&rule),
+            Rule::infinity => value = BoundValue::PosInfinity,
+            Rule::number => value = BoundValue::Finite(parse_number(rule)?),
+            _ => unreachable!(),
+        }
+    }
+    Ok(match (value, is_negative) {
+        (BoundValue::Finite(value), true) => BoundValue::Finite(-value),
+        (BoundValue::PosInfinity, true) => BoundValue::NegInfinity,
+        (value, _) => value,
+    })
+}
+
+fn parse_expression<N>(
+    pair: Pair<Rule>,
+    variables: &mut Variables<N>,
+) -> Result<Terms<N>, Error<Rule>>
+where
+    N: Number,
+    N::Err: std::fmt::Debug,
+{
+    let mut terms = vec![];
+    for signed_term in pair.into_inner() {
+        let mut is_negative = false;
+        for rule in signed_term.into_inner() {
+            match rule.as_rule() {
+                Rule::sign => is_negative = parse_sign(&rule),
+                Rule::term => {
+                    let mut coefficient = N::one();
+                    for inner in rule.into_inner() {
+                        match inner.as_rule() {
+                            Rule::number => coefficient = parse_number(inner)?,
+                            Rule::identifier => {
+                                let index = variables.index_of(inner.as_str());
+                                if is_negative {
+                                    coefficient = -coefficient;
+                                }
+                                terms.push((index, coefficient.clone()));
+                            }
+                            _ => unreachable!(),
+                        }
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+    Ok(terms)
+}
+
+fn set_lower_bound<N: Number>(
+    variables: &mut Variables<N>,
+    index: usize,
+    value: BoundValue<N>,
+    span: Span,
+) -> Result<(), Error<Rule>> {
+    match value {
+        BoundValue::Finite(value) if value >= N::zero() => {
+            variables.lower_bounds[index] = Some(value);
+            Ok(())
+        }
+        BoundValue::PosInfinity => Err(custom_error(
+            "lower bound cannot be +infinity".to_string(),
+            span,
+        )),
+        _ => Err(custom_error(
+            format!(
+                "variable `{}` must be non-negative, negative lower bounds are not supported",
+                variables.names[index]
+            ),
+            span,
+        )),
+    }
+}
+
+fn set_upper_bound<N: Number>(
+    variables: &mut Variables<N>,
+    index: usize,
+    value: BoundValue<N>,
+    span: Span,
+) -> Result<(), Error<Rule>> {
+    match value {
+        BoundValue::Finite(value) => {
+            variables.upper_bounds[index] = Some(value);
+            Ok(())
+        }
+        BoundValue::PosInfinity => {
+            variables.upper_bounds[index] = None;
+            Ok(())
+        }
+        BoundValue::NegInfinity => Err(custom_error(
+            "upper bound cannot be -infinity".to_string(),
+            span,
+        )),
+    }
+}
+
+fn parse_bound<N>(pair: Pair<Rule>, variables: &mut Variables<N>) -> Result<(), Error<Rule>>
+where
+    N: Number,
+    N::Err: std::fmt::Debug,
+{
+    let span = pair.as_span();
+    let bound = pair.into_inner().next().unwrap();
+    let rule = bound.as_rule();
+    let mut inner_pairs = bound.into_inner();
+    match rule {
+        Rule::free_bound => {
+            let var = inner_pairs.next().unwrap().as_str();
+            Err(custom_error(
+                format!(
+                    "variable `{}` must be non-negative, free variables are not supported",
+                    var
+                ),
+                span,
+            ))
+        }
+        Rule::ranged_bound => {
+            let lower = parse_bound_value(inner_pairs.next().unwrap())?;
+            inner_pairs.next().unwrap();
+            let index = variables.index_of(inner_pairs.next().unwrap().as_str());
+            inner_pairs.next().unwrap();
+            let upper = parse_bound_value(inner_pairs.next().unwrap())?;
+            set_lower_bound(variables, index, lower, span)?;
+            set_upper_bound(variables, index, upper, span)
+        }
+        Rule::variable_bound | Rule::reversed_bound => {
+            let (index, oper, value) = if rule == Rule::variable_bound {
+                let index = variables.index_of(inner_pairs.next().unwrap().as_str());
+                let oper = parse_operator(inner_pairs.next().unwrap());
+                let value = parse_bound_value::<N>(inner_pairs.next().unwrap())?;
+                (index, oper, value)
+            } else {
+                let value = parse_bound_value::<N>(inner_pairs.next().unwrap())?;
+                // `value <= x` is equivalent to `x >= value`
+                let oper = match parse_operator(inner_pairs.next().unwrap()) {
+                    OperatorType::LtEq => OperatorType::GtEq,
+                    OperatorType::GtEq => OperatorType::LtEq,
+                    OperatorType::Eq => OperatorType::Eq,
+                };
+                let index = variables.index_of(inner_pairs.next().unwrap().as_str());
+                (index, oper, value)
+            };
+            match oper {
+                OperatorType::LtEq => set_upper_bound(variables, index, value, span),
+                OperatorType::GtEq => set_lower_bound(variables, index, value, span),
+                OperatorType::Eq => match value {
+                    BoundValue::Finite(value) => {
+                        set_lower_bound(variables, index, BoundValue::Finite(value.clone()), span)?;
+                        set_upper_bound(variables, index, BoundValue::Finite(value), span)
+                    }
+                    _ => Err(custom_error(
+                        "variable cannot be fixed to infinity".to_string(),
+                        span,
+                    )),
+                },
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Parses LP Problem from the CPLEX LP file format.
+///
+/// The supported sections are the objective (`Maximize`/`Minimize`), `Subject To`,
+/// `Bounds`, `General` and `Binary`, terminated by an optional `End`.
+/// Variables are declared implicitly and ordered by their first appearance.
+/// Since all variables of [`LpProblem`] are non-negative, negative lower bounds
+/// and free variables are rejected; other bounds are converted into constraints.
+pub fn parse_lp_file_format<N>(input: &str) -> Result<LpProblem<N>, Error<Rule>>
+where
+    N: Number,
+    N::Err: std::fmt::Debug,
+{
+    let lp_file = LpFileParser::parse(Rule::lp_file, input)?.next().unwrap();
+
+    let mut variables = Variables::<N>::new();
+    let mut objective_type = ObjectiveType::Max;
+    let mut objective = vec![];
+    let mut constraints = vec![];
+
+    for section in lp_file.into_inner() {
+        match section.as_rule() {
+            Rule::objective => {
+                let objective_rule = section.into_inner().next().unwrap();
+                if objective_rule.as_rule() == Rule::min_objective {
+                    objective_type = ObjectiveType::Min;
+                }
+                for rule in objective_rule.into_inner() {
+                    if rule.as_rule() == Rule::expression {
+                        objective = parse_expression(rule, &mut variables)?;
+                    }
+                }
+            }
+            Rule::constraints => {
+                for constraint in section.into_inner() {
+                    if constraint.as_rule() != Rule::constraint {
+                        continue;
+                    }
+                    let mut exp = vec![];
+                    let mut oper = OperatorType::LtEq;
+                    let mut rhs = N::zero();
+                    for rule in constraint.into_inner() {
+                        match rule.as_rule() {
+                            Rule::label => {}
+                            Rule::expression => exp = parse_expression(rule, &mut variables)?,
+                            Rule::operator => oper = parse_operator(rule),
+                            Rule::signed_number => rhs = parse_signed_number(rule)?,
+                            _ => unreachable!(),
+                        }
+                    }
+                    constraints.push((exp, oper, rhs));
+                }
+            }
+            Rule::bounds => {
+                for bound in section.into_inner() {
+                    if bound.as_rule() == Rule::bound {
+                        parse_bound(bound, &mut variables)?;
+                    }
+                }
+            }
+            Rule::generals | Rule::binaries => {
+                let is_binary = section.as_rule() == Rule::binaries;
+                for rule in section.into_inner() {
+                    if rule.as_rule() == Rule::identifier {
+                        let index = variables.index_of(rule.as_str());
+                        variables.is_int_constraints[index] = true;
+                        if is_binary {
+                            variables.lower_bounds[index] = None;
+                            variables.upper_bounds[index] = Some(N::one());
+                        }
+                    }
+                }
+            }
+            Rule::end_keyword | Rule::EOI => {}
+            _ => unreachable!(),
+        }
+    }
+
+    let mut dense_objective = variables.dense(&objective);
+    if objective_type == ObjectiveType::Min {
+        for v in dense_objective.iter_mut() {
+            *v = -v.clone();
+        }
+    }
+    let mut dense_constraints = vec![];
+    for (exp, oper, rhs) in constraints.iter() {
+        let exp = variables.dense(exp);
+        if oper != &OperatorType::GtEq {
+            dense_constraints.push((exp.clone(), rhs.clone()));
+        }
+        if oper != &OperatorType::LtEq {
+            dense_constraints.push((exp.into_iter().map(|v| -v).collect(), -rhs.clone()));
+        }
+    }
+    dense_constraints.extend(variables.bound_constraints());
+
+    Ok(LpProblem {
+        vars_list: variables.names,
+        is_int_constraints: variables.is_int_constraints,
+        constraints: dense_constraints,
+        objective: dense_objective,
+        objective_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rational64, Solution, Solver};
+
+    fn rational(numer: i64, denom: i64) -> Rational64 {
+        Rational64::new(numer, denom)
+    }
+
+    #[test]
+    fn lp_file_format_matches_dsl() {
+        let lp_file = r"
+            \ the example in the README
+            Maximize
+             obj: 2 x1 + 3 x2
+            Subject To
+             c1: 2 x1 + x2 <= 18
+             c2: 6 x1 + 5 x2 <= 60
+             c3: 2 x1 + 5 x2 <= 40
+            Bounds
+             x1 >= 0
+             x2 >= 0
+            End
+        ";
+        let dsl = "
+            vars x1>=0, x2>=0
+            max 2x1+3x2
+            subject to
+                2x1 +  x2 <= 18,
+                6x1 + 5x2 <= 60,
+                2x1 + 5x2 <= 40
+        ";
+        let problem = parse_lp_file_format::<Rational64>(lp_file).unwrap();
+        assert_eq!(problem.vars_list, ["x1", "x2"]);
+        let solution = Solver::from(problem).solve();
+        assert_eq!(solution, Solver::<Rational64>::new(dsl).solve());
+        assert_eq!(
+            solution,
+            Solution::Optimal(rational(28, 1), vec![rational(5, 1), rational(6, 1)])
+        );
+    }
+
+    #[test]
+    fn lp_file_format_minimize_with_bounds() {
+        let lp_file = r"
+            MINIMIZE
+             cost: x + 2 y - z
+            SUBJECT TO
+             total: x + y + z <= 10
+             - x + y >= -2
+            BOUNDS
+             1 <= x <= 4
+             z <= 3
+            GENERALS
+             y
+            END
+        ";
+        let problem = parse_lp_file_format::<Rational64>(lp_file).unwrap();
+        assert_eq!(problem.vars_list, ["x", "y", "z"]);
+        assert_eq!(problem.is_int_constraints, [false, true, false]);
+        let dsl = "
+            vars x>=0, y(Z)>=0, z>=0
+            min x + 2y - z
+            subject to
+                x + y + z <= 10,
+                -x + y >= -2,
+                x >= 1,
+                x <= 4,
+                z <= 3
+        ";
+        let solution = Solver::from(problem).solve();
+        assert_eq!(solution, Solver::<Rational64>::new(dsl).solve());
+        assert_eq!(
+            solution,
+            Solution::Optimal(
+                rational(-2, 1),
+                vec![rational(1, 1), rational(0, 1), rational(3, 1)]
+            )
+        );
+    }
+
+    #[test]
+    fn lp_file_format_equality_constraint() {
+        let lp_file = "
+            maximize
+             a + b
+            subject to
+             a + 2 b = 3
+            end
+        ";
+        let problem = parse_lp_file_format::<Rational64>(lp_file).unwrap();
+        assert_eq!(
+            problem.constraints,
+            [
+                (vec![rational(1, 1), rational(2, 1)], rational(3, 1)),
+                (vec![rational(-1, 1), rational(-2, 1)], rational(-3, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lp_file_format_rejects_negative_variables() {
+        let lp_file = "
+            max x
+            st
+             x <= 1
+            bounds
+             x >= -1
+            end
+        ";
+        assert!(parse_lp_file_format::<Rational64>(lp_file).is_err());
+        let lp_file = "
+            max x
+            st
+             x <= 1
+            bounds
+             x free
+            end
+        ";
+        assert!(parse_lp_file_format::<f64>(lp_file).is_err());
+    }
+
+    #[test]
+    fn lp_file_format_rejects_unrepresentable_numbers() {
+        let lp_file = "
+            max x
+            st
+             x <= 99999999999999999999
+            end
+        ";
+        let error = parse_lp_file_format::<Rational64>(lp_file).err().unwrap();
+        assert!(error
+            .to_string()
+            .contains("cannot represent number `99999999999999999999`"));
+        assert!(parse_lp_file_format::<crate::BigRational>(lp_file).is_ok());
+    }
+}
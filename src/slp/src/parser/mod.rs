@@ -1,5 +1,5 @@
 use crate::Number;
-use pest::error::Error;
+use pest::error::{Error, ErrorVariant};
 use pest::Parser;
 
 mod lp_parser {
@@ -10,6 +10,11 @@ mod lp_parser {
 
 use lp_parser::*;
 
+mod lp_file;
+pub use lp_file::parse_lp_file_format;
+mod mps;
+pub use mps::parse_mps;
+
 /// LP Problem instance.
 pub struct LpProblem<N> {
     /// Variables list.
@@ -24,6 +29,41 @@ pub struct LpProblem<N> {
     pub(crate) objective_type: ObjectiveType,
 }
 
+impl<N: Number> LpProblem<N> {
+    /// Creates a problem optimizing `objective · x` subject to `a · x <= b` for each `(a, b)` in
+    /// `constraints` and `x >= 0`, without integer constraints, e.g. to build a problem in code
+    /// rather than parsing it.
+    pub fn new(
+        vars_list: Vec<String>,
+        objective_type: ObjectiveType,
+        objective: Vec<N>,
+        constraints: Vec<(Vec<N>, N)>,
+    ) -> Self {
+        assert_eq!(
+            objective.len(),
+            vars_list.len(),
+            "objective must have a coefficient for every variable"
+        );
+        assert!(
+            constraints.iter().all(|(a, _)| a.len() == vars_list.len()),
+            "constraints must have a coefficient for every variable"
+        );
+        // stored as maximized, like the parsers do
+        let objective = if objective_type == ObjectiveType::Min {
+            objective.into_iter().map(|v| -v).collect()
+        } else {
+            objective
+        };
+        LpProblem {
+            is_int_constraints: vec![false; vars_list.len()],
+            vars_list,
+            constraints,
+            objective,
+            objective_type,
+        }
+    }
+}
+
 #[derive(PartialEq)]
 enum OperatorType {
     LtEq,
@@ -62,6 +102,19 @@ struct AstInternal {
     objective_type: ObjectiveType,
 }
 
+/// Parses a number literal with [`Number::try_from_str`], so that every input format reports a
+/// malformed or unrepresentable number with the same error.
+fn parse_number_literal<N: Number, R: pest::RuleType>(span: pest::Span) -> Result<N, Error<R>> {
+    N::try_from_str(span.as_str()).ok_or_else(|| {
+        Error::new_from_span(
+            ErrorVariant::CustomError {
+                message: format!("cannot represent number `{}`", span.as_str()),
+            },
+            span,
+        )
+    })
+}
+
 /// Parses LP Problem from given input.
 pub fn parse_lp_problem<N>(input: &str) -> Result<LpProblem<N>, Error<Rule>>
 where
@@ -78,7 +131,10 @@ where
         objective_type: ObjectiveType::Max,
     };
 
-    fn parse_pair<N>(pair: Pair<Rule>, internal: &mut AstInternal) -> AstNode<N>
+    fn parse_pair<N>(
+        pair: Pair<Rule>,
+        internal: &mut AstInternal,
+    ) -> Result<AstNode<N>, Error<Rule>>
     where
         N: Number,
         N::Err: std::fmt::Debug,
@@ -87,12 +143,12 @@ where
             Rule::lp_problem => {
                 let mut inner_pairs = pair.into_inner();
                 let vars_rule = inner_pairs.next().unwrap();
-                parse_pair::<N>(vars_rule, internal);
+                parse_pair::<N>(vars_rule, internal)?;
                 let obj_rule = inner_pairs.next().unwrap();
-                let obj = parse_pair(obj_rule, internal);
+                let obj = parse_pair(obj_rule, internal)?;
                 let cons_rule = inner_pairs.next().unwrap();
-                let cons = parse_pair(cons_rule, internal);
-                AstNode::Lp {
+                let cons = parse_pair(cons_rule, internal)?;
+                Ok(AstNode::Lp {
                     objective: if let AstNode::Objective(vs) = obj {
                         vs
                     } else {
@@ -103,13 +159,13 @@ where
                     } else {
                         unreachable!()
                     },
-                }
+                })
             }
             Rule::variables => {
                 let mut vars = vec![];
                 let mut is_int_constraints = vec![];
                 for rule in pair.into_inner() {
-                    match parse_pair::<N>(rule, internal) {
+                    match parse_pair::<N>(rule, internal)? {
                         AstNode::Variable(var) => {
                             vars.push(var);
                             is_int_constraints.push(false);
@@ -124,22 +180,22 @@ where
                 internal.n_vars = vars.len();
                 internal.variables = vars;
                 internal.is_int_constraints = is_int_constraints;
-                AstNode::None
+                Ok(AstNode::None)
             }
             Rule::variable_real => {
                 let var = pair.into_inner().next().unwrap().as_str();
-                AstNode::Variable(var.to_string())
+                Ok(AstNode::Variable(var.to_string()))
             }
             Rule::variable_int => {
                 let var = pair.into_inner().next().unwrap().as_str();
-                AstNode::VariableInt(var.to_string())
+                Ok(AstNode::VariableInt(var.to_string()))
             }
             Rule::variable => parse_pair(pair.into_inner().next().unwrap(), internal),
             Rule::max_objective => {
                 internal.objective_type = ObjectiveType::Max;
                 let exp = pair.into_inner().next().unwrap();
-                if let AstNode::Expression(exp) = parse_pair(exp, internal) {
-                    AstNode::Objective(exp)
+                if let AstNode::Expression(exp) = parse_pair(exp, internal)? {
+                    Ok(AstNode::Objective(exp))
                 } else {
                     unreachable!()
                 }
@@ -147,11 +203,11 @@ where
             Rule::min_objective => {
                 let exp = pair.into_inner().next().unwrap();
                 internal.objective_type = ObjectiveType::Min;
-                if let AstNode::Expression(mut exp) = parse_pair::<N>(exp, internal) {
+                if let AstNode::Expression(mut exp) = parse_pair::<N>(exp, internal)? {
                     for v in exp.iter_mut() {
                         *v = -v.clone();
                     }
-                    AstNode::Objective(exp)
+                    Ok(AstNode::Objective(exp))
                 } else {
                     unreachable!()
                 }
@@ -160,18 +216,18 @@ where
             Rule::constraints => {
                 let mut cons = vec![];
                 for rule in pair.into_inner() {
-                    if let AstNode::Constraint(exp, rhs) = parse_pair(rule, internal) {
-                        cons.push((exp, rhs));
-                    } else {
-                        unreachable!()
+                    match parse_pair(rule, internal)? {
+                        AstNode::Constraint(exp, rhs) => cons.push((exp, rhs)),
+                        AstNode::Constraints(rows) => cons.extend(rows),
+                        _ => unreachable!(),
                     }
                 }
-                AstNode::Constraints(cons)
+                Ok(AstNode::Constraints(cons))
             }
             Rule::constraint => {
                 let mut inner_pairs = pair.into_inner();
                 let exp_rule = inner_pairs.next().unwrap();
-                let exp = parse_pair::<N>(exp_rule, internal);
+                let exp = parse_pair::<N>(exp_rule, internal)?;
                 let opr_rule = inner_pairs.next().unwrap();
                 let oper = match opr_rule.as_str() {
                     "<=" => OperatorType::LtEq,
@@ -179,7 +235,7 @@ where
                     _ => unreachable!(),
                 };
                 let rhs_rule = inner_pairs.next().unwrap();
-                let rhs = parse_pair::<N>(rhs_rule, internal);
+                let rhs = parse_pair::<N>(rhs_rule, internal)?;
                 match (exp, rhs) {
                     (AstNode::Expression(mut exp), AstNode::Number(mut rhs)) => {
                         if oper == OperatorType::GtEq {
@@ -188,7 +244,23 @@ where
                             }
                             rhs = -rhs;
                         }
-                        AstNode::Constraint(exp, rhs)
+                        Ok(AstNode::Constraint(exp, rhs))
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            Rule::ranged_constraint => {
+                // `lo <= exp <= hi` is `-exp <= -lo` and `exp <= hi`
+                let mut inner_pairs = pair.into_inner();
+                let lo = parse_pair::<N>(inner_pairs.next().unwrap(), internal)?;
+                inner_pairs.next().unwrap();
+                let exp = parse_pair::<N>(inner_pairs.next().unwrap(), internal)?;
+                inner_pairs.next().unwrap();
+                let hi = parse_pair::<N>(inner_pairs.next().unwrap(), internal)?;
+                match (lo, exp, hi) {
+                    (AstNode::Number(lo), AstNode::Expression(exp), AstNode::Number(hi)) => {
+                        let neg_exp = exp.iter().map(|t| -t.clone()).collect();
+                        Ok(AstNode::Constraints(vec![(neg_exp, -lo), (exp, hi)]))
                     }
                     _ => unreachable!(),
                 }
@@ -196,20 +268,21 @@ where
             Rule::expression => {
                 let mut terms = vec![N::zero(); internal.n_vars];
                 for rule in pair.into_inner() {
-                    if let AstNode::Term(r, i) = parse_pair(rule, internal) {
-                        terms[i] = r;
+                    if let AstNode::Term(r, i) = parse_pair(rule, internal)? {
+                        // a variable may appear more than once, e.g. `x1 - x1`
+                        terms[i] += r;
                     } else {
                         unreachable!();
                     }
                 }
-                AstNode::Expression(terms)
+                Ok(AstNode::Expression(terms))
             }
             Rule::signed_term => parse_pair(pair.into_inner().next().unwrap(), internal),
             Rule::pos_signed_term => parse_pair(pair.into_inner().next().unwrap(), internal),
             Rule::neg_signed_term => {
-                let term = parse_pair::<N>(pair.into_inner().next().unwrap(), internal);
+                let term = parse_pair::<N>(pair.into_inner().next().unwrap(), internal)?;
                 if let AstNode::Term(r, i) = term {
-                    AstNode::Term(-r, i)
+                    Ok(AstNode::Term(-r, i))
                 } else {
                     unreachable!()
                 }
@@ -217,7 +290,7 @@ where
             Rule::unsigned_term => {
                 let mut inner_pairs = pair.into_inner();
                 let coeff_rule = inner_pairs.next().unwrap();
-                if let AstNode::Number(r) = parse_pair(coeff_rule, internal) {
+                if let AstNode::Number(r) = parse_pair(coeff_rule, internal)? {
                     let var = inner_pairs.next().unwrap().as_str();
                     let mut index = internal.variables.len();
                     for (i, v) in internal.variables.iter().enumerate() {
@@ -229,7 +302,7 @@ where
                     if index == internal.variables.len() {
                         panic!("Unknown identifier {}", var);
                     }
-                    AstNode::Term(r, index)
+                    Ok(AstNode::Term(r, index))
                 } else {
                     unreachable!()
                 }
@@ -238,17 +311,17 @@ where
                 let rule = pair.into_inner().next();
                 match rule {
                     Some(rule) => parse_pair(rule, internal),
-                    None => AstNode::Number(N::one()),
+                    None => Ok(AstNode::Number(N::one())),
                 }
             }
             Rule::number | Rule::pos_number | Rule::neg_number => {
-                AstNode::Number(pair.as_str().parse().unwrap())
+                Ok(AstNode::Number(parse_number_literal(pair.as_span())?))
             }
-            _ => AstNode::None,
+            _ => Ok(AstNode::None),
         }
     }
 
-    let parsed = parse_pair(lp_problem, &mut internal);
+    let parsed = parse_pair(lp_problem, &mut internal)?;
 
     match parsed {
         AstNode::Lp {
@@ -263,4 +336,163 @@ where
         }),
         _ => unreachable!(),
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rational64, Solution, Solver};
+
+    fn solve(input: &str) -> Solution<Rational64> {
+        Solver::<Rational64>::new(input).solve()
+    }
+
+    #[test]
+    fn min_objective_with_gteq_constraints() {
+        // `min f` must equal `-max -f`, with `>=` constraints flipped independently of the objective
+        let r = Rational64::from_integer;
+        let min = solve(
+            "
+            vars x1>=0, x2>=0
+            min -2x1 - 3x2
+            subject to
+                -x1 >= -4,
+                -x2 >= -5
+        ",
+        );
+        let max = solve(
+            "
+            vars x1>=0, x2>=0
+            max 2x1 + 3x2
+            subject to
+                x1 <= 4,
+                x2 <= 5
+        ",
+        );
+        assert_eq!(min, Solution::Optimal(r(-23), vec![r(4), r(5)]));
+        assert_eq!(max, Solution::Optimal(r(23), vec![r(4), r(5)]));
+        // the same problem without a bound on x2 is unbounded in both formulations
+        let min = solve("vars x1>=0, x2>=0 min -2x1 - 3x2 subject to -x1 >= -4");
+        let max = solve("vars x1>=0, x2>=0 max 2x1 + 3x2 subject to x1 <= 4");
+        assert_eq!(min, Solution::Unbounded);
+        assert_eq!(max, Solution::Unbounded);
+        // a lower bound through `>=` with a positive objective
+        let min = solve(
+            "
+            vars x1>=0, x2>=0
+            min x1 + 2x2
+            subject to
+                x1 + x2 >= 3,
+                -x1 >= -1
+        ",
+        );
+        assert_eq!(min, Solution::Optimal(r(5), vec![r(1), r(2)]));
+    }
+
+    #[test]
+    fn lp_problem_new_min_objective() {
+        // the last problem of `min_objective_with_gteq_constraints`, in `<=` form
+        let r = Rational64::from_integer;
+        let lp_problem = LpProblem::new(
+            vec!["x1".to_string(), "x2".to_string()],
+            ObjectiveType::Min,
+            vec![r(1), r(2)],
+            vec![(vec![r(-1), r(-1)], r(-3)), (vec![r(1), r(0)], r(1))],
+        );
+        let solution = Solver::from(lp_problem).solve();
+        assert_eq!(solution, Solution::Optimal(r(5), vec![r(1), r(2)]));
+    }
+
+    #[test]
+    fn unrepresentable_number_is_an_error() {
+        let input = "
+            vars x1>=0
+            max x1
+            subject to
+                x1 <= 99999999999999999999
+        ";
+        let error = match parse_lp_problem::<Rational64>(input) {
+            Err(error) => error,
+            Ok(_) => panic!("the literal overflows i64"),
+        };
+        let message = error.to_string();
+        assert!(message.contains("cannot represent number `99999999999999999999`"));
+        assert!(
+            message.contains("5:23"),
+            "error should point at the literal: {}",
+            message
+        );
+        // the same literal is fine with an arbitrary-precision number type
+        assert!(parse_lp_problem::<crate::BigRational>(input).is_ok());
+        // coefficients are checked as well
+        let input = "vars x1>=0 max 99999999999999999999x1 subject to x1 <= 1";
+        assert!(parse_lp_problem::<Rational64>(input).is_err());
+    }
+
+    #[test]
+    fn ranged_constraints() {
+        let lp_problem = parse_lp_problem::<Rational64>(
+            "
+            vars x1>=0, x2>=0
+            max x1
+            subject to
+                1 <= x1 - x2 <= 3,
+                x2 <= 2
+        ",
+        )
+        .unwrap();
+        let r = Rational64::from_integer;
+        // one statement, two rows
+        assert_eq!(
+            lp_problem.constraints,
+            vec![
+                (vec![r(-1), r(1)], r(-1)),
+                (vec![r(1), r(-1)], r(3)),
+                (vec![r(0), r(1)], r(2))
+            ]
+        );
+        // the upper bound is tight at the optimum
+        assert_eq!(
+            solve("vars x1>=0, x2>=0 max x1 subject to 1 <= x1 - x2 <= 3, x2 <= 2"),
+            Solution::Optimal(r(5), vec![r(5), r(2)])
+        );
+        // the lower bound is tight at the optimum
+        assert_eq!(
+            solve("vars x1>=0, x2>=0 max x2 subject to x1 <= 5, 1 <= x1 - x2 <= 3"),
+            Solution::Optimal(r(4), vec![r(5), r(4)])
+        );
+        // signed bounds
+        assert_eq!(
+            solve("vars x1>=0, x2>=0 min x1 subject to -2 <= x2 - x1 <= -1"),
+            Solution::Optimal(r(1), vec![r(1), r(0)])
+        );
+    }
+
+    #[test]
+    fn neg_signed_terms() {
+        let lp_problem = parse_lp_problem::<Rational64>(
+            "
+            vars x1>=0, x2>=0
+            min -x1 + x2 - 2x1
+            subject to
+                -x1 - x2 >= -3
+        ",
+        )
+        .unwrap();
+        let r = Rational64::from_integer;
+        // negated for minimization
+        assert_eq!(lp_problem.objective, vec![r(3), r(-1)]);
+        // negated for `>=`
+        assert_eq!(lp_problem.constraints, vec![(vec![r(1), r(1)], r(3))]);
+        assert_eq!(
+            solve(
+                "
+            vars x1>=0, x2>=0
+            min -x1 + x2 - 2x1
+            subject to
+                -x1 - x2 >= -3
+        "
+            ),
+            Solution::Optimal(r(-9), vec![r(3), r(0)])
+        );
+    }
+}
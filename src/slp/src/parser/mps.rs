@@ -0,0 +1,442 @@
+use super::lp_file::{Terms, Variables};
+use super::{parse_number_literal, LpProblem, ObjectiveType};
+use crate::Number;
+use pest::error::{Error, ErrorVariant};
+use pest::iterators::Pair;
+use pest::{Parser, Span};
+use std::collections::HashMap;
+
+mod mps_parser {
+    #[derive(Parser)]
+    #[grammar = "parser/mps.pest"]
+    pub struct MpsParser;
+}
+
+use mps_parser::*;
+
+#[derive(PartialEq)]
+enum RowType {
+    /// The objective row, i.e. the first `N` row.
+    Objective,
+    /// Any other `N` row, which is not a constraint.
+    Free,
+    LtEq,
+    GtEq,
+    Eq,
+}
+
+struct Row<N> {
+    row_type: RowType,
+    terms: Terms<N>,
+    rhs: N,
+    range: Option<N>,
+}
+
+fn custom_error(message: String, span: Span) -> Error<Rule> {
+    Error::new_from_span(ErrorVariant::CustomError { message }, span)
+}
+
+fn parse_number<N: Number>(pair: &Pair<Rule>) -> Result<N, Error<Rule>> {
+    parse_number_literal(pair.as_span())
+}
+
+fn abs<N: Number>(value: N) -> N {
+    if value < N::zero() {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Words of each data line in a section.
+fn section_lines(section: Pair<Rule>) -> Vec<Vec<Pair<Rule>>> {
+    section
+        .into_inner()
+        .filter(|line| line.as_rule() == Rule::line)
+        .map(|line| line.into_inner().collect())
+        .collect()
+}
+
+/// Lines in the COLUMNS, RHS and RANGES sections have one or two (row, value) entries.
+fn check_entries(words: &[Pair<Rule>], leading: usize, span: Span) -> Result<(), Error<Rule>> {
+    let entries = words.len() - leading;
+    if entries == 2 || entries == 4 {
+        Ok(())
+    } else {
+        Err(custom_error(
+            "expecting one or two (row, value) entries".to_string(),
+            span,
+        ))
+    }
+}
+
+/// Parses LP Problem from the free MPS (Mathematical Programming System) format.
+///
+/// The supported sections are `NAME`, `OBJSENSE`, `ROWS`, `COLUMNS`, `RHS`, `RANGES`
+/// and `BOUNDS`, terminated by `ENDATA`. As in the MPS convention, the objective is
+/// the first `N` row and is minimized unless `OBJSENSE` says otherwise; integer
+/// variables are marked by `'MARKER'` lines in `COLUMNS` or by integer bounds.
+/// Since all variables of [`LpProblem`] are non-negative, negative lower bounds
+/// and free variables are rejected; other bounds are converted into constraints.
+pub fn parse_mps<N>(input: &str) -> Result<LpProblem<N>, Error<Rule>>
+where
+    N: Number,
+    N::Err: std::fmt::Debug,
+{
+    let mps = MpsParser::parse(Rule::mps, input)?.next().unwrap();
+
+    let mut variables = Variables::<N>::new();
+    let mut objective_type = ObjectiveType::Min;
+    let mut objective = vec![];
+    let mut rows: Vec<Row<N>> = vec![];
+    let mut row_indices = HashMap::new();
+
+    let find_row = |row_indices: &HashMap<String, usize>, word: &Pair<Rule>| {
+        row_indices
+            .get(word.as_str())
+            .cloned()
+            .ok_or_else(|| custom_error(format!("unknown row `{}`", word.as_str()), word.as_span()))
+    };
+
+    for section in mps.into_inner() {
+        let section_span = section.as_span();
+        match section.as_rule() {
+            Rule::name | Rule::EOI => {}
+            Rule::objsense => {
+                let mut words = vec![];
+                for rule in section.into_inner() {
+                    match rule.as_rule() {
+                        Rule::word => words.push(rule),
+                        Rule::line => words.extend(rule.into_inner()),
+                        _ => unreachable!(),
+                    }
+                }
+                objective_type = match words.iter().map(|w| w.as_str()).collect::<Vec<_>>()[..] {
+                    ["MAX"] | ["MAXIMIZE"] => ObjectiveType::Max,
+                    ["MIN"] | ["MINIMIZE"] => ObjectiveType::Min,
+                    _ => {
+                        return Err(custom_error(
+                            "expecting objective sense MAX or MIN".to_string(),
+                            section_span,
+                        ))
+                    }
+                };
+            }
+            Rule::rows => {
+                for words in section_lines(section) {
+                    if words.len() != 2 {
+                        return Err(custom_error(
+                            "expecting row type and row name".to_string(),
+                            words[0].as_span(),
+                        ));
+                    }
+                    let row_type = match words[0].as_str() {
+                        "N" if !rows.iter().any(|row| row.row_type == RowType::Objective) => {
+                            RowType::Objective
+                        }
+                        "N" => RowType::Free,
+                        "L" => RowType::LtEq,
+                        "G" => RowType::GtEq,
+                        "E" => RowType::Eq,
+                        _ => {
+                            return Err(custom_error(
+                                format!("unknown row type `{}`", words[0].as_str()),
+                                words[0].as_span(),
+                            ))
+                        }
+                    };
+                    row_indices.insert(words[1].as_str().to_string(), rows.len());
+                    rows.push(Row {
+                        row_type,
+                        terms: vec![],
+                        rhs: N::zero(),
+                        range: None,
+                    });
+                }
+            }
+            Rule::columns => {
+                let mut is_int = false;
+                for words in section_lines(section) {
+                    let span = words[0].as_span();
+                    if words.len() == 3 && words[1].as_str() == "'MARKER'" {
+                        is_int = match words[2].as_str() {
+                            "'INTORG'" => true,
+                            "'INTEND'" => false,
+                            _ => {
+                                return Err(custom_error(
+                                    format!("unknown marker `{}`", words[2].as_str()),
+                                    words[2].as_span(),
+                                ))
+                            }
+                        };
+                        continue;
+                    }
+                    check_entries(&words, 1, span)?;
+                    let index = variables.index_of(words[0].as_str());
+                    variables.is_int_constraints[index] |= is_int;
+                    for entry in words[1..].chunks(2) {
+                        let row = &mut rows[find_row(&row_indices, &entry[0])?];
+                        let value = parse_number::<N>(&entry[1])?;
+                        match row.row_type {
+                            RowType::Objective => objective.push((index, value)),
+                            RowType::Free => {}
+                            _ => row.terms.push((index, value)),
+                        }
+                    }
+                }
+            }
+            Rule::rhs | Rule::ranges => {
+                let is_rhs = section.as_rule() == Rule::rhs;
+                for words in section_lines(section) {
+                    // the name of the RHS or RANGES vector is optional
+                    let leading = words.len() % 2;
+                    check_entries(&words, leading, words[0].as_span())?;
+                    for entry in words[leading..].chunks(2) {
+                        let row = &mut rows[find_row(&row_indices, &entry[0])?];
+                        let value = parse_number::<N>(&entry[1])?;
+                        match (&row.row_type, is_rhs) {
+                            (RowType::Objective, _) => {
+                                return Err(custom_error(
+                                    "objective constant is not supported".to_string(),
+                                    entry[0].as_span(),
+                                ))
+                            }
+                            (RowType::Free, _) => {}
+                            (_, true) => row.rhs = value,
+                            (_, false) => row.range = Some(value),
+                        }
+                    }
+                }
+            }
+            Rule::bounds => {
+                for words in section_lines(section) {
+                    let bound_type = words[0].as_str();
+                    let has_value = matches!(bound_type, "UP" | "LO" | "FX" | "LI" | "UI");
+                    // the name of the BOUNDS vector is optional
+                    let expected_len = if has_value { 3 } else { 2 };
+                    let column = if words.len() == expected_len {
+                        &words[1]
+                    } else if words.len() == expected_len + 1 {
+                        &words[2]
+                    } else {
+                        return Err(custom_error(
+                            "wrong number of fields in bound".to_string(),
+                            words[0].as_span(),
+                        ));
+                    };
+                    let span = column.as_span();
+                    let index = variables.index_of(column.as_str());
+                    let value = if has_value {
+                        parse_number::<N>(words.last().unwrap())?
+                    } else {
+                        N::zero()
+                    };
+                    let negative_error = || {
+                        custom_error(
+                            format!(
+                                "variable `{}` must be non-negative, negative bounds are not supported",
+                                column.as_str()
+                            ),
+                            span,
+                        )
+                    };
+                    match bound_type {
+                        "LO" | "LI" | "FX" if value < N::zero() => return Err(negative_error()),
+                        "UP" | "UI" if value < N::zero() => return Err(negative_error()),
+                        "FR" | "MI" => return Err(negative_error()),
+                        _ => {}
+                    }
+                    match bound_type {
+                        "LO" | "LI" => variables.lower_bounds[index] = Some(value),
+                        "UP" | "UI" => variables.upper_bounds[index] = Some(value),
+                        "FX" => {
+                            variables.lower_bounds[index] = Some(value.clone());
+                            variables.upper_bounds[index] = Some(value);
+                        }
+                        "PL" => variables.upper_bounds[index] = None,
+                        "BV" => {
+                            variables.lower_bounds[index] = None;
+                            variables.upper_bounds[index] = Some(N::one());
+                        }
+                        _ => {
+                            return Err(custom_error(
+                                format!("unknown bound type `{}`", bound_type),
+                                words[0].as_span(),
+                            ))
+                        }
+                    }
+                    if matches!(bound_type, "LI" | "UI" | "BV") {
+                        variables.is_int_constraints[index] = true;
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let mut dense_objective = variables.dense(&objective);
+    if objective_type == ObjectiveType::Min {
+        for v in dense_objective.iter_mut() {
+            *v = -v.clone();
+        }
+    }
+    let mut constraints = vec![];
+    for row in rows.into_iter() {
+        let rhs = row.rhs;
+        let (lower, upper) = match (row.row_type, row.range) {
+            (RowType::Objective, _) | (RowType::Free, _) => continue,
+            (RowType::LtEq, range) => (range.map(|r| rhs.clone() - abs(r)), Some(rhs)),
+            (RowType::GtEq, range) => (Some(rhs.clone()), range.map(|r| rhs + abs(r))),
+            (RowType::Eq, None) => (Some(rhs.clone()), Some(rhs)),
+            (RowType::Eq, Some(r)) if r < N::zero() => (Some(rhs.clone() + r), Some(rhs)),
+            (RowType::Eq, Some(r)) => (Some(rhs.clone()), Some(rhs + r)),
+        };
+        let exp = variables.dense(&row.terms);
+        if let Some(upper) = upper {
+            constraints.push((exp.clone(), upper));
+        }
+        if let Some(lower) = lower {
+            constraints.push((exp.into_iter().map(|v| -v).collect(), -lower));
+        }
+    }
+    constraints.extend(variables.bound_constraints());
+
+    Ok(LpProblem {
+        vars_list: variables.names,
+        is_int_constraints: variables.is_int_constraints,
+        constraints,
+        objective: dense_objective,
+        objective_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rational64, Solution, Solver};
+
+    fn rational(numer: i64, denom: i64) -> Rational64 {
+        Rational64::new(numer, denom)
+    }
+
+    #[test]
+    fn mps_matches_dsl() {
+        let mps = "
+* the example in the README
+NAME          README
+OBJSENSE
+    MAX
+ROWS
+ N  obj
+ L  c1
+ L  c2
+ L  c3
+COLUMNS
+    x1        obj       2              c1        2
+    x1        c2        6              c3        2
+    x2        obj       3              c1        1
+    x2        c2        5              c3        5
+RHS
+    RHS1      c1        18             c2        60
+    RHS1      c3        40
+ENDATA
+";
+        let dsl = "
+            vars x1>=0, x2>=0
+            max 2x1+3x2
+            subject to
+                2x1 +  x2 <= 18,
+                6x1 + 5x2 <= 60,
+                2x1 + 5x2 <= 40
+        ";
+        let problem = parse_mps::<Rational64>(mps).unwrap();
+        assert_eq!(problem.vars_list, ["x1", "x2"]);
+        let solution = Solver::from(problem).solve();
+        assert_eq!(solution, Solver::<Rational64>::new(dsl).solve());
+        assert_eq!(
+            solution,
+            Solution::Optimal(rational(28, 1), vec![rational(5, 1), rational(6, 1)])
+        );
+    }
+
+    #[test]
+    fn mps_minimize_with_ranges_and_bounds() {
+        let mps = "
+NAME
+ROWS
+ N  cost
+ G  lim1
+ L  lim2
+COLUMNS
+    MARKER                 'MARKER'                 'INTORG'
+    y         cost      1              lim1      2
+    y         lim2      -1
+    MARKER                 'MARKER'                 'INTEND'
+    x         cost      2              lim1      1
+    x         lim2      1
+RHS
+    lim1      3              lim2      2
+RANGES
+    RNG       lim2      2
+BOUNDS
+ LO BND       x         1
+ UP BND       y         5
+ENDATA
+";
+        let problem = parse_mps::<Rational64>(mps).unwrap();
+        assert_eq!(problem.vars_list, ["y", "x"]);
+        assert_eq!(problem.is_int_constraints, [true, false]);
+        let dsl = "
+            vars y(Z)>=0, x>=0
+            min y + 2x
+            subject to
+                2y + x >= 3,
+                -y + x <= 2,
+                -y + x >= 0,
+                x >= 1,
+                y <= 5
+        ";
+        let solution = Solver::from(problem).solve();
+        assert_eq!(solution, Solver::<Rational64>::new(dsl).solve());
+        assert_eq!(
+            solution,
+            Solution::Optimal(rational(3, 1), vec![rational(1, 1), rational(1, 1)])
+        );
+    }
+
+    #[test]
+    fn mps_rejects_free_variables() {
+        let mps = "
+ROWS
+ N  obj
+ L  c1
+COLUMNS
+    x         obj       1              c1        1
+RHS
+    c1        1
+BOUNDS
+ FR BND       x
+ENDATA
+";
+        assert!(parse_mps::<f64>(mps).is_err());
+    }
+
+    #[test]
+    fn mps_rejects_unrepresentable_numbers() {
+        let mps = "
+ROWS
+ N  obj
+ L  c1
+COLUMNS
+    x         obj       1              c1        1
+RHS
+    c1        99999999999999999999
+ENDATA
+";
+        let error = parse_mps::<Rational64>(mps).err().unwrap();
+        assert!(error
+            .to_string()
+            .contains("cannot represent number `99999999999999999999`"));
+        assert!(parse_mps::<crate::BigRational>(mps).is_ok());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::lp::*;
 use crate::parser::{LpProblem, ObjectiveType};
-use crate::{Number, Solution, SolverOptions, SolverSettings};
+use crate::{Column, DualSolution, Number, Solution, SolverOptions, SolverSettings};
 
 /// Linear Programming Solver.
 pub struct Solver<N> {
@@ -8,6 +8,12 @@ pub struct Solver<N> {
     options: SolverOptions,
     is_int_constraints: Vec<bool>,
     negate_objective: bool,
+    n_iterations: usize,
+    /// The objective (as maximized) and the right hand sides of the original problem, kept to
+    /// evaluate the duality gap independently of the tableau.
+    objective: Vec<N>,
+    rhs: Vec<N>,
+    optimality_gap: Option<N>,
 }
 
 impl<N: Number> Solver<N> {
@@ -25,11 +31,20 @@ impl<N: Number> Solver<N> {
         is_int_constraints: Vec<bool>,
         negate_objective: bool,
     ) -> Self {
+        let objective = lp.tableau[0][1..=lp.n_vars]
+            .iter()
+            .map(|v| -v.clone())
+            .collect();
+        let rhs = lp.tableau[1..].iter().map(|row| row[0].clone()).collect();
         Solver {
             lp,
             options: SolverOptions { parallel: false },
             is_int_constraints,
             negate_objective,
+            n_iterations: 0,
+            objective,
+            rhs,
+            optimality_gap: None,
         }
     }
 
@@ -46,10 +61,21 @@ impl<N: Number> Solver<N> {
     ///
     /// Returns [a solution](enum.Solution.html).
     pub fn solve(&mut self) -> Solution<N> {
-        match self.lp.solve(self.options.parallel) {
+        let previous_iterations = self.lp.n_iterations;
+        let solution = self.lp.solve(self.options.parallel);
+        self.n_iterations = self.lp.n_iterations - previous_iterations;
+        self.optimality_gap = None;
+        match solution {
             Solution::Infeasible => Solution::Infeasible,
             Solution::Unbounded => Solution::Unbounded,
             Solution::Optimal(opt, model) => {
+                let is_integral = model
+                    .iter()
+                    .zip(&self.is_int_constraints)
+                    .all(|(v, &is_int)| !is_int || v.is_integer());
+                if is_integral {
+                    self.optimality_gap = Some(self.duality_gap(&model));
+                }
                 let solution = Self::branch_and_bound(
                     &self.lp,
                     self.options.parallel,
@@ -57,6 +83,7 @@ impl<N: Number> Solver<N> {
                     model,
                     &self.is_int_constraints,
                     None,
+                    &mut self.n_iterations,
                 );
                 if let Solution::Optimal(opt, model) = solution {
                     if self.negate_objective {
@@ -71,6 +98,270 @@ impl<N: Number> Solver<N> {
         }
     }
 
+    /// Solves the constraints of this problem against several objectives in lexicographic order.
+    ///
+    /// The first objective is optimized, then its optimum is fixed as a constraint while the
+    /// next one is optimized, and so on; the objective of the original problem is ignored.
+    /// Must be called before [`solve`](Self::solve), which modifies the underlying tableau.
+    ///
+    /// Returns the solution of the last objective, or the first non-optimal solution.
+    pub fn solve_lexicographic(&mut self, objectives: &[(ObjectiveType, Vec<N>)]) -> Solution<N> {
+        assert!(
+            self.lp.n_iterations == 0,
+            "solve_lexicographic must be called on a solver that has not been solved"
+        );
+        let n_vars = self.lp.n_vars;
+        let mut constraints: Vec<(Vec<N>, N)> = self.lp.tableau[1..]
+            .iter()
+            .map(|row| (row[1..=n_vars].to_vec(), row[0].clone()))
+            .collect();
+        let mut solution = Solution::Infeasible;
+        self.n_iterations = 0;
+        for (objective_type, objective) in objectives {
+            assert_eq!(
+                objective.len(),
+                n_vars,
+                "objective must have a coefficient for every variable"
+            );
+            let mut objective = objective.clone();
+            if *objective_type == ObjectiveType::Min {
+                for v in objective.iter_mut() {
+                    *v = -v.clone();
+                }
+            }
+            let mut solver: Solver<N> = LpProblem {
+                vars_list: vec![String::new(); n_vars],
+                is_int_constraints: self.is_int_constraints.clone(),
+                constraints: constraints.clone(),
+                objective: objective.clone(),
+                objective_type: if *objective_type == ObjectiveType::Min {
+                    ObjectiveType::Min
+                } else {
+                    ObjectiveType::Max
+                },
+            }
+            .into();
+            solver.options.parallel = self.options.parallel;
+            solution = solver.solve();
+            self.n_iterations += solver.n_iterations;
+            match &solution {
+                Solution::Optimal(opt, _) => {
+                    // objective (as maximized) >= optimum
+                    let opt = if *objective_type == ObjectiveType::Min {
+                        -opt.clone()
+                    } else {
+                        opt.clone()
+                    };
+                    constraints.push((objective.into_iter().map(|v| -v).collect(), -opt));
+                }
+                _ => break,
+            }
+        }
+        solution
+    }
+
+    /// Solves the LP by column generation, taking this problem as the initial restricted problem.
+    ///
+    /// After each solve, `pricing` is called with the optimal solution and the dual values of
+    /// the constraints; the column it returns is added as a new variable and the LP is
+    /// re-solved from the current basis, until `pricing` returns `None`. The model of the
+    /// returned solution has one value per original variable followed by one per added column.
+    ///
+    /// Integer constraints are not supported.
+    pub fn solve_with_pricing(
+        &mut self,
+        mut pricing: impl FnMut(&DualSolution<N>) -> Option<Column<N>>,
+    ) -> Solution<N> {
+        assert!(
+            self.is_int_constraints.iter().all(|&is_int| !is_int),
+            "column generation does not support integer variables"
+        );
+        self.optimality_gap = None;
+        let mut solution = self.lp.solve(self.options.parallel);
+        loop {
+            self.n_iterations = self.lp.n_iterations;
+            let (opt, model) = match solution {
+                Solution::Optimal(opt, model) => (opt, model),
+                _ => return solution,
+            };
+            let negate_objective = self.negate_objective;
+            let sign = |v: N| if negate_objective { -v } else { v };
+            let slack_start = self.lp.n_vars + 1;
+            let dual_solution = DualSolution {
+                objective: sign(opt),
+                duals: self.lp.tableau[0][slack_start..slack_start + self.lp.n_constraints]
+                    .iter()
+                    .map(|v| sign(v.clone()))
+                    .collect(),
+                model,
+            };
+            match pricing(&dual_solution) {
+                Some(Column {
+                    objective,
+                    coefficients,
+                }) => {
+                    let objective = sign(objective);
+                    self.objective.push(objective.clone());
+                    self.is_int_constraints.push(false);
+                    self.lp.add_column(objective, coefficients);
+                    solution = self.lp.simplex(self.options.parallel);
+                }
+                None => {
+                    self.optimality_gap = Some(self.duality_gap(&dual_solution.model));
+                    return Solution::Optimal(dual_solution.objective, dual_solution.model);
+                }
+            }
+        }
+    }
+
+    /// Solves the LP and enumerates the vertices of its optimal face, i.e. its alternative
+    /// optima, exploring at most `limit` optimal bases.
+    ///
+    /// Starting from the optimal basis, every nonbasic column with a zero reduced cost is
+    /// pivoted in, which keeps the objective value; the optimal bases are explored breadth
+    /// first and each basic solution not seen before is recorded. Each basis gives a single
+    /// model, so at most `limit` distinct models are returned, but on a degenerate optimum many
+    /// bases share the same vertex and fewer models may be returned even if more exist.
+    /// Returns an empty vector if the LP is infeasible or unbounded.
+    ///
+    /// Integer constraints are not supported.
+    pub fn enumerate_optimal_vertices(&mut self, limit: usize) -> Vec<Vec<N>> {
+        assert!(
+            self.is_int_constraints.iter().all(|&is_int| !is_int),
+            "enumerating optimal vertices does not support integer variables"
+        );
+        self.optimality_gap = None;
+        let previous_iterations = self.lp.n_iterations;
+        let solution = self.lp.solve(self.options.parallel);
+        self.n_iterations = self.lp.n_iterations - previous_iterations;
+        let model = match solution {
+            Solution::Optimal(_, model) => model,
+            _ => return vec![],
+        };
+        self.optimality_gap = Some(self.duality_gap(&model));
+        let tot_col = self.lp.tableau[0].len();
+        let mut vertices: Vec<Vec<N>> = vec![];
+        let mut visited_bases = std::collections::BTreeSet::new();
+        let mut queue = std::collections::VecDeque::new();
+        visited_bases.insert(Self::sorted_basis(&self.lp));
+        queue.push_back((self.lp.tableau.clone(), self.lp.basic_indices.clone()));
+        let mut n_explored = 0;
+        while let Some((tableau, basic_indices)) = queue.pop_front() {
+            if n_explored >= limit {
+                break;
+            }
+            n_explored += 1;
+            let model = Self::basic_solution(&tableau, &basic_indices, self.lp.n_vars);
+            if !vertices.contains(&model) {
+                vertices.push(model);
+            }
+            for entering_var in 1..tot_col {
+                if tableau[0][entering_var] != N::zero() || basic_indices.contains(&entering_var) {
+                    continue;
+                }
+                // every row reaching the minimum ratio may leave, which matters on degenerate vertices
+                let mut min_ratio = None;
+                let mut leaving_vars = vec![];
+                for (i, row) in tableau.iter().enumerate().skip(1) {
+                    if row[entering_var] <= N::zero() {
+                        continue;
+                    }
+                    let ratio = row[0].clone() / row[entering_var].clone();
+                    match &min_ratio {
+                        Some(min_ratio) if ratio > *min_ratio => continue,
+                        Some(min_ratio) if ratio == *min_ratio => {}
+                        _ => {
+                            min_ratio = Some(ratio);
+                            leaving_vars.clear();
+                        }
+                    }
+                    leaving_vars.push(i);
+                }
+                for leaving_var in leaving_vars {
+                    let mut next = LP {
+                        n_constraints: self.lp.n_constraints,
+                        n_vars: self.lp.n_vars,
+                        tableau: tableau.clone(),
+                        basic_indices: basic_indices.clone(),
+                        n_iterations: 0,
+                    };
+                    LP::pivot(
+                        &mut next.tableau,
+                        entering_var,
+                        leaving_var,
+                        self.options.parallel,
+                    );
+                    next.basic_indices[leaving_var] = entering_var;
+                    self.n_iterations += 1;
+                    if visited_bases.insert(Self::sorted_basis(&next)) {
+                        queue.push_back((next.tableau, next.basic_indices));
+                    }
+                }
+            }
+        }
+        vertices
+    }
+
+    /// Replaces the right hand sides of the constraints without re-parsing the problem, so that
+    /// the next call to [`solve`](Self::solve) is warm-started from the current basis.
+    ///
+    /// The right hand sides are given as stored, i.e. with one value per row in `<=` form: a
+    /// `>=` constraint takes its negated right hand side, and a ranged constraint takes two rows.
+    pub fn update_rhs(&mut self, new_rhs: &[N]) {
+        self.lp.update_rhs(new_rhs);
+        self.rhs = new_rhs.to_vec();
+    }
+
+    /// Returns the number of simplex iterations (pivots) performed by the last call to
+    /// [`solve`](Self::solve), including the auxiliary problem and branch and bound.
+    pub fn num_iterations(&self) -> usize {
+        self.n_iterations
+    }
+
+    /// Returns the gap between the dual and the primal objective of the last call to
+    /// [`solve`](Self::solve), as a numeric certificate of optimality: it is zero for exact
+    /// number types and within rounding errors of zero for floats.
+    ///
+    /// The primal objective is evaluated at the returned model and the dual objective at the
+    /// dual values read from the slack columns of the final tableau, both against the original
+    /// problem. Returns `None` if the solution is not optimal or if it comes from branch and
+    /// bound, whose optimality is not certified by a single tableau.
+    pub fn optimality_gap(&self) -> Option<N> {
+        self.optimality_gap.clone()
+    }
+
+    /// Dual objective minus primal objective of the maximized problem.
+    fn duality_gap(&self, model: &[N]) -> N {
+        let objective_row = &self.lp.tableau[0];
+        let mut gap = N::zero();
+        for (i, b) in self.rhs.iter().enumerate() {
+            gap += objective_row[self.lp.n_vars + 1 + i].clone() * b.clone();
+        }
+        for (c, x) in self.objective.iter().zip(model) {
+            gap -= c.clone() * x.clone();
+        }
+        gap
+    }
+
+    /// Values of the original variables at the basic solution of the given tableau.
+    fn basic_solution(tableau: &[Vec<N>], basic_indices: &[usize], n_vars: usize) -> Vec<N> {
+        (1..=n_vars)
+            .map(
+                |i| match basic_indices.iter().skip(1).position(|&v| v == i) {
+                    Some(row) => tableau[row + 1][0].clone(),
+                    None => N::zero(),
+                },
+            )
+            .collect()
+    }
+
+    /// Basic variables of the constraint rows, sorted to identify the basis.
+    fn sorted_basis(lp: &LP<N>) -> Vec<usize> {
+        let mut basis = lp.basic_indices[1..].to_vec();
+        basis.sort_unstable();
+        basis
+    }
+
     fn branch_and_bound(
         lp: &LP<N>,
         parallel: bool,
@@ -78,6 +369,7 @@ impl<N: Number> Solver<N> {
         model: Vec<N>,
         is_int_constraints: &[bool],
         mut known_opt: Option<N>,
+        n_iterations: &mut usize,
     ) -> Solution<N> {
         let mut non_int_index = 0;
         for (i, v) in model.iter().enumerate() {
@@ -120,9 +412,11 @@ impl<N: Number> Solver<N> {
             n_vars: lp.n_vars,
             tableau,
             basic_indices,
+            n_iterations: 0,
         };
 
         let sol1 = new_lp.dual_simplex(parallel);
+        *n_iterations += new_lp.n_iterations;
         let sol1_int = match sol1 {
             Solution::Infeasible => Solution::Infeasible,
             Solution::Unbounded => Solution::Unbounded,
@@ -133,6 +427,7 @@ impl<N: Number> Solver<N> {
                 model,
                 is_int_constraints,
                 known_opt.clone(),
+                n_iterations,
             ),
         };
 
@@ -165,14 +460,22 @@ impl<N: Number> Solver<N> {
             n_vars: lp.n_vars,
             tableau,
             basic_indices,
+            n_iterations: 0,
         };
         let sol2 = new_lp.dual_simplex(parallel);
+        *n_iterations += new_lp.n_iterations;
         let sol2_int = match sol2 {
             Solution::Infeasible => Solution::Infeasible,
             Solution::Unbounded => Solution::Unbounded,
-            Solution::Optimal(opt, model) => {
-                Self::branch_and_bound(&new_lp, parallel, opt, model, is_int_constraints, known_opt)
-            }
+            Solution::Optimal(opt, model) => Self::branch_and_bound(
+                &new_lp,
+                parallel,
+                opt,
+                model,
+                is_int_constraints,
+                known_opt,
+                n_iterations,
+            ),
         };
 
         match (sol1_int, sol2_int) {
@@ -192,40 +495,317 @@ impl<N: Number> Solver<N> {
 }
 
 impl<N: Number> From<LpProblem<N>> for Solver<N> {
-    fn from(mut lp_problem: LpProblem<N>) -> Self {
-        let mut tableau = vec![];
-        let mut basic_indices = vec![0];
-        let n_constraints = lp_problem.constraints.len();
-        let n_vars = lp_problem.vars_list.len();
-        let mut obj = lp_problem.objective;
-        for i in obj.iter_mut() {
-            *i = -i.clone();
-        }
-        obj.insert(0, N::zero());
-        for _ in 0..n_constraints {
-            obj.push(N::zero());
-        }
-        tableau.push(obj);
-        for (i, constr) in lp_problem.constraints.iter_mut().enumerate() {
-            constr.0.insert(0, constr.1.clone());
-            for j in 0..n_constraints {
-                constr.0.push(if i == j { N::one() } else { N::zero() });
-            }
-            // TODO Remove clone
-            tableau.push(constr.0.clone());
-            basic_indices.push(n_vars + i + 1);
-        }
-
-        let lp = LP {
-            n_constraints,
-            n_vars,
-            basic_indices,
-            tableau,
-        };
+    fn from(lp_problem: LpProblem<N>) -> Self {
+        debug_assert_eq!(lp_problem.objective.len(), lp_problem.vars_list.len());
+        let lp = LP::new(lp_problem.objective, lp_problem.constraints);
         Solver::new_with_int_constraints(
             lp,
             lp_problem.is_int_constraints,
             lp_problem.objective_type == ObjectiveType::Min,
         )
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rational64;
+
+    #[test]
+    fn solver_num_iterations() {
+        let mut solver = Solver::<Rational64>::new(
+            "
+            vars x1>=0, x2>=0
+            max 2x1+3x2
+            subject to
+                2x1 +  x2 <= 18,
+                6x1 + 5x2 <= 60,
+                2x1 + 5x2 <= 40
+        ",
+        );
+        assert_eq!(solver.num_iterations(), 0);
+        solver.solve();
+        assert_eq!(solver.num_iterations(), 2);
+        // already optimal at the origin
+        let mut solver = Solver::<Rational64>::new(
+            "
+            vars x1>=0, x2>=0
+            min x1+x2
+            subject to
+                x1 + x2 <= 1
+        ",
+        );
+        solver.solve();
+        assert_eq!(solver.num_iterations(), 0);
+    }
+
+    #[test]
+    fn solver_optimality_gap() {
+        let input = "
+            vars x1>=0, x2>=0
+            max 2x1+3x2
+            subject to
+                2x1 +  x2 <= 18,
+                6x1 + 5x2 <= 60,
+                2x1 + 5x2 <= 40
+        ";
+        let mut solver = Solver::<Rational64>::new(input);
+        assert_eq!(solver.optimality_gap(), None);
+        solver.solve();
+        assert_eq!(solver.optimality_gap(), Some(Rational64::from_integer(0)));
+        let mut solver = Solver::<f64>::new(input);
+        solver.solve();
+        assert!(solver.optimality_gap().unwrap().abs() < 1e-9);
+        // a minimization going through phase one
+        let mut solver = Solver::<f64>::new(
+            "
+            vars x1>=0, x2>=0
+            min 3x1+2x2
+            subject to
+                x1 + x2 >= 2.5,
+                x1 - x2 <= 1
+        ",
+        );
+        assert_eq!(solver.solve(), Solution::Optimal(5., vec![0., 2.5]));
+        assert!(solver.optimality_gap().unwrap().abs() < 1e-9);
+        let mut solver = Solver::<f64>::new(
+            "
+            vars x1>=0
+            max x1
+            subject to
+                x1 >= 1
+        ",
+        );
+        assert_eq!(solver.solve(), Solution::Unbounded);
+        assert_eq!(solver.optimality_gap(), None);
+    }
+
+    #[test]
+    fn solver_update_rhs() {
+        let problem = |x1_lower_bound: i64| {
+            format!(
+                "
+                vars x1>=0, x2>=0
+                max 2x1+3x2
+                subject to
+                    2x1 +  x2 <= 18,
+                    6x1 + 5x2 <= 60,
+                    2x1 + 5x2 <= 40,
+                    x1 >= {}
+                ",
+                x1_lower_bound
+            )
+        };
+        let r = Rational64::from_integer;
+        let mut solver = Solver::<Rational64>::new(&problem(1));
+        assert_eq!(solver.solve(), Solution::Optimal(r(28), vec![r(5), r(6)]));
+        for x1_lower_bound in [6, 20, 0] {
+            solver.update_rhs(&[r(18), r(60), r(40), r(-x1_lower_bound)]);
+            let solution = solver.solve();
+            let expected = Solver::<Rational64>::new(&problem(x1_lower_bound)).solve();
+            assert_eq!(solution, expected, "x1 >= {}", x1_lower_bound);
+            if let Solution::Optimal(..) = solution {
+                assert_eq!(solver.optimality_gap(), Some(r(0)));
+            }
+        }
+        // the last solve only needed to move away from the infeasible basis
+        assert!(solver.num_iterations() > 0);
+    }
+
+    #[test]
+    fn solver_column_generation() {
+        let r = Rational64::from_integer;
+        // the README LP, starting from x1 only
+        let mut solver = Solver::<Rational64>::new(
+            "
+            vars x1>=0
+            max 2x1
+            subject to
+                2x1 <= 18,
+                6x1 <= 60,
+                2x1 <= 40
+        ",
+        );
+        let pool = [
+            Column {
+                objective: r(1),
+                coefficients: vec![r(2), r(2), r(2)],
+            },
+            Column {
+                objective: r(3),
+                coefficients: vec![r(1), r(5), r(5)],
+            },
+        ];
+        let mut n_pricing = 0;
+        let solution = solver.solve_with_pricing(|dual_solution| {
+            n_pricing += 1;
+            let reduced_cost = |column: &Column<Rational64>| {
+                column.objective
+                    - (column.coefficients.iter())
+                        .zip(&dual_solution.duals)
+                        .map(|(a, y)| a * y)
+                        .sum::<Rational64>()
+            };
+            let best = pool.iter().max_by_key(|column| reduced_cost(column))?;
+            if reduced_cost(best) > r(0) {
+                Some(best.clone())
+            } else {
+                None
+            }
+        });
+        assert_eq!(solution, Solution::Optimal(r(28), vec![r(5), r(6)]));
+        assert_eq!(n_pricing, 2);
+        assert_eq!(solver.optimality_gap(), Some(r(0)));
+        // a minimization: min 3x1 + x2 subject to x1 + x2 >= 2, adding x2 once
+        let mut solver = Solver::<f64>::new(
+            "
+            vars x1>=0
+            min 3x1
+            subject to
+                x1 >= 2
+        ",
+        );
+        let mut duals = vec![];
+        let solution = solver.solve_with_pricing(|dual_solution| {
+            duals.push(dual_solution.duals.clone());
+            (duals.len() == 1).then(|| Column {
+                objective: 1.,
+                coefficients: vec![-1.],
+            })
+        });
+        assert_eq!(solution, Solution::Optimal(2., vec![0., 2.]));
+        assert_eq!(duals, vec![vec![-3.], vec![-1.]]);
+    }
+
+    #[test]
+    fn solver_lexicographic_tie_break() {
+        // every point on the segment x1 + x2 = 4, 1 <= x1 <= 3 maximizes the primary objective
+        let input = "
+            vars x1>=0, x2>=0
+            max x1+x2
+            subject to
+                x1 + x2 <= 4,
+                x1 <= 3,
+                x2 <= 3
+        ";
+        let r = Rational64::from_integer;
+        let mut solver = Solver::<Rational64>::new(input);
+        let solution = solver.solve_lexicographic(&[
+            (ObjectiveType::Max, vec![r(1), r(1)]),
+            (ObjectiveType::Max, vec![r(1), r(0)]),
+        ]);
+        assert_eq!(solution, Solution::Optimal(r(3), vec![r(3), r(1)]));
+        let mut solver = Solver::<Rational64>::new(input);
+        let solution = solver.solve_lexicographic(&[
+            (ObjectiveType::Max, vec![r(1), r(1)]),
+            (ObjectiveType::Min, vec![r(1), r(0)]),
+        ]);
+        assert_eq!(solution, Solution::Optimal(r(1), vec![r(1), r(3)]));
+        // the primary objective takes precedence over the secondary
+        let mut solver = Solver::<Rational64>::new(input);
+        let solution = solver.solve_lexicographic(&[
+            (ObjectiveType::Min, vec![r(1), r(1)]),
+            (ObjectiveType::Max, vec![r(1), r(0)]),
+        ]);
+        assert_eq!(solution, Solution::Optimal(r(0), vec![r(0), r(0)]));
+    }
+
+    #[test]
+    fn solver_data_parallelism_same_optimum() {
+        // large enough to exceed the parallel threshold
+        let n_vars = 60;
+        let n_constraints = 20;
+        let vars: Vec<String> = (0..n_vars).map(|i| format!("x{}>=0", i)).collect();
+        let objective: Vec<String> = (0..n_vars)
+            .map(|i| format!("{}x{}", 1 + (i * 7) % 11, i))
+            .collect();
+        let constraints: Vec<String> = (0..n_constraints)
+            .map(|j| {
+                let lhs: Vec<String> = (0..n_vars)
+                    .map(|i| format!("{}x{}", 1 + (i * 3 + j * 5) % 13, i))
+                    .collect();
+                format!("{} <= {}", lhs.join(" + "), 100 + 10 * j)
+            })
+            .collect();
+        let input = format!(
+            "vars {}\nmax {}\nsubject to\n{}",
+            vars.join(", "),
+            objective.join(" + "),
+            constraints.join(",\n")
+        );
+        let mut serial = Solver::<f64>::new(&input);
+        let mut parallel = Solver::<f64>::new(&input);
+        parallel.setting(SolverSettings::EnableDataParallelism);
+        let solution = serial.solve();
+        assert!(matches!(solution, Solution::Optimal(..)));
+        assert_eq!(solution, parallel.solve());
+        assert_eq!(serial.num_iterations(), parallel.num_iterations());
+    }
+
+    #[test]
+    fn solver_enumerate_optimal_vertices() {
+        // every point on the segment between (3, 1) and (1, 3) is optimal
+        let input = "
+            vars x1>=0, x2>=0
+            max x1+x2
+            subject to
+                x1 + x2 <= 4,
+                x1 <= 3,
+                x2 <= 3
+        ";
+        let r = Rational64::from_integer;
+        let mut solver = Solver::<Rational64>::new(input);
+        let mut vertices = solver.enumerate_optimal_vertices(10);
+        vertices.sort();
+        assert_eq!(vertices, vec![vec![r(1), r(3)], vec![r(3), r(1)]]);
+        assert_eq!(solver.optimality_gap(), Some(r(0)));
+        let mut solver = Solver::<Rational64>::new(input);
+        assert_eq!(solver.enumerate_optimal_vertices(1).len(), 1);
+        // a unique optimum in a minimization
+        let mut solver = Solver::<f64>::new(
+            "
+            vars x1>=0, x2>=0
+            min 3x1+2x2
+            subject to
+                x1 + x2 >= 2.5,
+                x1 - x2 <= 1
+        ",
+        );
+        assert_eq!(solver.enumerate_optimal_vertices(10), vec![vec![0., 2.5]]);
+        let mut solver = Solver::<f64>::new(
+            "
+            vars x1>=0
+            max x1
+            subject to
+                x1 >= 1
+        ",
+        );
+        assert!(solver.enumerate_optimal_vertices(10).is_empty());
+    }
+
+    #[test]
+    fn solver_enumerate_optimal_vertices_degenerate() {
+        // the unique optimum (1, 1) is tight on every constraint, so it is the basic solution of
+        // many optimal bases
+        let input = "
+            vars x1>=0, x2>=0
+            max x1+x2
+            subject to
+                x1 + x2 <= 2,
+                x1 <= 1,
+                x2 <= 1,
+                x1 + 2x2 <= 3,
+                2x1 + x2 <= 3
+        ";
+        let r = Rational64::from_integer;
+        let mut solver = Solver::<Rational64>::new(input);
+        assert_eq!(
+            solver.enumerate_optimal_vertices(usize::MAX),
+            vec![vec![r(1), r(1)]]
+        );
+        let all_iterations = solver.num_iterations();
+        // the limit bounds the explored bases, not only the returned vertices
+        let mut solver = Solver::<Rational64>::new(input);
+        assert_eq!(solver.enumerate_optimal_vertices(1), vec![vec![r(1), r(1)]]);
+        assert!(solver.num_iterations() < all_iterations);
+    }
+}
@@ -9,11 +9,28 @@ pub struct UnionFindGeneric<NodeType: UnionNodeTrait> {
     pub payload: Vec<NodeType>,
     /// internal cache of parent list when calling `find`
     find_parent_list: Vec<usize>,
+    /// how to choose the root when [`UnionNodeTrait::tie`] reports that both roots are equally good
+    #[serde(default)]
+    pub tie_break: UnionTieBreak,
+}
+
+/// the root chosen by [`UnionFindGeneric::union`] when the two roots tie (e.g. on rank or set size)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnionTieBreak {
+    /// the root with the lower index becomes the root of the union, regardless of the argument order
+    #[default]
+    LowerIndex,
+    /// follow [`UnionNodeTrait::union`], which typically favors the first argument
+    Left,
 }
 
 pub trait UnionNodeTrait {
     /// return `is_left`, `after_union`
     fn union(left: &Self, right: &Self) -> (bool, Self);
+    /// whether `left` and `right` are equally good roots, in which case [`UnionFindGeneric::tie_break`] decides
+    fn tie(_left: &Self, _right: &Self) -> bool {
+        false
+    }
     /// clear the state, if [`UnionFindGeneric::clear`] is called then this must be provided
     fn clear(&mut self) {
         unimplemented!("[`UnionNodeTrait::clear`] must be implemented")
@@ -46,6 +63,10 @@ impl UnionNodeTrait for ExampleUnionNode {
         (left.set_size >= right.set_size, result)
     }
     #[inline]
+    fn tie(left: &Self, right: &Self) -> bool {
+        left.set_size == right.set_size
+    }
+    #[inline]
     fn clear(&mut self) {
         self.set_size = 1;
     }
@@ -62,6 +83,7 @@ impl<NodeType: UnionNodeTrait> FromIterator<NodeType> for UnionFindGeneric<NodeT
             link_parent: vec![],
             payload: vec![],
             find_parent_list: Vec::new(),
+            tie_break: UnionTieBreak::default(),
         };
         uf.extend(iterator);
         uf
@@ -112,10 +134,11 @@ impl<NodeType: UnionNodeTrait> UnionFindGeneric<NodeType> {
             return false;
         }
 
-        let (parent, child, val) = match NodeType::union(&self.payload[k0], &self.payload[k1]) {
-            (true, val) => (k0, k1, val),  // left
-            (false, val) => (k1, k0, val), // right
-        };
+        let (mut is_left, val) = NodeType::union(&self.payload[k0], &self.payload[k1]);
+        if self.tie_break == UnionTieBreak::LowerIndex && NodeType::tie(&self.payload[k0], &self.payload[k1]) {
+            is_left = k0 < k1;
+        }
+        let (parent, child) = if is_left { (k0, k1) } else { (k1, k0) };
         self.payload[parent] = val;
         self.link_parent[child] = parent;
 
@@ -223,4 +246,33 @@ mod tests {
         assert_eq!(101, uf.size());
         uf.clear();
     }
+
+    #[test]
+    fn union_find_tie_break_lower_index() {
+        // cargo test union_find_tie_break_lower_index -- --nocapture
+        let mut uf = ExampleUnionFind::new(8);
+        assert!(uf.union(1, 0));
+        assert_eq!(uf.find(1), 0);
+        assert!(uf.union(7, 6));
+        assert_eq!(uf.find(7), 6);
+        assert!(uf.union(5, 4));
+        // two sets of equal size: the lower root wins no matter the argument order
+        assert!(uf.union(6, 4));
+        assert_eq!(uf.find(7), 4);
+        assert!(uf.union(3, 2));
+        assert!(uf.union(3, 1));
+        assert_eq!(uf.find(3), 0);
+        assert!(uf.union(7, 3));
+        assert!((0..8).all(|key| uf.find(key) == 0));
+        // unequal sizes still follow the node's own choice
+        let mut uf = ExampleUnionFind::new(3);
+        assert!(uf.union(2, 1));
+        assert!(uf.union(0, 2));
+        assert_eq!(uf.find(0), 1);
+        // the previous behavior is still available
+        let mut uf = ExampleUnionFind::new(2);
+        uf.tie_break = UnionTieBreak::Left;
+        assert!(uf.union(1, 0));
+        assert_eq!(uf.find(0), 1);
+    }
 }
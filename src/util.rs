@@ -15,7 +15,7 @@ use pyo3::prelude::*;
 #[cfg(feature = "python_binding")]
 use pyo3::types::{PyDict, PyFloat, PyList, PyTuple};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::{BufReader, BufWriter};
@@ -69,6 +69,80 @@ pub type VertexNodeIndex = VertexIndex; // must be same as VertexIndex, NodeInde
 pub type VertexNum = VertexIndex;
 pub type NodeNum = VertexIndex;
 
+/// convert a weight to the fixed-point integer `round(w * scale)`, e.g. to interoperate with integer-only matchers;
+/// values beyond the range of `i64` saturate to `i64::MIN` or `i64::MAX`
+pub fn rational_to_fixed(w: &Rational, scale: u64) -> i64 {
+    assert!(scale > 0, "the fixed-point scale must be positive");
+    let scaled = w * Rational::from_u64(scale).unwrap();
+    cfg_if::cfg_if! {
+        if #[cfg(feature="f64_weight")] {
+            scaled.to_f64().unwrap().round() as i64 // `as` saturates on overflow
+        } else if #[cfg(feature="rational_weight")] {
+            use num_traits::Signed;
+            scaled.round().to_i64().unwrap_or(if scaled.is_negative() { i64::MIN } else { i64::MAX })
+        }
+    }
+}
+
+/// the conversion from [`Rational`] to `f64` cannot be trusted, either because the value overflows `f64` or because
+/// its numerator or denominator has more significant bits than `f64` can hold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecisionWarning {
+    /// the closest finite `f64`, saturated to `f64::MAX` or `f64::MIN` on overflow
+    pub value: f64,
+}
+
+/// convert a rational to `f64`, reporting a [`PrecisionWarning`] instead of panicking or silently losing precision
+pub fn rational_to_f64_checked(value: &Rational) -> Result<f64, PrecisionWarning> {
+    let approximate = value.to_f64().unwrap_or(f64::NAN);
+    let saturated = if approximate.is_nan() {
+        if value < &Rational::zero() {
+            f64::MIN
+        } else {
+            f64::MAX
+        }
+    } else {
+        approximate.clamp(f64::MIN, f64::MAX)
+    };
+    if saturated != approximate {
+        return Err(PrecisionWarning { value: saturated });
+    }
+    cfg_if::cfg_if! {
+        if #[cfg(feature="rational_weight")] {
+            // exact if it round-trips, e.g. any value created from `f64`; otherwise it's still a correctly rounded
+            // quotient if both the numerator and denominator are exact in `f64`, i.e. no more than 53 bits
+            let fits = |x: &num_bigint::BigInt| x.bits() <= f64::MANTISSA_DIGITS as u64;
+            if Rational::from_float(saturated).as_ref() != Some(value) && !(fits(value.numer()) && fits(value.denom())) {
+                return Err(PrecisionWarning { value: saturated });
+            }
+        }
+    }
+    Ok(saturated)
+}
+
+static PRECISION_WARNING_LOGGED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// convert a rational to `f64` for non-critical uses like visualization and heuristics: on precision loss, the
+/// closest finite value is returned and a warning is printed, only once per process to avoid flooding the output
+pub fn rational_to_f64_lossy(value: &Rational) -> f64 {
+    rational_to_f64_checked(value).unwrap_or_else(|warning| {
+        if !PRECISION_WARNING_LOGGED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            eprintln!(
+                "[warning] rational {value} loses precision when converted to f64: {}",
+                warning.value
+            );
+        }
+        warning.value
+    })
+}
+
+/// convert a fixed-point integer back to a weight, the inverse of [`rational_to_fixed`] up to its rounding error of
+/// at most `1 / (2 * scale)`
+pub fn fixed_to_rational(x: i64, scale: u64) -> Rational {
+    assert!(scale > 0, "the fixed-point scale must be positive");
+    Rational::from_i64(x).unwrap() / Rational::from_u64(scale).unwrap()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "python_binding", pyclass(module = "mwpf"))]
 pub struct HyperEdge {
@@ -134,6 +208,17 @@ pub struct SolverInitializer {
     pub heralds: Vec<Vec<(EdgeIndex, Weight)>>,
 }
 
+/// summary statistics of the edge weights of a decoding graph, see [`SolverInitializer::weight_stats`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// the average of the two middle weights if there are an even number of edges
+    pub median: f64,
+    pub negative_count: usize,
+}
+
 pub fn exclusive_weight_sum(w1: &Weight, w2: &Weight) -> Weight {
     // w1 = log( (1-p1) / p1 ), weight_2 = log( (1-p2) / p2 )
     // p1 = 1 / (1 + exp(w1)), p2 = 1 / (1 + exp(w2))
@@ -165,6 +250,84 @@ impl SolverInitializer {
     }
 }
 
+/// which weight to keep when [`SolverInitializerBuilder::add_edge`] meets an edge with the same incident vertices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateEdgePolicy {
+    /// keep the smaller weight, i.e. the more likely error
+    #[default]
+    KeepMin,
+    /// keep the larger weight, i.e. the less likely error
+    KeepMax,
+    /// treat the edges as independent errors with the same effect and combine their probabilities,
+    /// see [`exclusive_weight_sum`]
+    CombineProbabilities,
+}
+
+/// build a [`SolverInitializer`] edge by edge, collapsing duplicate edges on insertion instead of failing the
+/// [`SolverInitializer::sanity_check`] afterwards
+#[derive(Debug, Clone, Default)]
+pub struct SolverInitializerBuilder {
+    /// if not given, the number of vertices is the largest incident vertex index plus one
+    vertex_num: Option<VertexNum>,
+    weighted_edges: Vec<HyperEdge>,
+    /// the sorted incident vertices of every edge
+    edge_indices: BTreeMap<Vec<VertexIndex>, EdgeIndex>,
+    policy: DuplicateEdgePolicy,
+}
+
+impl SolverInitializer {
+    pub fn builder() -> SolverInitializerBuilder {
+        SolverInitializerBuilder::default()
+    }
+}
+
+impl SolverInitializerBuilder {
+    /// set the number of vertices explicitly, e.g. to include vertices without any incident edge
+    pub fn vertex_num(mut self, vertex_num: VertexNum) -> Self {
+        self.vertex_num = Some(vertex_num);
+        self
+    }
+
+    pub fn policy(mut self, policy: DuplicateEdgePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// add an edge and return its index; if an edge with the same incident vertices (in any order) already exists,
+    /// its weight is updated according to the [`DuplicateEdgePolicy`] and its index is returned instead
+    pub fn add_edge(&mut self, vertices: Vec<VertexIndex>, weight: Weight) -> EdgeIndex {
+        let mut sorted_vertices = vertices.clone();
+        sorted_vertices.sort();
+        if let Some(&edge_index) = self.edge_indices.get(&sorted_vertices) {
+            let existing = &mut self.weighted_edges[edge_index].weight;
+            match self.policy {
+                DuplicateEdgePolicy::KeepMin if weight < *existing => *existing = weight,
+                DuplicateEdgePolicy::KeepMax if weight > *existing => *existing = weight,
+                DuplicateEdgePolicy::CombineProbabilities => *existing = exclusive_weight_sum(existing, &weight),
+                _ => {}
+            }
+            return edge_index;
+        }
+        let edge_index = self.weighted_edges.len();
+        self.edge_indices.insert(sorted_vertices, edge_index);
+        self.weighted_edges.push(HyperEdge::new(vertices, weight));
+        edge_index
+    }
+
+    /// build the initializer, failing if it does not pass [`SolverInitializer::sanity_check`]
+    pub fn build(self) -> Result<SolverInitializer, String> {
+        let vertex_num = self.vertex_num.unwrap_or_else(|| {
+            (self.weighted_edges.iter())
+                .flat_map(|edge| edge.vertices.iter())
+                .max()
+                .map_or(0, |&vertex_index| vertex_index + 1)
+        });
+        let initializer = SolverInitializer::new(vertex_num, self.weighted_edges);
+        initializer.sanity_check()?;
+        Ok(initializer)
+    }
+}
+
 #[cfg(feature = "python_binding")]
 #[pymethods]
 impl SolverInitializer {
@@ -342,6 +505,254 @@ impl SolverInitializer {
             edge.weight = weight.clone();
         }
     }
+
+    /// summary statistics of the edge weights, useful to spot mis-scaled weights before decoding;
+    /// all statistics are zero if there are no edges
+    pub fn weight_stats(&self) -> WeightStats {
+        let mut weights: Vec<f64> = self.weighted_edges.iter().map(|edge| edge.weight.to_f64().unwrap()).collect();
+        let negative_count = weights.iter().filter(|&&weight| weight < 0.).count();
+        if weights.is_empty() {
+            return WeightStats {
+                min: 0.,
+                max: 0.,
+                mean: 0.,
+                median: 0.,
+                negative_count,
+            };
+        }
+        weights.sort_by(f64::total_cmp);
+        let length = weights.len();
+        let median = if length % 2 == 1 {
+            weights[length / 2]
+        } else {
+            (weights[length / 2 - 1] + weights[length / 2]) / 2.
+        };
+        WeightStats {
+            min: weights[0],
+            max: weights[length - 1],
+            mean: weights.iter().sum::<f64>() / length as f64,
+            median,
+            negative_count,
+        }
+    }
+
+    /// the number of edges incident to each vertex, indexed by the vertex index
+    #[allow(clippy::unnecessary_cast)]
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.vertex_num as usize];
+        for edge in self.weighted_edges.iter() {
+            for &vertex_index in edge.vertices.iter() {
+                degrees[vertex_index as usize] += 1;
+            }
+        }
+        degrees
+    }
+
+    /// the number of edges of each size, i.e. hyperedge cardinality, where sizes above 2 indicate a hypergraph
+    pub fn edge_size_distribution(&self) -> BTreeMap<usize, usize> {
+        let mut distribution = BTreeMap::new();
+        for edge in self.weighted_edges.iter() {
+            *distribution.entry(edge.vertices.len()).or_insert(0) += 1;
+        }
+        distribution
+    }
+
+    /// the degree assortativity, i.e. the Pearson correlation between the degrees of every (ordered) pair of vertices
+    /// sharing an edge; `None` if there is no such pair or all of them have the same degree
+    #[allow(clippy::unnecessary_cast)]
+    pub fn degree_assortativity(&self) -> Option<f64> {
+        let degrees = self.degree_sequence();
+        let (mut count, mut sum, mut square_sum, mut product_sum) = (0., 0., 0., 0.);
+        for edge in self.weighted_edges.iter() {
+            for (i, &vertex_1) in edge.vertices.iter().enumerate() {
+                for &vertex_2 in edge.vertices[i + 1..].iter() {
+                    let (degree_1, degree_2) = (degrees[vertex_1 as usize] as f64, degrees[vertex_2 as usize] as f64);
+                    count += 2.;
+                    sum += degree_1 + degree_2;
+                    square_sum += degree_1 * degree_1 + degree_2 * degree_2;
+                    product_sum += 2. * degree_1 * degree_2;
+                }
+            }
+        }
+        if count == 0. {
+            return None;
+        }
+        let mean = sum / count;
+        let variance = square_sum / count - mean * mean;
+        if variance.abs() < 1e-12 {
+            return None;
+        }
+        Some((product_sum / count - mean * mean) / variance)
+    }
+
+    /// merge the parallel edges, i.e. those with the same incident vertices in any order, into the first of them
+    /// according to `policy`; the heralds are re-indexed accordingly and the new index of every original edge is returned
+    pub fn merge_parallel_edges(&mut self, policy: DuplicateEdgePolicy) -> Vec<EdgeIndex> {
+        let mut builder = SolverInitializer::builder().policy(policy);
+        let edge_map: Vec<EdgeIndex> = (self.weighted_edges.iter())
+            .map(|edge| builder.add_edge(edge.vertices.clone(), edge.weight.clone()))
+            .collect();
+        self.weighted_edges = builder.weighted_edges;
+        for herald in self.heralds.iter_mut() {
+            for (edge_index, _) in herald.iter_mut() {
+                *edge_index = edge_map[*edge_index];
+            }
+        }
+        edge_map
+    }
+
+    /// combine two decoding graphs into a single one, e.g. for decoding two code blocks together;
+    /// the vertices of `other` are shifted by `self.vertex_num` and its edges (and heralds) are appended after `self`'s
+    pub fn merge(&self, other: &SolverInitializer) -> SolverInitializer {
+        let vertex_offset = self.vertex_num;
+        let edge_offset = self.weighted_edges.len();
+        let mut weighted_edges = self.weighted_edges.clone();
+        weighted_edges.extend(other.weighted_edges.iter().map(|edge| {
+            HyperEdge::new(
+                edge.vertices
+                    .iter()
+                    .map(|&vertex_index| vertex_index + vertex_offset)
+                    .collect(),
+                edge.weight.clone(),
+            )
+        }));
+        let mut heralds = self.heralds.clone();
+        heralds.extend(other.heralds.iter().map(|herald| {
+            herald
+                .iter()
+                .map(|(edge_index, weight)| (edge_index + edge_offset, weight.clone()))
+                .collect()
+        }));
+        SolverInitializer::new_with_heralds(self.vertex_num + other.vertex_num, weighted_edges, heralds)
+    }
+
+    /// the connected components of the decoding hypergraph, where a hyperedge connects all its vertices; defects in
+    /// different components can never be matched across them. Each component lists its vertices in ascending order,
+    /// and the components are ordered by their smallest vertex; an isolated vertex forms a component by itself
+    pub fn connected_components(&self) -> Vec<Vec<VertexIndex>> {
+        let mut vertex_edges: Vec<Vec<EdgeIndex>> = vec![vec![]; self.vertex_num];
+        for (edge_index, edge) in self.weighted_edges.iter().enumerate() {
+            for &vertex_index in edge.vertices.iter() {
+                vertex_edges[vertex_index].push(edge_index);
+            }
+        }
+        let mut visited = vec![false; self.vertex_num];
+        let mut components = vec![];
+        for root in 0..self.vertex_num {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            while let Some(vertex_index) = stack.pop() {
+                for &edge_index in vertex_edges[vertex_index].iter() {
+                    for &peer in self.weighted_edges[edge_index].vertices.iter() {
+                        if !visited[peer] {
+                            visited[peer] = true;
+                            component.push(peer);
+                            stack.push(peer);
+                        }
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+
+    /// whether the decoding hypergraph has at most one connected component, see [`Self::connected_components`]
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().len() <= 1
+    }
+}
+
+/// relabel the vertices in breadth-first order so that neighboring vertices are close in memory;
+/// edges keep their indices, and `permutation[old_vertex_index]` is the new index of each vertex,
+/// which maps syndromes onto the reordered graph (results are edge-based and need no mapping)
+pub fn reorder_vertices_bfs(initializer: &SolverInitializer) -> (SolverInitializer, Vec<VertexIndex>) {
+    let vertex_num = initializer.vertex_num;
+    let mut vertex_edges: Vec<Vec<EdgeIndex>> = vec![vec![]; vertex_num];
+    for (edge_index, edge) in initializer.weighted_edges.iter().enumerate() {
+        for &vertex_index in edge.vertices.iter() {
+            vertex_edges[vertex_index].push(edge_index);
+        }
+    }
+    let mut permutation: Vec<Option<VertexIndex>> = vec![None; vertex_num];
+    let mut next_index = 0;
+    let mut queue = std::collections::VecDeque::new();
+    for root in 0..vertex_num {
+        if permutation[root].is_some() {
+            continue;
+        }
+        permutation[root] = Some(next_index);
+        next_index += 1;
+        queue.push_back(root);
+        while let Some(vertex_index) = queue.pop_front() {
+            for &edge_index in vertex_edges[vertex_index].iter() {
+                for &peer in initializer.weighted_edges[edge_index].vertices.iter() {
+                    if permutation[peer].is_none() {
+                        permutation[peer] = Some(next_index);
+                        next_index += 1;
+                        queue.push_back(peer);
+                    }
+                }
+            }
+        }
+    }
+    let permutation: Vec<VertexIndex> = permutation.into_iter().map(Option::unwrap).collect();
+    let weighted_edges = initializer
+        .weighted_edges
+        .iter()
+        .map(|edge| {
+            HyperEdge::new(
+                edge.vertices.iter().map(|&vertex_index| permutation[vertex_index]).collect(),
+                edge.weight.clone(),
+            )
+        })
+        .collect();
+    let reordered = SolverInitializer::new_with_heralds(vertex_num, weighted_edges, initializer.heralds.clone());
+    (reordered, permutation)
+}
+
+/// a random decoding hypergraph that passes [`SolverInitializer::sanity_check`], reproducible from `seed`, e.g. for
+/// fuzzing the solver: `edge_num` distinct hyperedges, each with 1 to `max_edge_degree` distinct vertices and an
+/// integer weight in [1, 10]
+pub fn random_initializer(vertex_num: VertexNum, edge_num: usize, max_edge_degree: usize, seed: u64) -> SolverInitializer {
+    use crate::rand_xoshiro::rand_core::SeedableRng;
+    let max_edge_degree = max_edge_degree.min(vertex_num);
+    assert!(edge_num > 0 && max_edge_degree > 0, "the graph cannot be empty");
+    // the number of distinct hyperedges, counting until it's enough
+    let (mut binomial, mut capacity) = (1u128, 0u128);
+    for degree in 1..=max_edge_degree {
+        binomial = binomial * (vertex_num - degree + 1) as u128 / degree as u128;
+        capacity += binomial;
+        if capacity >= edge_num as u128 {
+            break;
+        }
+    }
+    assert!(
+        capacity >= edge_num as u128,
+        "only {capacity} distinct hyperedges exist with {vertex_num} vertices and degree at most {max_edge_degree}"
+    );
+    let mut rng = DeterministicRng::seed_from_u64(seed);
+    let mut random_below = |bound: usize| (rng.next_u64() % bound as u64) as usize;
+    let mut builder = SolverInitializer::builder().vertex_num(vertex_num);
+    let mut added = 0;
+    while added < edge_num {
+        let degree = 1 + random_below(max_edge_degree);
+        let mut vertices = BTreeSet::new();
+        while vertices.len() < degree {
+            vertices.insert(random_below(vertex_num));
+        }
+        let weight = Rational::from_usize(1 + random_below(10)).unwrap();
+        // a duplicate is collapsed into the existing edge of the same vertices
+        if builder.add_edge(vertices.into_iter().collect(), weight) == added {
+            added += 1;
+        }
+    }
+    builder.build().expect("the random hyperedges are distinct")
 }
 
 impl MWPSVisualizer for SolverInitializer {
@@ -353,7 +764,7 @@ impl MWPSVisualizer for SolverInitializer {
         }
         for HyperEdge { vertices, weight } in self.weighted_edges.iter() {
             edges.push(json!({
-                if abbrev { "w" } else { "weight" }: weight.to_f64(),
+                if abbrev { "w" } else { "weight" }: rational_to_f64_lossy(weight),
                 "wn": numer_of(weight),
                 "wd": denom_of(weight),
                 if abbrev { "v" } else { "vertices" }: vertices,
@@ -415,6 +826,40 @@ impl SyndromePattern {
     }
 }
 
+/// parse the compact form of a syndrome, e.g. `"3,29,30"` for defect vertices only or `"3,29,30|e:5,6"` with erasures
+impl std::str::FromStr for SyndromePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_indices(list: &str, name: &str) -> Result<Vec<usize>, String> {
+            let list = list.trim();
+            if list.is_empty() {
+                return Ok(vec![]);
+            }
+            list.split(',')
+                .map(|index| {
+                    let index = index.trim();
+                    index.parse::<usize>().map_err(|_| format!("invalid {name} index `{index}`"))
+                })
+                .collect()
+        }
+        let (defects, erasures) = match s.split_once('|') {
+            Some((defects, erasures)) => {
+                let erasures = erasures
+                    .trim()
+                    .strip_prefix("e:")
+                    .ok_or_else(|| format!("expect erasures in the form of `e:1,2,3` after `|`, found `{erasures}`"))?;
+                (defects, erasures)
+            }
+            None => (s, ""),
+        };
+        Ok(Self::new_erasure(
+            parse_indices(defects, "defect vertex")?,
+            parse_indices(erasures, "erasure edge")?,
+        ))
+    }
+}
+
 impl MWPSVisualizer for SyndromePattern {
     fn snapshot(&self, abbrev: bool) -> serde_json::Value {
         let vertex_num = self.defect_vertices.iter().cloned().max().unwrap_or_default() + 1;
@@ -561,11 +1006,11 @@ pub type Subgraph = Vec<EdgeIndex>;
 
 pub struct OutputSubgraph {
     pub subgraph: Subgraph,
-    pub flip_edge_indices: hashbrown::HashSet<EdgeIndex>,
+    pub flip_edge_indices: BTreeSet<EdgeIndex>,
 }
 
 impl OutputSubgraph {
-    pub fn new(subgraph: Subgraph, flip_edge_indices: hashbrown::HashSet<EdgeIndex>) -> Self {
+    pub fn new(subgraph: Subgraph, flip_edge_indices: BTreeSet<EdgeIndex>) -> Self {
         Self {
             subgraph,
             flip_edge_indices,
@@ -580,6 +1025,12 @@ impl OutputSubgraph {
         }
     }
 
+    /// the same edges as [`Self::iter`] but in increasing edge index, for deterministic output
+    pub fn iter_sorted(&self) -> impl Iterator<Item = EdgeIndex> {
+        let subgraph_set: BTreeSet<EdgeIndex> = self.subgraph.iter().cloned().collect();
+        (&subgraph_set ^ &self.flip_edge_indices).into_iter()
+    }
+
     // Mutable iterator with updates to `subgraph` during iteration
     pub fn iter_mut(&mut self) -> OutputSubgraphIterMut {
         OutputSubgraphIterMut {
@@ -588,11 +1039,83 @@ impl OutputSubgraph {
             flip_edge_indices: &mut self.flip_edge_indices,
         }
     }
+
+    /// export the decoding graph in Graphviz DOT format with the edges of this subgraph highlighted;
+    /// an edge with two vertices is drawn as a line, other hyperedges as a point connected to each of its vertices
+    pub fn to_dot(&self, model_graph: &crate::model_hypergraph::ModelHyperGraph) -> String {
+        use std::fmt::Write;
+        let selected: BTreeSet<EdgeIndex> = self.iter().cloned().collect();
+        let mut dot = "graph subgraph {\n    node [shape=circle];\n".to_string();
+        for vertex_index in 0..model_graph.initializer.vertex_num {
+            writeln!(dot, "    v{vertex_index};").unwrap();
+        }
+        for (edge_index, edge) in model_graph.initializer.weighted_edges.iter().enumerate() {
+            let highlight = selected.contains(&edge_index);
+            let style = if highlight { ", color=red, penwidth=3" } else { "" };
+            if let [u, v] = edge.vertices[..] {
+                writeln!(dot, "    v{u} -- v{v} [label=\"e{edge_index}\"{style}];").unwrap();
+            } else {
+                writeln!(dot, "    e{edge_index} [shape=point, xlabel=\"e{edge_index}\"{style}];").unwrap();
+                for vertex_index in edge.vertices.iter() {
+                    if highlight {
+                        writeln!(dot, "    e{edge_index} -- v{vertex_index} [color=red, penwidth=3];").unwrap();
+                    } else {
+                        writeln!(dot, "    e{edge_index} -- v{vertex_index};").unwrap();
+                    }
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// greedily remove redundant edges: whenever a subset of the selected edges has an empty syndrome (e.g. a loop)
+    /// and a positive total weight, dropping it yields an equivalent correction of lower weight;
+    /// the subsets are found by Gaussian elimination over the selected edges, so the result is not guaranteed to be
+    /// the minimum-weight equivalent, but no fundamental loop with positive weight remains
+    pub fn minimize(&mut self, model_graph: &crate::model_hypergraph::ModelHyperGraph) {
+        use crate::num_traits::Signed;
+        let weighted_edges = &model_graph.initializer.weighted_edges;
+        let mut edges: BTreeSet<EdgeIndex> = self.iter().cloned().collect();
+        'search: loop {
+            // the eliminated rows, indexed by their smallest vertex: (syndrome, edges generating this syndrome)
+            let mut pivots: BTreeMap<VertexIndex, (BTreeSet<VertexIndex>, BTreeSet<EdgeIndex>)> = BTreeMap::new();
+            for &edge_index in edges.iter() {
+                let mut syndrome = BTreeSet::new();
+                for &vertex_index in weighted_edges[edge_index].vertices.iter() {
+                    if !syndrome.remove(&vertex_index) {
+                        syndrome.insert(vertex_index);
+                    }
+                }
+                let mut combination = BTreeSet::from([edge_index]);
+                while let Some((pivot_syndrome, pivot_combination)) =
+                    syndrome.first().and_then(|vertex_index| pivots.get(vertex_index))
+                {
+                    syndrome = &syndrome ^ pivot_syndrome;
+                    combination = &combination ^ pivot_combination;
+                }
+                if let Some(&pivot) = syndrome.first() {
+                    pivots.insert(pivot, (syndrome, combination));
+                    continue;
+                }
+                let weight = combination.iter().fold(Rational::zero(), |weight, &edge_index| {
+                    weight + &weighted_edges[edge_index].weight
+                });
+                if weight.is_positive() {
+                    edges = &edges - &combination;
+                    continue 'search;
+                }
+            }
+            break;
+        }
+        self.subgraph = edges.into_iter().collect();
+        self.flip_edge_indices.clear();
+    }
 }
 
 impl From<Subgraph> for OutputSubgraph {
     fn from(value: Subgraph) -> Self {
-        Self::new(value, hashbrown::HashSet::new())
+        Self::new(value, BTreeSet::new())
     }
 }
 
@@ -641,8 +1164,8 @@ impl IntoIterator for OutputSubgraph {
 
 pub struct OutputSubgraphIter<'a> {
     subgraph_iter: std::slice::Iter<'a, usize>,
-    flip_edge_indices: &'a hashbrown::HashSet<EdgeIndex>,
-    remaining_indices: hashbrown::HashSet<EdgeIndex>,
+    flip_edge_indices: &'a BTreeSet<EdgeIndex>,
+    remaining_indices: BTreeSet<EdgeIndex>,
 }
 
 impl<'a> Iterator for OutputSubgraphIter<'a> {
@@ -680,7 +1203,7 @@ impl<'a> Iterator for OutputSubgraphIter<'a> {
 pub struct OutputSubgraphIterMut<'a> {
     subgraph: &'a mut Subgraph,
     subgraph_iter: usize, // Index within `subgraph`
-    flip_edge_indices: &'a mut hashbrown::HashSet<EdgeIndex>,
+    flip_edge_indices: &'a mut BTreeSet<EdgeIndex>,
 }
 
 // note: use of unsafe
@@ -726,8 +1249,8 @@ impl<'a> Iterator for OutputSubgraphIterMut<'a> {
 // Consuming iterator
 pub struct OutputSubgraphIntoIter {
     subgraph_iter: std::vec::IntoIter<usize>,
-    flip_edge_indices: hashbrown::HashSet<EdgeIndex>,
-    remaining_indices: hashbrown::HashSet<EdgeIndex>,
+    flip_edge_indices: BTreeSet<EdgeIndex>,
+    remaining_indices: BTreeSet<EdgeIndex>,
 }
 
 impl Iterator for OutputSubgraphIntoIter {
@@ -758,19 +1281,12 @@ impl Iterator for OutputSubgraphIntoIter {
 
 impl MWPSVisualizer for OutputSubgraph {
     fn snapshot(&self, _abbrev: bool) -> serde_json::Value {
-        let mut adjusted_subgraph_set = self.subgraph.iter().collect::<hashbrown::HashSet<_>>();
-        for to_flip in self.flip_edge_indices.iter() {
-            if adjusted_subgraph_set.contains(to_flip) {
-                adjusted_subgraph_set.remove(to_flip);
-            } else {
-                adjusted_subgraph_set.insert(to_flip);
-            }
-        }
-        let adjusted_subgraph = adjusted_subgraph_set.into_iter().collect::<Vec<_>>();
+        let mut subgraph = self.subgraph.clone();
+        subgraph.sort_unstable();
         json!({
-            "subgraph": self.subgraph,
+            "subgraph": subgraph,
             "flip_edge_indices": self.flip_edge_indices.iter().collect::<Vec<_>>(),
-            "adjusted_subgraph_for_negative_weight": adjusted_subgraph
+            "adjusted_subgraph_for_negative_weight": self.iter_sorted().collect::<Vec<_>>()
         })
     }
 }
@@ -778,28 +1294,15 @@ impl MWPSVisualizer for OutputSubgraph {
 #[allow(clippy::to_string_in_format_args)]
 impl std::fmt::Debug for OutputSubgraph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Create adjusted subgraph set
-        let mut adjusted_subgraph_set = self.subgraph.iter().copied().collect::<hashbrown::HashSet<_>>();
-        for &to_flip in &self.flip_edge_indices {
-            if adjusted_subgraph_set.contains(&to_flip) {
-                adjusted_subgraph_set.remove(&to_flip);
-            } else {
-                adjusted_subgraph_set.insert(to_flip);
-            }
-        }
-        let adjusted_subgraph = adjusted_subgraph_set.into_iter().collect::<Vec<_>>();
+        // Output debug information in the same format as snapshot
+        write!(f, "{}", self.snapshot(false).to_string())
+    }
+}
 
-        // Output debug information in similar format to snapshot
-        write!(
-            f,
-            "{}",
-            json!({
-                "subgraph": self.subgraph,
-                "flip_edge_indices": self.flip_edge_indices.iter().collect::<Vec<_>>(),
-                "adjusted_subgraph_for_negative_weight": adjusted_subgraph
-            })
-            .to_string()
-        )
+/// the selected edges in increasing edge index, e.g. `[1, 4, 7]`
+impl std::fmt::Display for OutputSubgraph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.iter_sorted().collect::<Vec<_>>())
     }
 }
 
@@ -826,14 +1329,18 @@ impl WeightRange {
     pub fn is_optimal(&self) -> bool {
         self.lower == self.upper
     }
+    /// the exact difference between the bounds, zero if and only if the solution is proven optimal
+    pub fn gap(&self) -> Rational {
+        &self.upper - &self.lower
+    }
 }
 
 impl MWPSVisualizer for WeightRange {
     fn snapshot(&self, _abbrev: bool) -> serde_json::Value {
         json!({
             "weight_range": {
-                "lower": self.lower.to_f64(),
-                "upper": self.upper.to_f64(),
+                "lower": rational_to_f64_lossy(&self.lower),
+                "upper": rational_to_f64_lossy(&self.upper),
                 "ln": numer_of(&self.lower),
                 "ld": denom_of(&self.lower),
                 "un": numer_of(&self.upper),
@@ -1247,13 +1754,13 @@ impl BenchmarkSuite {
 
 #[cfg(test)]
 pub mod tests {
-    use crate::example_codes::ExampleCode;
+    use crate::example_codes::{CodeCapacityColorCode, ExampleCode};
 
     use super::*;
     use bytebuffer::ByteBuffer;
-    use hashbrown::HashSet;
     use num_bigint::BigInt;
     use std::str::FromStr;
+    use std::sync::Arc;
 
     #[test]
     fn util_py_json_bigint() {
@@ -1270,7 +1777,7 @@ pub mod tests {
     #[test]
     fn test_iter() {
         let subgraph = vec![1, 2, 3, 4];
-        let mut flip_edge_indices = HashSet::new();
+        let mut flip_edge_indices = BTreeSet::new();
         flip_edge_indices.insert(2);
         flip_edge_indices.insert(5);
 
@@ -1284,7 +1791,7 @@ pub mod tests {
     #[test]
     fn test_iter_empty_flip_edge_indices() {
         let subgraph = vec![1, 2, 3];
-        let flip_edge_indices = HashSet::new();
+        let flip_edge_indices = BTreeSet::new();
 
         let output_subgraph = OutputSubgraph::new(subgraph.clone(), flip_edge_indices);
 
@@ -1296,7 +1803,7 @@ pub mod tests {
     #[test]
     fn test_iter_all_elements_flipped() {
         let subgraph = vec![1, 2, 3];
-        let mut flip_edge_indices = HashSet::new();
+        let mut flip_edge_indices = BTreeSet::new();
         flip_edge_indices.insert(1);
         flip_edge_indices.insert(2);
         flip_edge_indices.insert(3);
@@ -1312,7 +1819,7 @@ pub mod tests {
     #[test]
     fn test_iter_mut() {
         let subgraph = vec![1, 2, 3, 4];
-        let mut flip_edge_indices = HashSet::new();
+        let mut flip_edge_indices = BTreeSet::new();
         flip_edge_indices.insert(2);
         flip_edge_indices.insert(5);
 
@@ -1331,7 +1838,7 @@ pub mod tests {
     #[test]
     fn test_iter_mut_no_modifications() {
         let subgraph = vec![10, 20, 30];
-        let flip_edge_indices = HashSet::new(); // Empty flip edge indices
+        let flip_edge_indices = BTreeSet::new(); // Empty flip edge indices
 
         let mut output_subgraph = OutputSubgraph::new(subgraph.clone(), flip_edge_indices);
 
@@ -1348,7 +1855,7 @@ pub mod tests {
     #[test]
     fn test_into_iter() {
         let subgraph = vec![1, 2, 3, 4];
-        let mut flip_edge_indices = HashSet::new();
+        let mut flip_edge_indices = BTreeSet::new();
         flip_edge_indices.insert(2);
         flip_edge_indices.insert(5);
 
@@ -1364,7 +1871,7 @@ pub mod tests {
     #[test]
     fn test_into_iter_all_elements_flipped() {
         let subgraph = vec![1, 2, 3];
-        let mut flip_edge_indices = HashSet::new();
+        let mut flip_edge_indices = BTreeSet::new();
         flip_edge_indices.insert(1);
         flip_edge_indices.insert(2);
         flip_edge_indices.insert(3);
@@ -1380,7 +1887,7 @@ pub mod tests {
     #[test]
     fn test_iter_empty_subgraph() {
         let subgraph = vec![];
-        let mut flip_edge_indices = HashSet::new();
+        let mut flip_edge_indices = BTreeSet::new();
         flip_edge_indices.insert(1);
         flip_edge_indices.insert(2);
 
@@ -1395,7 +1902,7 @@ pub mod tests {
     #[test]
     fn test_iter_mut_update_subgraph() {
         let subgraph = vec![1, 2, 3, 4];
-        let mut flip_edge_indices = HashSet::new();
+        let mut flip_edge_indices = BTreeSet::new();
         flip_edge_indices.insert(2);
         flip_edge_indices.insert(5);
 
@@ -1491,4 +1998,387 @@ pub mod tests {
         assert_eq!(cbor_length_of(vec![Vec::<usize>::new(); 100]), 102); // 1 bytes each for empty vec
         assert_eq!(cbor_length_of(vec![None::<usize>; 100]), 102); // 1 bytes each for null vec
     }
+
+    #[test]
+    fn solver_initializer_merge() {
+        // cargo test solver_initializer_merge -- --nocapture
+        use crate::example_codes::CodeCapacityRepetitionCode;
+        let initializer_1 = CodeCapacityRepetitionCode::new(3, 0.1).get_initializer();
+        let initializer_2 = CodeCapacityRepetitionCode::new(5, 0.2).get_initializer();
+        let merged = initializer_1.merge(&initializer_2);
+        merged.sanity_check().unwrap();
+        assert_eq!(merged.vertex_num, initializer_1.vertex_num + initializer_2.vertex_num);
+        assert_eq!(
+            merged.weighted_edges.len(),
+            initializer_1.weighted_edges.len() + initializer_2.weighted_edges.len()
+        );
+        for (edge, original) in merged.weighted_edges.iter().zip(initializer_1.weighted_edges.iter()) {
+            assert_eq!(edge.vertices, original.vertices);
+            assert_eq!(edge.weight, original.weight);
+        }
+        for (edge, original) in merged.weighted_edges[initializer_1.weighted_edges.len()..]
+            .iter()
+            .zip(initializer_2.weighted_edges.iter())
+        {
+            let shifted: Vec<VertexIndex> = original.vertices.iter().map(|v| v + initializer_1.vertex_num).collect();
+            assert_eq!(edge.vertices, shifted);
+            assert_eq!(edge.weight, original.weight);
+        }
+    }
+
+    #[test]
+    fn solver_initializer_weight_stats() {
+        // cargo test solver_initializer_weight_stats -- --nocapture
+        use crate::example_codes::{weight_of_p, CodeCapacityPlanarCode};
+        let mut initializer = CodeCapacityPlanarCode::new(5, 0.1).get_initializer();
+        let edge_num = initializer.weighted_edges.len();
+        let weight = weight_of_p(0.1);
+        let stats = initializer.weight_stats();
+        assert!((stats.min - weight).abs() < 1e-9 && (stats.max - weight).abs() < 1e-9);
+        assert!((stats.mean - weight).abs() < 1e-9 && (stats.median - weight).abs() < 1e-9);
+        assert_eq!(stats.negative_count, 0);
+        // mis-scale two of the edges
+        initializer.weighted_edges[0].weight = Rational::from_f64(-1.).unwrap();
+        initializer.weighted_edges[1].weight = Rational::from_f64(100.).unwrap();
+        let stats = initializer.weight_stats();
+        println!("{stats:?}");
+        assert_eq!(stats.min, -1.);
+        assert_eq!(stats.max, 100.);
+        let expected_mean = ((edge_num - 2) as f64 * weight + 99.) / edge_num as f64;
+        assert!((stats.mean - expected_mean).abs() < 1e-9);
+        assert!((stats.median - weight).abs() < 1e-9);
+        assert_eq!(stats.negative_count, 1);
+        assert_eq!(SolverInitializer::new(1, vec![]).weight_stats().negative_count, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "rational_weight")]
+    fn util_rational_to_f64_checked() {
+        // cargo test util_rational_to_f64_checked -- --nocapture
+        for value in [0., 1., -2.5, 2.71, 1e-9] {
+            assert_eq!(rational_to_f64_checked(&Rational::from_float(value).unwrap()), Ok(value));
+        }
+        let third = Rational::from_u64(1).unwrap() / Rational::from_u64(3).unwrap();
+        assert_eq!(rational_to_f64_checked(&third), Ok(1. / 3.));
+        // a huge numerator no longer fits in the mantissa
+        let huge = Rational::from_float(1e30).unwrap() + Rational::from_u64(1).unwrap() / Rational::from_u64(3).unwrap();
+        let warning = rational_to_f64_checked(&huge).unwrap_err();
+        assert!(warning.value.is_finite());
+        assert!((warning.value - 1e30).abs() <= 1e15);
+        // beyond the range of f64, the value saturates
+        let overflow = Rational::from_float(f64::MAX).unwrap() * Rational::from_u64(4).unwrap();
+        assert_eq!(rational_to_f64_checked(&overflow), Err(PrecisionWarning { value: f64::MAX }));
+        assert_eq!(rational_to_f64_checked(&-overflow), Err(PrecisionWarning { value: f64::MIN }));
+        // the lossy conversion still produces the finite value, and logs the warning
+        assert_eq!(rational_to_f64_lossy(&huge), warning.value);
+        assert!(PRECISION_WARNING_LOGGED.load(std::sync::atomic::Ordering::Relaxed));
+    }
+
+    #[test]
+    fn util_solver_initializer_builder() {
+        // cargo test util_solver_initializer_builder -- --nocapture
+        let weight = |value: usize| Rational::from_usize(value).unwrap();
+        let build = |policy: DuplicateEdgePolicy| {
+            let mut builder = SolverInitializer::builder().policy(policy);
+            assert_eq!(builder.add_edge(vec![0, 1], weight(3)), 0);
+            assert_eq!(builder.add_edge(vec![1, 2], weight(1)), 1);
+            // the same edge with the incident vertices in another order
+            assert_eq!(builder.add_edge(vec![1, 0], weight(2)), 0);
+            assert_eq!(builder.add_edge(vec![0, 1], weight(5)), 0);
+            builder.build().unwrap()
+        };
+        let initializer = build(DuplicateEdgePolicy::KeepMin);
+        assert_eq!(initializer.vertex_num, 3);
+        assert_eq!(initializer.weighted_edges.len(), 2);
+        assert_eq!(initializer.weighted_edges[0].vertices, vec![0, 1]);
+        assert_eq!(initializer.weighted_edges[0].weight, weight(2));
+        let initializer = build(DuplicateEdgePolicy::KeepMax);
+        assert_eq!(initializer.weighted_edges.len(), 2);
+        assert_eq!(initializer.weighted_edges[0].weight, weight(5));
+        // invalid graphs are still rejected
+        let mut builder = SolverInitializer::builder().vertex_num(4);
+        builder.add_edge(vec![0, 0], weight(1));
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn solver_initializer_degree_sequence() {
+        // cargo test solver_initializer_degree_sequence -- --nocapture
+        use crate::example_codes::CodeCapacityTailoredCode;
+        let initializer = CodeCapacityTailoredCode::new(5, 0.1, 0.1).get_initializer();
+        let degrees = initializer.degree_sequence();
+        assert_eq!(degrees.len(), initializer.vertex_num);
+        let distribution = initializer.edge_size_distribution();
+        println!("{distribution:?}");
+        // Y errors in the bulk flip four stabilizers
+        assert!(distribution[&4] > 0);
+        assert_eq!(distribution.values().sum::<usize>(), initializer.weighted_edges.len());
+        let total_size: usize = distribution.iter().map(|(size, count)| size * count).sum();
+        assert_eq!(degrees.iter().sum::<usize>(), total_size);
+        assert!(initializer.degree_assortativity().is_some());
+        // a path 0 - 1 - 2 always connects the middle vertex to a leaf
+        let weight = Rational::from_usize(1).unwrap();
+        let path = SolverInitializer::new(
+            3,
+            vec![
+                HyperEdge::new(vec![0, 1], weight.clone()),
+                HyperEdge::new(vec![1, 2], weight.clone()),
+            ],
+        );
+        assert_eq!(path.degree_sequence(), vec![1, 2, 1]);
+        assert!((path.degree_assortativity().unwrap() + 1.).abs() < 1e-12);
+        // a single edge has only one degree
+        let single = SolverInitializer::new(2, vec![HyperEdge::new(vec![0, 1], weight)]);
+        assert_eq!(single.degree_assortativity(), None);
+    }
+
+    #[test]
+    fn util_solver_initializer_merge_parallel_edges() {
+        // cargo test util_solver_initializer_merge_parallel_edges -- --nocapture
+        let weight = |value: usize| Rational::from_usize(value).unwrap();
+        let initializer = SolverInitializer::new_with_heralds(
+            3,
+            vec![
+                HyperEdge::new(vec![0, 1], weight(3)),
+                HyperEdge::new(vec![1, 2], weight(1)),
+                HyperEdge::new(vec![1, 0], weight(2)),
+            ],
+            vec![vec![(2, weight(1))]],
+        );
+        let merge = |policy: DuplicateEdgePolicy| {
+            let mut merged = initializer.clone();
+            assert_eq!(merged.merge_parallel_edges(policy), vec![0, 1, 0]);
+            assert_eq!(merged.weighted_edges.len(), 2);
+            assert_eq!(merged.weighted_edges[0].vertices, vec![0, 1]);
+            assert_eq!(merged.weighted_edges[1].weight, weight(1));
+            assert_eq!(merged.heralds, vec![vec![(0, weight(1))]]);
+            merged.sanity_check().unwrap();
+            merged.weighted_edges[0].weight.clone()
+        };
+        assert_eq!(merge(DuplicateEdgePolicy::KeepMin), weight(2));
+        assert_eq!(merge(DuplicateEdgePolicy::KeepMax), weight(3));
+        // two independent errors are more likely to flip the edge than either alone
+        let combined = merge(DuplicateEdgePolicy::CombineProbabilities);
+        assert!(rational_approx_eq(&combined, &exclusive_weight_sum(&weight(3), &weight(2))));
+        assert!(combined < weight(2));
+    }
+
+    #[test]
+    fn util_random_initializer() {
+        // cargo test util_random_initializer -- --nocapture
+        let initializer = random_initializer(20, 50, 4, 123);
+        initializer.sanity_check().unwrap();
+        assert_eq!(initializer.vertex_num, 20);
+        assert_eq!(initializer.weighted_edges.len(), 50);
+        assert!((initializer.weighted_edges.iter()).all(|edge| (1..=4).contains(&edge.vertices.len())));
+        // reproducible from the seed
+        let same = random_initializer(20, 50, 4, 123);
+        let other = random_initializer(20, 50, 4, 124);
+        let summary = |initializer: &SolverInitializer| format!("{:?}", initializer.weighted_edges);
+        assert_eq!(summary(&initializer), summary(&same));
+        assert_ne!(summary(&initializer), summary(&other));
+        // every possible hyperedge
+        let complete = random_initializer(4, 15, 4, 0);
+        complete.sanity_check().unwrap();
+    }
+
+    #[test]
+    fn util_connected_components() {
+        // cargo test util_connected_components -- --nocapture
+        let weight = || Rational::from_usize(1).unwrap();
+        let initializer = SolverInitializer::new(
+            7,
+            vec![
+                HyperEdge::new(vec![0, 2], weight()),
+                HyperEdge::new(vec![4, 5, 6], weight()),
+                HyperEdge::new(vec![2, 3], weight()),
+                HyperEdge::new(vec![5], weight()),
+                HyperEdge::new(vec![6, 1], weight()),
+            ],
+        );
+        assert_eq!(initializer.connected_components(), vec![vec![0, 2, 3], vec![1, 4, 5, 6]]);
+        assert!(!initializer.is_connected());
+        let mut connected = initializer.clone();
+        connected.weighted_edges.push(HyperEdge::new(vec![3, 4], weight()));
+        assert_eq!(connected.connected_components(), vec![vec![0, 1, 2, 3, 4, 5, 6]]);
+        assert!(connected.is_connected());
+    }
+
+    #[test]
+    fn util_output_subgraph_minimize() {
+        // cargo test util_output_subgraph_minimize -- --nocapture
+        use crate::model_hypergraph::ModelHyperGraph;
+        let weight = |value: usize| Rational::from_usize(value).unwrap();
+        let initializer = SolverInitializer::new(
+            5,
+            vec![
+                HyperEdge::new(vec![0, 1], weight(1)),
+                HyperEdge::new(vec![1, 2], weight(1)),
+                HyperEdge::new(vec![2, 0], weight(1)),
+                HyperEdge::new(vec![2, 3], weight(2)),
+                HyperEdge::new(vec![3, 4], weight(0)),
+                HyperEdge::new(vec![3, 4], weight(0)),
+                HyperEdge::new(vec![0, 1, 2, 3], weight(1)),
+            ],
+        );
+        let model_graph = ModelHyperGraph::new(Arc::new(initializer));
+        let sorted = |subgraph: &OutputSubgraph| subgraph.iter().cloned().collect::<BTreeSet<_>>();
+        // the triangle 0-1-2 is a redundant loop of weight 3
+        let mut subgraph = OutputSubgraph::from(vec![3, 0, 1, 2]);
+        subgraph.minimize(&model_graph);
+        assert_eq!(sorted(&subgraph), [3].into());
+        // a loop of zero weight is kept
+        let mut subgraph = OutputSubgraph::from(vec![3, 4, 5]);
+        subgraph.minimize(&model_graph);
+        assert_eq!(sorted(&subgraph), [3, 4, 5].into());
+        // loops may go through hyperedges, and the flipped edges are taken into account:
+        // {0, 3, 6} has an empty syndrome, leaving only edge 1
+        let mut subgraph = OutputSubgraph::new(vec![0, 1, 4], [3, 4, 6].into());
+        subgraph.minimize(&model_graph);
+        assert_eq!(sorted(&subgraph), [1].into());
+        assert!(subgraph.flip_edge_indices.is_empty());
+    }
+
+    #[test]
+    fn util_output_subgraph_iter_sorted() {
+        // cargo test util_output_subgraph_iter_sorted -- --nocapture
+        use crate::mwpf_solver::{SolverSerialJointSingleHair, SolverTrait};
+        let subgraph = OutputSubgraph::new(vec![7, 1, 4, 9], [9, 3].into());
+        assert_eq!(subgraph.iter_sorted().collect::<Vec<_>>(), vec![1, 3, 4, 7]);
+        assert_eq!(subgraph.to_string(), "[1, 3, 4, 7]");
+        // two decodes of the same syndrome give byte-identical outputs
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let initializer = Arc::new(code.get_initializer());
+        let defect_vertices = vec![1, 5, 8, 13];
+        let mut outputs = vec![];
+        for _ in 0..2 {
+            let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+            solver.solve(SyndromePattern::new_vertices(defect_vertices.clone()));
+            let subgraph = solver.subgraph();
+            assert!(initializer.matches_subgraph_syndrome(&subgraph, &defect_vertices));
+            outputs.push((subgraph.to_string(), format!("{subgraph:?}")));
+        }
+        println!("{:?}", outputs[0]);
+        assert_eq!(outputs[0], outputs[1]);
+    }
+
+    #[test]
+    fn util_fixed_point_round_trip() {
+        // cargo test util_fixed_point_round_trip -- --nocapture
+        use num_traits::Signed;
+        let scale = 1000;
+        let max_error = Rational::from_float(0.5).unwrap() / Rational::from_u64(scale).unwrap();
+        for value in [0., 1., -1., 0.1234567, -2.0005, 2.71, 1e-9, 123456.789] {
+            let weight = Rational::from_float(value).unwrap();
+            let fixed = rational_to_fixed(&weight, scale);
+            assert_eq!(fixed, (value * scale as f64).round() as i64);
+            let error = (fixed_to_rational(fixed, scale) - weight).abs();
+            assert!(error <= max_error, "value {value} round-trips to {fixed} with error {error}");
+        }
+        // values that are exactly representable round-trip without error
+        assert_eq!(
+            fixed_to_rational(rational_to_fixed(&fixed_to_rational(-12345, scale), scale), scale),
+            fixed_to_rational(-12345, scale)
+        );
+        // overflow saturates
+        let huge = Rational::from_float(1e30).unwrap();
+        assert_eq!(rational_to_fixed(&huge, scale), i64::MAX);
+        assert_eq!(rational_to_fixed(&-huge, scale), i64::MIN);
+    }
+
+    #[test]
+    fn reorder_vertices_bfs_isomorphic() {
+        // cargo test reorder_vertices_bfs_isomorphic -- --nocapture
+        use crate::mwpf_solver::{SolverSerialJointSingleHair, SolverTrait};
+        let mut code = CodeCapacityColorCode::new(5, 0.1);
+        // scramble the vertices so that the BFS order is different from the original one
+        let mut initializer = code.get_initializer();
+        let vertex_num = initializer.vertex_num;
+        let scramble = |vertex_index: VertexIndex| (vertex_index * 7 + 3) % vertex_num;
+        assert_eq!((0..vertex_num).map(scramble).collect::<BTreeSet<_>>().len(), vertex_num);
+        for edge in initializer.weighted_edges.iter_mut() {
+            edge.vertices = edge.vertices.iter().map(|&vertex_index| scramble(vertex_index)).collect();
+        }
+        let initializer = Arc::new(initializer);
+        let (reordered, permutation) = reorder_vertices_bfs(&initializer);
+        reordered.sanity_check().unwrap();
+        assert_eq!(permutation.iter().cloned().collect::<BTreeSet<_>>().len(), vertex_num);
+        assert_eq!(permutation[0], 0);
+        let reordered = Arc::new(reordered);
+        let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+        let mut reordered_solver = SolverSerialJointSingleHair::new(&reordered, json!({}));
+        for seed in 0..10 {
+            let (syndrome_pattern, _) = code.generate_random_errors(seed);
+            let defect_vertices: Vec<VertexIndex> = syndrome_pattern
+                .defect_vertices
+                .iter()
+                .map(|&vertex_index| scramble(vertex_index))
+                .collect();
+            let reordered_defect_vertices: Vec<VertexIndex> = defect_vertices
+                .iter()
+                .map(|&vertex_index| permutation[vertex_index])
+                .collect();
+            solver.solve(SyndromePattern::new_vertices(defect_vertices.clone()));
+            reordered_solver.solve(SyndromePattern::new_vertices(reordered_defect_vertices.clone()));
+            let (subgraph, weight_range) = solver.subgraph_range();
+            let (reordered_subgraph, reordered_weight_range) = reordered_solver.subgraph_range();
+            assert!(initializer.matches_subgraph_syndrome(&subgraph, &defect_vertices));
+            // edges keep their indices, so the subgraph is valid in both graphs
+            assert!(initializer.matches_subgraph_syndrome(&reordered_subgraph, &defect_vertices));
+            assert!(reordered.matches_subgraph_syndrome(&reordered_subgraph, &reordered_defect_vertices));
+            assert_eq!(weight_range.upper, reordered_weight_range.upper);
+            assert_eq!(weight_range.lower, reordered_weight_range.lower);
+            solver.clear();
+            reordered_solver.clear();
+        }
+    }
+
+    #[test]
+    fn syndrome_pattern_from_str() {
+        // cargo test syndrome_pattern_from_str -- --nocapture
+        let syndrome_pattern: SyndromePattern = "3,29,30".parse().unwrap();
+        assert_eq!(syndrome_pattern.defect_vertices, vec![3, 29, 30]);
+        assert!(syndrome_pattern.erasures.is_empty());
+        let syndrome_pattern: SyndromePattern = "3, 29,30 | e:5,6".parse().unwrap();
+        assert_eq!(syndrome_pattern.defect_vertices, vec![3, 29, 30]);
+        assert_eq!(syndrome_pattern.erasures, vec![5, 6]);
+        let syndrome_pattern: SyndromePattern = "|e:5".parse().unwrap();
+        assert!(syndrome_pattern.defect_vertices.is_empty());
+        assert_eq!(syndrome_pattern.erasures, vec![5]);
+        assert!(SyndromePattern::from_str("").unwrap().defect_vertices.is_empty());
+        // malformed inputs
+        assert_eq!(
+            SyndromePattern::from_str("3,x").unwrap_err(),
+            "invalid defect vertex index `x`"
+        );
+        assert_eq!(
+            SyndromePattern::from_str("3,,4").unwrap_err(),
+            "invalid defect vertex index ``"
+        );
+        assert!(SyndromePattern::from_str("3|5,6").is_err());
+        assert_eq!(
+            SyndromePattern::from_str("3|e:-1").unwrap_err(),
+            "invalid erasure edge index `-1`"
+        );
+    }
+
+    #[test]
+    fn output_subgraph_to_dot() {
+        // cargo test output_subgraph_to_dot -- --nocapture
+        use crate::example_codes::CodeCapacityRepetitionCode;
+        let code = CodeCapacityRepetitionCode::new(5, 0.1);
+        let initializer = std::sync::Arc::new(code.get_initializer());
+        let mut solver = SolverSerialJointSingleHair::new(&initializer, json!({}));
+        // edges: 0: [0, 1], 1: [1, 2], 2: [2, 3], 3: [0], 4: [3]
+        solver.solve(SyndromePattern::new_vertices(vec![1, 2]));
+        let subgraph = solver.subgraph();
+        let dot = subgraph.to_dot(&code.get_model_graph());
+        println!("{dot}");
+        assert!(dot.starts_with("graph subgraph {"));
+        assert!(dot.contains("    v1 -- v2 [label=\"e1\", color=red, penwidth=3];"));
+        assert!(dot.contains("    v0 -- v1 [label=\"e0\"];"));
+        assert!(dot.contains("    e3 [shape=point, xlabel=\"e3\"];"));
+        assert!(dot.contains("    e3 -- v0;"));
+        assert_eq!(dot.matches("color=red").count(), 1);
+    }
 }
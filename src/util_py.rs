@@ -843,6 +843,14 @@ impl PyWeightRange {
     fn set_upper(&mut self, value: PyRational) {
         self.0.upper = value.into();
     }
+    /// the exact `upper - lower`, without rounding to float
+    #[getter]
+    fn get_gap(&self) -> PyRational {
+        self.0.gap().into()
+    }
+    fn is_optimal(&self) -> bool {
+        self.0.is_optimal()
+    }
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
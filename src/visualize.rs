@@ -3,6 +3,7 @@
 //! This module helps visualize the progress of a mwpf module
 //!
 
+use crate::example_codes::InitializerWithPositions;
 use crate::html_export::*;
 use crate::serde::{Deserialize, Serialize};
 use crate::serde_json;
@@ -387,6 +388,15 @@ impl Visualizer {
         })
     }
 
+    /// create a new visualizer using the positions bundled by [`crate::example_codes::ExampleCode::to_initializer_with_positions`]
+    pub fn new_with_initializer_positions(
+        filepath: Option<String>,
+        initializer_with_positions: &InitializerWithPositions,
+        center: bool,
+    ) -> std::io::Result<Self> {
+        Self::new(filepath, initializer_with_positions.positions.clone(), center)
+    }
+
     pub fn incremental_save(&mut self, name: String, value: serde_json::Value) -> std::io::Result<()> {
         if let Some(file) = self.file.as_mut() {
             self.snapshots.push(name.clone());
@@ -415,6 +425,32 @@ impl Visualizer {
         Ok(())
     }
 
+    /// append a snapshot comparing two decoding runs on the same decoding graph: the left run is stored as the
+    /// snapshot itself with `"side": "left"`, so that viewers unaware of the comparison still render it, and the right
+    /// run is nested under the `"right"` key with `"side": "right"`
+    pub fn snapshot_compare(
+        &mut self,
+        name: String,
+        left_algorithms: Vec<&dyn MWPSVisualizer>,
+        right_algorithms: Vec<&dyn MWPSVisualizer>,
+    ) -> std::io::Result<()> {
+        let abbrev = true;
+        let combine = |mwpf_algorithms: Vec<&dyn MWPSVisualizer>, side: &str| {
+            let mut value = json!({});
+            for mwpf_algorithm in mwpf_algorithms.iter() {
+                snapshot_combine_values(&mut value, mwpf_algorithm.snapshot(abbrev), abbrev);
+            }
+            snapshot_fix_missing_fields(&mut value, abbrev);
+            value.as_object_mut().unwrap().insert("side".to_string(), json!(side));
+            value
+        };
+        let mut value = combine(left_algorithms, "left");
+        let right = combine(right_algorithms, "right");
+        value.as_object_mut().unwrap().insert("right".to_string(), right);
+        self.incremental_save(name, value)?;
+        Ok(())
+    }
+
     /// append another snapshot of the mwpf modules, and also update the file in case
     pub fn snapshot(&mut self, name: String, mwpf_algorithm: &impl MWPSVisualizer) -> std::io::Result<()> {
         let abbrev = true;
@@ -473,6 +509,32 @@ impl Visualizer {
         let html_path = self.html_along_json_path();
         self.save_html(&html_path);
     }
+
+    /// write every snapshot as a standalone visualizer file `frame_0001.json`, `frame_0002.json`, ... in `directory`,
+    /// e.g. to feed an external animation tool, together with a `manifest.json` listing the frame names and files in order
+    pub fn save_frames(&mut self, directory: &str) -> std::io::Result<()> {
+        let data = self.get_visualizer_data();
+        let directory = std::path::Path::new(directory);
+        std::fs::create_dir_all(directory)?;
+        let mut frames = vec![];
+        for (index, snapshot) in data["snapshots"].as_array().unwrap().iter().enumerate() {
+            let filename = format!("frame_{:04}.json", index + 1);
+            let frame = json!({
+                "format": data["format"],
+                "version": data["version"],
+                "positions": data["positions"],
+                "snapshots": [snapshot],
+            });
+            std::fs::write(directory.join(&filename), frame.to_string())?;
+            frames.push(json!({ "name": snapshot[0], "file": filename }));
+        }
+        let manifest = json!({
+            "format": data["format"],
+            "version": data["version"],
+            "frames": frames,
+        });
+        std::fs::write(directory.join("manifest.json"), manifest.to_string())
+    }
 }
 
 #[cfg(feature = "python_binding")]
@@ -554,6 +616,11 @@ impl Visualizer {
         let mut file = File::create(path).expect("cannot create HTML file");
         file.write_all(html.as_bytes()).expect("cannot write to HTML file");
     }
+
+    #[pyo3(name = "save_frames")]
+    pub fn save_frames_py(&mut self, directory: String) -> std::io::Result<()> {
+        self.save_frames(&directory)
+    }
 }
 
 const DEFAULT_VISUALIZE_DATA_FOLDER: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/visualize/data/");
@@ -581,4 +648,95 @@ pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::dual_module::*;
+    use crate::dual_module_pq::DualModulePQ;
+    use crate::example_codes::*;
+    use crate::plugin::*;
+    use crate::plugin_single_hair::PluginSingleHair;
+    use crate::plugin_union_find::PluginUnionFind;
+    use crate::primal_module::*;
+    use crate::primal_module_serial::PrimalModuleSerial;
+    use crate::util::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn visualize_snapshot_compare() {
+        // cargo test visualize_snapshot_compare -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let decode = |plugins: PluginVec| {
+            let mut primal_module = PrimalModuleSerial::new_empty(&model_graph.initializer);
+            primal_module.plugins = Arc::new(plugins);
+            let mut dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+            let interface_ptr = DualModuleInterfacePtr::new(model_graph.clone());
+            primal_module.solve(
+                &interface_ptr,
+                Arc::new(SyndromePattern::new_vertices(vec![3, 5, 10, 12])),
+                &mut dual_module,
+            );
+            (interface_ptr, primal_module, dual_module)
+        };
+        let (left_interface, left_primal, left_dual) = decode(vec![]);
+        let (right_interface, right_primal, right_dual) = decode(vec![
+            PluginUnionFind::entry(),
+            PluginSingleHair::entry_with_strategy(RepeatStrategy::Once),
+        ]);
+        let mut visualizer = Visualizer::new(Some(String::new()), code.get_positions(), true).unwrap();
+        visualizer
+            .snapshot_compare(
+                "compare".to_string(),
+                vec![&left_interface, &left_primal, &left_dual],
+                vec![&right_interface, &right_primal, &right_dual],
+            )
+            .unwrap();
+        let data = visualizer.get_visualizer_data();
+        let (name, snapshot) = (&data["snapshots"][0][0], &data["snapshots"][0][1]);
+        assert_eq!(name, "compare");
+        assert_eq!(snapshot["side"], "left");
+        assert_eq!(snapshot["right"]["side"], "right");
+        for side in [snapshot, &snapshot["right"]] {
+            assert!(side["vertices"].is_array() && side["edges"].is_array());
+        }
+        assert_eq!(snapshot["dual_nodes"], left_interface.snapshot(true)["dual_nodes"]);
+        assert_eq!(snapshot["right"]["dual_nodes"], right_interface.snapshot(true)["dual_nodes"]);
+        assert_ne!(snapshot["dual_nodes"], snapshot["right"]["dual_nodes"]);
+    }
+
+    #[test]
+    fn visualize_save_frames() {
+        // cargo test visualize_save_frames -- --nocapture
+        let code = CodeCapacityColorCode::new(7, 0.1);
+        let model_graph = code.get_model_graph();
+        let dual_module = DualModulePQ::new_empty(&model_graph.initializer);
+        let mut visualizer = Visualizer::new(Some(String::new()), code.get_positions(), true).unwrap();
+        for name in ["first", "second", "third"] {
+            visualizer.snapshot(name.to_string(), &dual_module).unwrap();
+        }
+        let directory = tempfile::tempdir().unwrap();
+        visualizer.save_frames(directory.path().to_str().unwrap()).unwrap();
+        let mut filenames: Vec<String> = std::fs::read_dir(directory.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        filenames.sort();
+        assert_eq!(
+            filenames,
+            ["frame_0001.json", "frame_0002.json", "frame_0003.json", "manifest.json"]
+        );
+        let read_json = |filename: &str| -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(directory.path().join(filename)).unwrap()).unwrap()
+        };
+        let manifest = read_json("manifest.json");
+        let data = visualizer.get_visualizer_data();
+        for (index, name) in ["first", "second", "third"].iter().enumerate() {
+            let filename = format!("frame_{:04}.json", index + 1);
+            assert_eq!(manifest["frames"][index], json!({ "name": name, "file": filename }));
+            // each frame is a visualizer file on its own, with a single snapshot
+            let frame = read_json(&filename);
+            assert_eq!(frame["positions"], data["positions"]);
+            assert_eq!(frame["snapshots"], json!([data["snapshots"][index]]));
+        }
+    }
+}